@@ -4,7 +4,7 @@
 //! dispatcher agent compared to the PowerShell baseline.
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use workflow_orchestrator::dispatcher_core::{Strategy, Agent};
+use workflow_orchestrator::dispatcher_core::{AgentRegistry, Strategy};
 
 /// Benchmark: Strategy parsing from strings
 fn bench_strategy_parsing(c: &mut Criterion) {
@@ -34,35 +34,45 @@ fn bench_strategy_parsing(c: &mut Criterion) {
 /// Benchmark: Agent label generation
 fn bench_agent_operations(c: &mut Criterion) {
     let mut group = c.benchmark_group("agent_operations");
+    let registry = AgentRegistry::defaults();
+    let copilot = registry.get("copilot").unwrap();
+    let jules = registry.get("jules").unwrap();
     
     group.bench_function("copilot_label", |b| {
         b.iter(|| {
-            let label = black_box(Agent::Copilot).label();
+            let label = black_box(copilot).label();
             black_box(label);
         });
     });
     
     group.bench_function("jules_label", |b| {
         b.iter(|| {
-            let label = black_box(Agent::Jules).label();
+            let label = black_box(jules).label();
             black_box(label);
         });
     });
     
     group.bench_function("copilot_assignee", |b| {
         b.iter(|| {
-            let assignee = black_box(Agent::Copilot).assignee();
+            let assignee = black_box(copilot).assignee();
             black_box(assignee);
         });
     });
     
     group.bench_function("jules_assignee", |b| {
         b.iter(|| {
-            let assignee = black_box(Agent::Jules).assignee();
+            let assignee = black_box(jules).assignee();
             black_box(assignee);
         });
     });
     
+    group.bench_function("registry_lookup", |b| {
+        b.iter(|| {
+            let agent = black_box(&registry).get(black_box("jules"));
+            black_box(agent);
+        });
+    });
+    
     group.finish();
 }
 
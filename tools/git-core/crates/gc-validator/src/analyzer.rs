@@ -1,7 +1,9 @@
 //! Workflow analyzer with parallel execution
 
 use crate::github::{GitHubClient, WorkflowAnalysis, WorkflowRun};
-use anyhow::Result;
+use crate::run_store::{RunStore, DEFAULT_BACKFILL_DAYS};
+use crate::workflow_security::{self, Rule, SecurityAudit};
+use anyhow::{Context, Result};
 use futures::future::join_all;
 use std::collections::HashMap;
 use tracing::{info, warn};
@@ -34,7 +36,13 @@ pub struct PerformanceReport {
     pub max_duration_seconds: i64,
     pub min_duration_seconds: i64,
     pub slowest_workflows: Vec<(String, i64)>,
+    /// `job / step` with its longest observed duration
+    pub slowest_steps: Vec<(String, i64)>,
     pub parallel_efficiency: f64,
+    pub avg_queue_wait_seconds: f64,
+    pub idle_seconds: i64,
+    pub cache_hits: usize,
+    pub cache_misses: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -43,34 +51,43 @@ pub struct SecurityReport {
     pub outdated_actions: Vec<String>,
     pub unsafe_permissions: Vec<String>,
     pub supply_chain_risks: Vec<String>,
+    pub script_injections: Vec<String>,
+    pub security_score: f64,
 }
 
-/// Run parallel analysis on all workflows
+impl SecurityReport {
+    pub fn from_audit(audit: &SecurityAudit) -> Self {
+        let collect = |rules: &[Rule]| audit.by_rule(rules).map(|f| f.to_string()).collect();
+
+        Self {
+            hardcoded_secrets: collect(&[Rule::HardcodedSecret, Rule::SecretInLogs]),
+            outdated_actions: collect(&[Rule::OutdatedAction]),
+            unsafe_permissions: collect(&[Rule::BroadPermissions, Rule::MissingPermissions]),
+            supply_chain_risks: collect(&[Rule::UnpinnedAction, Rule::PrTargetCheckout]),
+            script_injections: collect(&[Rule::ScriptInjection]),
+            security_score: audit.score(),
+        }
+    }
+}
+
+/// Run analysis on the most recent stored runs
 pub async fn run_analysis(
-    client: &GitHubClient,
+    store: &RunStore,
     analysis_types: &[String],
     include_success: bool,
     output_format: &str,
 ) -> Result<AnalysisResult> {
-    info!("🔍 Starting parallel workflow analysis...");
+    info!("🔍 Starting workflow analysis...");
     info!("📋 Analysis types: {:?}", analysis_types);
 
-    // Fetch all runs in parallel batches
-    let runs = client.get_workflow_runs(50).await?;
-
-    // Filter runs based on criteria
-    let runs_to_analyze: Vec<WorkflowRun> = if include_success {
-        runs
-    } else {
-        runs.into_iter()
-            .filter(|r| r.conclusion.as_deref() != Some("success"))
-            .collect()
-    };
-
-    info!("📊 Analyzing {} workflow runs...", runs_to_analyze.len());
+    // Latest runs from the local store (already analyzed during sync)
+    let analyses: Vec<WorkflowAnalysis> = store
+        .recent_analyses(50)?
+        .into_iter()
+        .filter(|a| include_success || a.run.conclusion.as_deref() != Some("success"))
+        .collect();
 
-    // Parallel analysis
-    let analyses = client.analyze_runs_parallel(runs_to_analyze).await?;
+    info!("📊 Analyzing {} workflow runs...", analyses.len());
 
     // Build result
     let result = build_analysis_result(&analyses, analysis_types).await;
@@ -83,6 +100,7 @@ pub async fn run_analysis(
                 "successful": result.successful,
                 "failed": result.failed,
                 "errors": result.errors.len(),
+                "security_score": result.security.security_score,
                 "recommendations": result.recommendations,
             }))?);
         }
@@ -98,7 +116,9 @@ pub async fn run_analysis(
 }
 
 /// Health check for all workflows
-pub async fn health_check(client: &GitHubClient, quick: bool) -> Result<()> {
+///
+/// The deep check reads runs from the store (required unless `quick`).
+pub async fn health_check(client: &GitHubClient, store: Option<&RunStore>, quick: bool) -> Result<()> {
     info!("🏥 Running workflow health check...");
 
     let workflows = client.get_workflows().await?;
@@ -115,8 +135,9 @@ pub async fn health_check(client: &GitHubClient, quick: bool) -> Result<()> {
             println!("{} {} ({})", status_icon, wf.name, wf.path);
         }
     } else {
-        // Deep check - analyze recent runs for each workflow
-        let runs = client.get_workflow_runs(100).await?;
+        // Deep check - stored runs of the backfill window for each workflow
+        let store = store.context("Run store required for the deep health check")?;
+        let runs = store.runs_since(chrono::Utc::now() - chrono::Duration::days(DEFAULT_BACKFILL_DAYS))?;
 
         // Group runs by workflow
         let mut workflow_runs: HashMap<u64, Vec<&WorkflowRun>> = HashMap::new();
@@ -217,22 +238,36 @@ async fn build_analysis_result(
         },
         max_duration_seconds: durations.iter().max().copied().unwrap_or(0),
         min_duration_seconds: durations.iter().min().copied().unwrap_or(0),
-        slowest_workflows: analyses.iter()
-            .filter_map(|a| a.duration_seconds.map(|d| (a.run.name.clone(), d)))
-            .collect::<Vec<_>>()
-            .into_iter()
-            .take(5)
-            .collect(),
+        slowest_workflows: slowest(
+            analyses.iter().filter_map(|a| a.duration_seconds.map(|d| (a.run.name.clone(), d))),
+        ),
+        slowest_steps: slowest(analyses.iter().flat_map(|a| {
+            a.timeline.steps.iter().map(|s| (format!("{} / {}", s.job, s.step), s.seconds))
+        })),
         parallel_efficiency: calculate_parallel_efficiency(analyses),
+        avg_queue_wait_seconds: {
+            let total: i64 = analyses.iter().map(|a| a.timeline.total_queue_wait_seconds).sum();
+            let jobs: usize = analyses.iter().map(|a| a.timeline.queued_jobs).sum();
+            if jobs == 0 { 0.0 } else { total as f64 / jobs as f64 }
+        },
+        idle_seconds: analyses.iter()
+            .flat_map(|a| a.timeline.idle_gaps.iter())
+            .map(|g| g.seconds)
+            .sum(),
+        cache_hits: analyses.iter().map(|a| a.cache.hits).sum(),
+        cache_misses: analyses.iter().map(|a| a.cache.misses).sum(),
     };
 
-    // Security analysis (basic)
-    let security = SecurityReport {
-        hardcoded_secrets: Vec::new(), // Would need file access
-        outdated_actions: Vec::new(),  // Would need to check action versions
-        unsafe_permissions: Vec::new(),
-        supply_chain_risks: Vec::new(),
+    // Security analysis (static, local workflow files)
+    let audit = if analysis_types.iter().any(|t| t == "security") {
+        workflow_security::audit_dir(workflow_security::DEFAULT_WORKFLOWS_DIR).unwrap_or_else(|e| {
+            warn!("Skipping workflow security audit: {}", e);
+            SecurityAudit::default()
+        })
+    } else {
+        SecurityAudit::default()
     };
+    let security = SecurityReport::from_audit(&audit);
 
     // Generate recommendations
     let mut recommendations = Vec::new();
@@ -256,6 +291,45 @@ async fn build_analysis_result(
         );
     }
 
+    if performance.avg_queue_wait_seconds > 60.0 {
+        recommendations.push(format!(
+            "⏳ Jobs wait {:.0}s on average for a runner. Check runner capacity and concurrency limits.",
+            performance.avg_queue_wait_seconds
+        ));
+    }
+
+    if performance.cache_misses > performance.cache_hits {
+        recommendations.push(format!(
+            "💾 Cache misses ({}) outnumber hits ({}). Stabilize cache keys and add restore-keys.",
+            performance.cache_misses, performance.cache_hits
+        ));
+    }
+
+    if let Some((step, seconds)) = performance.slowest_steps.first() {
+        if *seconds > 300 {
+            recommendations.push(format!(
+                "🐢 Slowest step: {} ({}s). Consider splitting or caching it.",
+                step, seconds
+            ));
+        }
+    }
+
+    let critical = security.hardcoded_secrets.len() + security.script_injections.len()
+        + audit.by_rule(&[Rule::PrTargetCheckout]).count();
+    if critical > 0 {
+        recommendations.push(format!(
+            "🔐 {} secret exposure / injection finding(s) in workflow files. Run `audit` for details.",
+            critical
+        ));
+    }
+
+    if !security.supply_chain_risks.is_empty() {
+        recommendations.push(format!(
+            "📌 {} action reference(s) not pinned to a commit SHA.",
+            security.supply_chain_risks.len()
+        ));
+    }
+
     if cancelled > 0 {
         recommendations.push(format!(
             "⚠️ {} runs were cancelled. Check for timeout issues or manual cancellations.",
@@ -276,31 +350,32 @@ async fn build_analysis_result(
 }
 
 fn calculate_parallel_efficiency(analyses: &[WorkflowAnalysis]) -> f64 {
-    // Calculate how well jobs are parallelized
-    // Efficiency = (sum of job durations) / (total workflow duration * num_jobs)
-
-    let mut total_efficiency = 0.0;
-    let mut count = 0;
+    // Average over runs of critical path / wall clock (see timeline.rs):
+    // queue waits and idle gaps between jobs lower the score
+    let efficiencies: Vec<f64> = analyses.iter()
+        .filter(|a| a.timeline.wall_clock_seconds > 0)
+        .map(|a| a.timeline.parallel_efficiency)
+        .collect();
 
-    for analysis in analyses {
-        if let Some(workflow_duration) = analysis.duration_seconds {
-            let job_count = analysis.jobs.len();
-            if job_count > 1 && workflow_duration > 0 {
-                // Estimate: if all jobs run in parallel, efficiency = 1.0
-                // if all sequential, efficiency = 1/num_jobs
-                let avg_job_time = workflow_duration as f64 / job_count as f64;
-                let efficiency = avg_job_time / workflow_duration as f64 * job_count as f64;
-                total_efficiency += efficiency.min(1.0);
-                count += 1;
-            }
-        }
+    if efficiencies.is_empty() {
+        1.0
+    } else {
+        efficiencies.iter().sum::<f64>() / efficiencies.len() as f64
     }
+}
 
-    if count > 0 {
-        total_efficiency / count as f64
-    } else {
-        1.0
+/// Top 5 by duration, keeping the longest duration per name
+fn slowest(durations: impl Iterator<Item = (String, i64)>) -> Vec<(String, i64)> {
+    let mut longest: HashMap<String, i64> = HashMap::new();
+    for (name, seconds) in durations {
+        let entry = longest.entry(name).or_insert(seconds);
+        *entry = (*entry).max(seconds);
     }
+
+    let mut sorted: Vec<(String, i64)> = longest.into_iter().collect();
+    sorted.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    sorted.truncate(5);
+    sorted
 }
 
 fn print_terminal_report(result: &AnalysisResult) {
@@ -317,6 +392,8 @@ fn print_terminal_report(result: &AnalysisResult) {
         result.performance.min_duration_seconds);
     println!("║   Parallel Efficiency: {:>5.1}%                                  ║",
         result.performance.parallel_efficiency * 100.0);
+    println!("║ 🔐 Security Score: {:>5.1}%                                      ║",
+        result.security.security_score);
     println!("╠════════════════════════════════════════════════════════════════╣");
 
     if !result.recommendations.is_empty() {
@@ -345,8 +422,37 @@ fn print_markdown_report(result: &AnalysisResult) {
     println!("| Average Duration | {:.1}s |", result.performance.avg_duration_seconds);
     println!("| Max Duration | {}s |", result.performance.max_duration_seconds);
     println!("| Parallel Efficiency | {:.1}% |", result.performance.parallel_efficiency * 100.0);
+    println!("| Avg Queue Wait | {:.1}s |", result.performance.avg_queue_wait_seconds);
+    println!("| Idle Time | {}s |", result.performance.idle_seconds);
+    println!("| Cache | {} hit / {} miss |", result.performance.cache_hits, result.performance.cache_misses);
     println!();
 
+    if !result.performance.slowest_steps.is_empty() {
+        println!("### Slowest Steps\n");
+        for (step, seconds) in &result.performance.slowest_steps {
+            println!("- {} ({}s)", step, seconds);
+        }
+        println!();
+    }
+
+    println!("## Security\n");
+    println!("**Score:** {:.1}%\n", result.security.security_score);
+    for (title, items) in [
+        ("Hardcoded / Leaked Secrets", &result.security.hardcoded_secrets),
+        ("Script Injection", &result.security.script_injections),
+        ("Unsafe Permissions", &result.security.unsafe_permissions),
+        ("Supply Chain", &result.security.supply_chain_risks),
+        ("Outdated Actions", &result.security.outdated_actions),
+    ] {
+        if !items.is_empty() {
+            println!("### {}\n", title);
+            for item in items {
+                println!("- {}", item);
+            }
+            println!();
+        }
+    }
+
     if !result.recommendations.is_empty() {
         println!("## Recommendations\n");
         for rec in &result.recommendations {
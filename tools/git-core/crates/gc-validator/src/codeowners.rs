@@ -0,0 +1,228 @@
+//! # CODEOWNERS Parser
+//!
+//! Parses `.github/CODEOWNERS` and resolves the owners of a changed path
+//! using GitHub semantics: patterns follow gitignore rules and the **last**
+//! matching line wins.
+//!
+//! ```text
+//! # Comment
+//! *                 @org/maintainers
+//! /docs/            @docs-team-lead
+//! *.rs              @rustacean
+//! tools/**/ci.yml   @org/devops
+//! /vendor/          # no owners: explicitly unowned
+//! ```
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::path::Path;
+
+/// A single CODEOWNERS line
+#[derive(Debug, Clone)]
+pub struct CodeOwnerRule {
+    pub pattern: String,
+    pub owners: Vec<String>,
+    regex: Regex,
+}
+
+impl CodeOwnerRule {
+    /// Check whether a repository-relative path matches this rule
+    pub fn matches(&self, path: &str) -> bool {
+        self.regex.is_match(path.trim_start_matches('/'))
+    }
+}
+
+/// Parsed CODEOWNERS file
+#[derive(Debug, Clone, Default)]
+pub struct CodeOwners {
+    rules: Vec<CodeOwnerRule>,
+}
+
+impl CodeOwners {
+    /// Load and parse a CODEOWNERS file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = std::fs::read_to_string(path.as_ref())
+            .with_context(|| format!("Failed to read {}", path.as_ref().display()))?;
+        Self::parse(&content)
+    }
+
+    /// Parse CODEOWNERS content
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut rules = Vec::new();
+
+        for (index, line) in content.lines().enumerate() {
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut parts = line.split_whitespace();
+            let Some(pattern) = parts.next() else {
+                continue;
+            };
+
+            let regex = Regex::new(&pattern_to_regex(pattern))
+                .with_context(|| format!("Invalid CODEOWNERS pattern on line {}: {}", index + 1, pattern))?;
+
+            rules.push(CodeOwnerRule {
+                pattern: pattern.to_string(),
+                owners: parts.map(|o| o.to_string()).collect(),
+                regex,
+            });
+        }
+
+        Ok(Self { rules })
+    }
+
+    /// Rule that decides the owners of a path (the last matching one)
+    pub fn rule_for(&self, path: &str) -> Option<&CodeOwnerRule> {
+        self.rules.iter().rev().find(|rule| rule.matches(path))
+    }
+
+    /// Owners of a path (last matching rule wins)
+    ///
+    /// Returns `None` when no rule matches, and an empty slice when the
+    /// matching rule explicitly lists no owners.
+    pub fn owners_for(&self, path: &str) -> Option<&[String]> {
+        self.rule_for(path).map(|rule| rule.owners.as_slice())
+    }
+}
+
+/// Split an `@org/team` owner into `(org, team)`
+pub fn parse_team(owner: &str) -> Option<(&str, &str)> {
+    owner.strip_prefix('@')?.split_once('/')
+}
+
+/// Remove a trailing `# comment` (an escaped `\#` is kept)
+fn strip_comment(line: &str) -> &str {
+    let bytes = line.as_bytes();
+    for (i, &b) in bytes.iter().enumerate() {
+        if b == b'#' && (i == 0 || bytes[i - 1] != b'\\') {
+            return &line[..i];
+        }
+    }
+    line
+}
+
+/// Translate a gitignore-style pattern into an anchored regex
+fn pattern_to_regex(pattern: &str) -> String {
+    let dir_only = pattern.ends_with('/');
+    let trimmed = pattern.trim_end_matches('/');
+
+    // A slash anywhere but the end anchors the pattern to the repo root
+    let anchored = trimmed.contains('/');
+    let glob = trimmed.trim_start_matches('/');
+
+    let mut regex = String::from(if anchored { "^" } else { "^(?:.*/)?" });
+
+    let chars: Vec<char> = glob.chars().collect();
+    let mut wildcard_in_segment = false;
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                if chars.get(i + 2) == Some(&'/') {
+                    regex.push_str("(?:.*/)?");
+                    i += 3;
+                } else {
+                    regex.push_str(".*");
+                    wildcard_in_segment = true;
+                    i += 2;
+                }
+                continue;
+            }
+            '*' => {
+                regex.push_str("[^/]*");
+                wildcard_in_segment = true;
+            }
+            '?' => {
+                regex.push_str("[^/]");
+                wildcard_in_segment = true;
+            }
+            '/' => {
+                regex.push('/');
+                wildcard_in_segment = false;
+            }
+            '\\' if i + 1 < chars.len() => {
+                i += 1;
+                regex.push_str(&regex::escape(&chars[i].to_string()));
+            }
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+        i += 1;
+    }
+
+    // Directories own everything beneath them; `docs/*` only matches direct children
+    regex.push_str(if dir_only {
+        "/.*$"
+    } else if wildcard_in_segment {
+        "$"
+    } else {
+        "(?:/.*)?$"
+    });
+    regex
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"
+# Default owners
+*                   @org/maintainers
+
+/docs/              @docs-lead      # docs
+*.rs                @rustacean
+tools/**/ci.yml     @org/devops
+/vendor/
+"#;
+
+    #[test]
+    fn test_last_match_wins() {
+        let owners = CodeOwners::parse(SAMPLE).unwrap();
+
+        assert_eq!(owners.owners_for("README.md").unwrap(), ["@org/maintainers"]);
+        assert_eq!(owners.owners_for("docs/guide.md").unwrap(), ["@docs-lead"]);
+        assert_eq!(owners.owners_for("docs/src/lib.rs").unwrap(), ["@rustacean"]);
+        assert_eq!(owners.owners_for("tools/a/b/ci.yml").unwrap(), ["@org/devops"]);
+        assert!(owners.owners_for("vendor/lib.js").unwrap().is_empty());
+        assert_eq!(owners.rule_for("docs/guide.md").unwrap().pattern, "/docs/");
+    }
+
+    #[test]
+    fn test_anchoring() {
+        let owners = CodeOwners::parse("/docs/ @a\nbuild/ @b\nsrc/app @c").unwrap();
+
+        assert_eq!(owners.owners_for("docs/x.md").unwrap(), ["@a"]);
+        assert!(owners.owners_for("nested/docs/x.md").is_none());
+
+        // Trailing slash only: matches at any depth
+        assert_eq!(owners.owners_for("nested/build/out.o").unwrap(), ["@b"]);
+
+        // Inner slash anchors to root
+        assert_eq!(owners.owners_for("src/app/main.rs").unwrap(), ["@c"]);
+        assert!(owners.owners_for("lib/src/app/main.rs").is_none());
+    }
+
+    #[test]
+    fn test_wildcards_stay_in_segment() {
+        let owners = CodeOwners::parse("/src/*.rs @a").unwrap();
+
+        assert!(owners.owners_for("src/lib.rs").is_some());
+        assert!(owners.owners_for("src/nested/lib.rs").is_none());
+
+        let owners = CodeOwners::parse("docs/* @a\n/apps @b").unwrap();
+
+        assert!(owners.owners_for("docs/a.md").is_some());
+        assert!(owners.owners_for("docs/a/b.md").is_none());
+        // Without a wildcard the last segment still owns its descendants
+        assert!(owners.owners_for("apps/web/index.ts").is_some());
+    }
+
+    #[test]
+    fn test_parse_team() {
+        assert_eq!(parse_team("@org/devops"), Some(("org", "devops")));
+        assert_eq!(parse_team("@user"), None);
+        assert_eq!(parse_team("dev@example.com"), None);
+    }
+}
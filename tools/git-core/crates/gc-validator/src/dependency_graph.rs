@@ -0,0 +1,364 @@
+//! # Dependency Graph - Issue Ordering for the Dispatcher
+//!
+//! Issues declare prerequisites in their body:
+//!
+//! ```text
+//! Blocked by #12
+//! Depends on #14, #15 and #16
+//!
+//! - [x] #20          (done: ignored)
+//! - [ ] #21          (open task: prerequisite)
+//! ```
+//!
+//! The dispatcher builds a graph over the open issues it could hand out,
+//! skips every issue with an open prerequisite, and dispatches the rest in
+//! topological order. Ties are broken by priority label, then by how much
+//! work an issue unblocks, then by issue number. Cycles are reported
+//! because their members can never become ready on their own.
+//!
+//! ```text
+//! Priority labels:  P0 / priority:critical  →  0
+//!                   P1 / priority:high      →  1
+//!                   P2 / priority:medium    →  2  (default)
+//!                   P3 / priority:low       →  3
+//! ```
+
+use regex::Regex;
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap};
+use std::sync::OnceLock;
+
+/// Rank of issues without a priority label
+pub const DEFAULT_PRIORITY: u8 = 2;
+
+/// Issue numbers this issue body declares as prerequisites
+pub fn parse_dependencies(body: &str) -> Vec<u64> {
+    static KEYWORD_RE: OnceLock<Regex> = OnceLock::new();
+    static TASK_RE: OnceLock<Regex> = OnceLock::new();
+    static REF_RE: OnceLock<Regex> = OnceLock::new();
+
+    let keyword_re = KEYWORD_RE.get_or_init(|| {
+        Regex::new(r"(?i)\b(?:blocked\s+by|depends\s+on)\s*:?\s*((?:#\d+(?:\s*,\s*|\s+and\s+|\s+)?)+)")
+            .expect("valid dependency regex")
+    });
+    let task_re = TASK_RE.get_or_init(|| {
+        Regex::new(r"(?m)^\s*[-*+]\s+\[ \]\s+(.*)$").expect("valid task regex")
+    });
+    // Same-repo references only (`other/repo#3` is not ours)
+    let ref_re = REF_RE.get_or_init(|| Regex::new(r"(?:^|[^\w/])#(\d+)\b").expect("valid ref regex"));
+
+    let mut deps = BTreeSet::new();
+
+    for caps in keyword_re.captures_iter(body).chain(task_re.captures_iter(body)) {
+        for r in ref_re.captures_iter(&caps[1]) {
+            if let Ok(n) = r[1].parse() {
+                deps.insert(n);
+            }
+        }
+    }
+
+    deps.into_iter().collect()
+}
+
+/// Priority rank from labels (0 = most urgent)
+pub fn priority_rank(labels: &[String]) -> u8 {
+    labels
+        .iter()
+        .filter_map(|label| {
+            let label = label.to_lowercase();
+            if let Some(level) = label.strip_prefix("priority") {
+                return match level.trim_start_matches([':', '-', '/', ' ']) {
+                    "p0" | "0" | "critical" | "urgent" => Some(0),
+                    "p1" | "1" | "high" => Some(1),
+                    "p2" | "2" | "medium" | "normal" => Some(2),
+                    "p3" | "3" | "low" => Some(3),
+                    _ => None,
+                };
+            }
+
+            match label.as_str() {
+                "p0" => Some(0),
+                "p1" => Some(1),
+                "p2" => Some(2),
+                "p3" => Some(3),
+                _ => None,
+            }
+        })
+        .min()
+        .unwrap_or(DEFAULT_PRIORITY)
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    priority: u8,
+    deps: Vec<u64>,
+}
+
+/// Outcome of ordering the candidate issues
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct DependencyPlan {
+    /// Issues with no open prerequisite, in dispatch order
+    pub ready: Vec<u64>,
+    /// Issues held back, with the open prerequisites blocking them
+    pub blocked: BTreeMap<u64, Vec<u64>>,
+    /// Dependency cycles (each sorted by issue number)
+    pub cycles: Vec<Vec<u64>>,
+}
+
+/// Prerequisite graph over open, dispatchable issues
+#[derive(Debug, Clone, Default)]
+pub struct DependencyGraph {
+    nodes: BTreeMap<u64, Node>,
+}
+
+impl DependencyGraph {
+    /// Create an empty graph
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an issue with its priority rank and prerequisites
+    pub fn add_issue(&mut self, number: u64, priority: u8, deps: Vec<u64>) {
+        let deps = deps.into_iter().filter(|&d| d != number).collect();
+        self.nodes.insert(number, Node { priority, deps });
+    }
+
+    /// Prerequisites that are not part of the graph (their state must be looked up)
+    pub fn external_dependencies(&self) -> Vec<u64> {
+        let external: BTreeSet<u64> = self
+            .nodes
+            .values()
+            .flat_map(|n| n.deps.iter().copied())
+            .filter(|d| !self.nodes.contains_key(d))
+            .collect();
+        external.into_iter().collect()
+    }
+
+    /// Order the graph
+    ///
+    /// Every issue in the graph is open; `is_open_external` answers for
+    /// prerequisites outside it.
+    pub fn plan(&self, is_open_external: impl Fn(u64) -> bool) -> DependencyPlan {
+        let mut plan = DependencyPlan::default();
+
+        for (&number, node) in &self.nodes {
+            let open: Vec<u64> = node
+                .deps
+                .iter()
+                .copied()
+                .filter(|&d| self.nodes.contains_key(&d) || is_open_external(d))
+                .collect();
+            if !open.is_empty() {
+                plan.blocked.insert(number, open);
+            }
+        }
+
+        let unblocks = self.transitive_dependents();
+
+        // Kahn's algorithm over in-graph edges, most urgent first
+        let mut indegree: HashMap<u64, usize> = self.nodes.keys().map(|&n| (n, 0)).collect();
+        let mut dependents: HashMap<u64, Vec<u64>> = HashMap::new();
+        for (&number, node) in &self.nodes {
+            for &dep in node.deps.iter().filter(|d| self.nodes.contains_key(d)) {
+                *indegree.get_mut(&number).expect("node indegree") += 1;
+                dependents.entry(dep).or_default().push(number);
+            }
+        }
+
+        let key = |n: u64| {
+            Reverse((
+                self.nodes[&n].priority,
+                Reverse(unblocks.get(&n).copied().unwrap_or(0)),
+                n,
+            ))
+        };
+
+        let mut heap: BinaryHeap<_> = indegree
+            .iter()
+            .filter(|(_, &d)| d == 0)
+            .map(|(&n, _)| key(n))
+            .collect();
+
+        while let Some(Reverse((_, _, number))) = heap.pop() {
+            if !plan.blocked.contains_key(&number) {
+                plan.ready.push(number);
+            }
+            for &next in dependents.get(&number).into_iter().flatten() {
+                let d = indegree.get_mut(&next).expect("node indegree");
+                *d -= 1;
+                if *d == 0 {
+                    heap.push(key(next));
+                }
+            }
+        }
+
+        plan.cycles = self.cycles();
+        plan
+    }
+
+    /// Number of issues (transitively) waiting on each issue
+    fn transitive_dependents(&self) -> HashMap<u64, usize> {
+        let mut dependents: HashMap<u64, Vec<u64>> = HashMap::new();
+        for (&number, node) in &self.nodes {
+            for &dep in &node.deps {
+                dependents.entry(dep).or_default().push(number);
+            }
+        }
+
+        self.nodes
+            .keys()
+            .map(|&start| {
+                let mut seen = BTreeSet::new();
+                let mut stack = vec![start];
+                while let Some(n) = stack.pop() {
+                    for &d in dependents.get(&n).into_iter().flatten() {
+                        if d != start && seen.insert(d) {
+                            stack.push(d);
+                        }
+                    }
+                }
+                (start, seen.len())
+            })
+            .collect()
+    }
+
+    /// Strongly connected components with more than one issue (Tarjan)
+    fn cycles(&self) -> Vec<Vec<u64>> {
+        struct Tarjan<'a> {
+            graph: &'a DependencyGraph,
+            index: usize,
+            indices: HashMap<u64, usize>,
+            lowlink: HashMap<u64, usize>,
+            stack: Vec<u64>,
+            on_stack: BTreeSet<u64>,
+            cycles: Vec<Vec<u64>>,
+        }
+
+        impl Tarjan<'_> {
+            fn visit(&mut self, v: u64) {
+                self.indices.insert(v, self.index);
+                self.lowlink.insert(v, self.index);
+                self.index += 1;
+                self.stack.push(v);
+                self.on_stack.insert(v);
+
+                let deps = self.graph.nodes[&v].deps.clone();
+                for w in deps.into_iter().filter(|w| self.graph.nodes.contains_key(w)) {
+                    if !self.indices.contains_key(&w) {
+                        self.visit(w);
+                        let low = self.lowlink[&v].min(self.lowlink[&w]);
+                        self.lowlink.insert(v, low);
+                    } else if self.on_stack.contains(&w) {
+                        let low = self.lowlink[&v].min(self.indices[&w]);
+                        self.lowlink.insert(v, low);
+                    }
+                }
+
+                if self.lowlink[&v] == self.indices[&v] {
+                    let mut component = Vec::new();
+                    while let Some(w) = self.stack.pop() {
+                        self.on_stack.remove(&w);
+                        component.push(w);
+                        if w == v {
+                            break;
+                        }
+                    }
+                    if component.len() > 1 {
+                        component.sort_unstable();
+                        self.cycles.push(component);
+                    }
+                }
+            }
+        }
+
+        let mut tarjan = Tarjan {
+            graph: self,
+            index: 0,
+            indices: HashMap::new(),
+            lowlink: HashMap::new(),
+            stack: Vec::new(),
+            on_stack: BTreeSet::new(),
+            cycles: Vec::new(),
+        };
+
+        for &n in self.nodes.keys() {
+            if !tarjan.indices.contains_key(&n) {
+                tarjan.visit(n);
+            }
+        }
+
+        let mut cycles = tarjan.cycles;
+        cycles.sort();
+        cycles
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_dependencies() {
+        let body = "Blocked by #12\n\
+                    This depends on: #14, #15 and #16.\n\
+                    Related to #99 (not a dependency)\n\
+                    See other/repo#7\n\
+                    - [x] #20 done\n\
+                    - [ ] #21 parser\n\
+                    * [ ] Wire up #22 and #23\n";
+
+        assert_eq!(parse_dependencies(body), vec![12, 14, 15, 16, 21, 22, 23]);
+        assert!(parse_dependencies("Fixes #3").is_empty());
+    }
+
+    #[test]
+    fn test_priority_rank() {
+        assert_eq!(priority_rank(&labels(&["bug", "P0"])), 0);
+        assert_eq!(priority_rank(&labels(&["priority: high"])), 1);
+        assert_eq!(priority_rank(&labels(&["priority-low", "priority/medium"])), 2);
+        assert_eq!(priority_rank(&labels(&["priority:low"])), 3);
+        assert_eq!(priority_rank(&labels(&["enhancement"])), DEFAULT_PRIORITY);
+    }
+
+    #[test]
+    fn test_plan_skips_blocked_and_orders_ready() {
+        let mut graph = DependencyGraph::new();
+        graph.add_issue(1, 2, vec![]);
+        graph.add_issue(2, 2, vec![1]); // waits on #1
+        graph.add_issue(3, 1, vec![]); // higher priority
+        graph.add_issue(4, 2, vec![50]); // external, still open
+        graph.add_issue(5, 2, vec![60]); // external, closed
+        graph.add_issue(6, 2, vec![]);
+
+        assert_eq!(graph.external_dependencies(), vec![50, 60]);
+
+        let plan = graph.plan(|n| n == 50);
+
+        // #3 by priority, then #1 because it unblocks #2, then by number
+        assert_eq!(plan.ready, vec![3, 1, 5, 6]);
+        assert_eq!(plan.blocked[&2], vec![1]);
+        assert_eq!(plan.blocked[&4], vec![50]);
+        assert!(plan.cycles.is_empty());
+    }
+
+    #[test]
+    fn test_plan_reports_cycles() {
+        let mut graph = DependencyGraph::new();
+        graph.add_issue(1, 2, vec![2]);
+        graph.add_issue(2, 2, vec![3]);
+        graph.add_issue(3, 2, vec![1]);
+        graph.add_issue(4, 2, vec![4]); // self reference is ignored
+        graph.add_issue(5, 2, vec![1]);
+
+        let plan = graph.plan(|_| false);
+
+        assert_eq!(plan.cycles, vec![vec![1, 2, 3]]);
+        assert_eq!(plan.ready, vec![4]);
+        assert!(plan.blocked.contains_key(&5));
+    }
+}
@@ -0,0 +1,433 @@
+//! # Change Detectors - Test and Scope Heuristics
+//!
+//! Language-aware heuristics used by Guardian to decide whether a PR
+//! includes tests and whether it stays within a single package.
+//!
+//! ## Test Detectors
+//!
+//! ```text
+//! TestDirectory:  any directory named tests/, test/, __tests__/, spec/, specs/
+//! Rust:           *_test.rs, test_*.rs, or a diff hunk adding #[cfg(test)] / #[test]
+//! Go:             *_test.go
+//! JavaScript/TS:  *.test.{js,ts,...}, *.spec.{js,ts,...}
+//! Python:         test_*.py, *_test.py, conftest.py
+//! ```
+//!
+//! Custom detectors implement [`TestDetector`] and are registered with
+//! `GuardianCore::with_test_detector`.
+//!
+//! ## Scope Resolution
+//!
+//! A file's scope is the deepest package that contains it: a Cargo or
+//! package.json workspace member, or the nearest directory holding a
+//! manifest (`Cargo.toml`, `package.json`, `go.mod`, `pyproject.toml`).
+//! Files outside any package fall back to their top-level directory.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// Manifests that mark a directory as a package root
+const MANIFESTS: &[&str] = &["Cargo.toml", "package.json", "go.mod", "pyproject.toml"];
+
+const TEST_DIRS: &[&str] = &["tests", "test", "__tests__", "spec", "specs"];
+const JS_EXTENSIONS: &[&str] = &["js", "jsx", "ts", "tsx", "mjs", "cjs", "mts", "cts"];
+
+/// A changed file with its unified diff (when the API provides one)
+#[derive(Debug, Clone, Default)]
+pub struct ChangedFile {
+    pub path: String,
+    pub patch: Option<String>,
+}
+
+impl ChangedFile {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            patch: None,
+        }
+    }
+
+    pub fn with_patch(mut self, patch: impl Into<String>) -> Self {
+        self.patch = Some(patch.into());
+        self
+    }
+
+    fn file_name(&self) -> &str {
+        self.path.rsplit('/').next().unwrap_or(&self.path)
+    }
+
+    fn extension(&self) -> Option<&str> {
+        let name = self.file_name();
+        name.rsplit_once('.').map(|(_, ext)| ext)
+    }
+
+    fn stem(&self) -> &str {
+        let name = self.file_name();
+        name.rsplit_once('.').map_or(name, |(stem, _)| stem)
+    }
+
+    /// Lines added by the diff (without the leading `+`)
+    fn added_lines(&self) -> impl Iterator<Item = &str> {
+        self.patch
+            .as_deref()
+            .unwrap_or_default()
+            .lines()
+            .filter(|l| l.starts_with('+') && !l.starts_with("+++"))
+            .map(|l| l[1..].trim())
+    }
+}
+
+/// Decides whether a changed file adds or modifies tests
+pub trait TestDetector: Send + Sync {
+    /// Short name used in debug logs
+    fn name(&self) -> &'static str;
+
+    /// Whether this file counts as test coverage
+    fn is_test(&self, file: &ChangedFile) -> bool;
+}
+
+/// Files inside a conventional test directory
+pub struct TestDirectoryDetector;
+
+impl TestDetector for TestDirectoryDetector {
+    fn name(&self) -> &'static str {
+        "test-directory"
+    }
+
+    fn is_test(&self, file: &ChangedFile) -> bool {
+        let mut segments: Vec<&str> = file.path.split('/').collect();
+        segments.pop(); // file name
+        segments.iter().any(|s| TEST_DIRS.contains(s))
+    }
+}
+
+/// Rust test files and `#[cfg(test)]` / `#[test]` hunks
+pub struct RustTestDetector;
+
+impl TestDetector for RustTestDetector {
+    fn name(&self) -> &'static str {
+        "rust"
+    }
+
+    fn is_test(&self, file: &ChangedFile) -> bool {
+        if file.extension() != Some("rs") {
+            return false;
+        }
+
+        let stem = file.stem();
+        if stem.ends_with("_test") || stem.ends_with("_tests") || stem.starts_with("test_") {
+            return true;
+        }
+
+        file.added_lines().any(|line| {
+            line.starts_with("#[cfg(test)]")
+                || line.starts_with("#[test]")
+                || line.starts_with("#[tokio::test")
+        })
+    }
+}
+
+/// Go `_test.go` files
+pub struct GoTestDetector;
+
+impl TestDetector for GoTestDetector {
+    fn name(&self) -> &'static str {
+        "go"
+    }
+
+    fn is_test(&self, file: &ChangedFile) -> bool {
+        file.file_name().ends_with("_test.go")
+    }
+}
+
+/// JavaScript/TypeScript `*.test.*` and `*.spec.*` files
+pub struct JsTestDetector;
+
+impl TestDetector for JsTestDetector {
+    fn name(&self) -> &'static str {
+        "javascript"
+    }
+
+    fn is_test(&self, file: &ChangedFile) -> bool {
+        if !file.extension().is_some_and(|ext| JS_EXTENSIONS.contains(&ext)) {
+            return false;
+        }
+
+        let stem = file.stem();
+        stem.ends_with(".test") || stem.ends_with(".spec")
+    }
+}
+
+/// Python pytest/unittest naming conventions
+pub struct PythonTestDetector;
+
+impl TestDetector for PythonTestDetector {
+    fn name(&self) -> &'static str {
+        "python"
+    }
+
+    fn is_test(&self, file: &ChangedFile) -> bool {
+        if file.extension() != Some("py") {
+            return false;
+        }
+
+        let stem = file.stem();
+        stem.starts_with("test_") || stem.ends_with("_test") || stem == "conftest"
+    }
+}
+
+/// Built-in detectors, in evaluation order
+pub fn default_test_detectors() -> Vec<Box<dyn TestDetector>> {
+    vec![
+        Box::new(TestDirectoryDetector),
+        Box::new(RustTestDetector),
+        Box::new(GoTestDetector),
+        Box::new(JsTestDetector),
+        Box::new(PythonTestDetector),
+    ]
+}
+
+/// Maps changed files to the package (scope) they belong to
+#[derive(Debug, Clone, Default)]
+pub struct ScopeResolver {
+    repo_root: Option<PathBuf>,
+    package_roots: BTreeSet<String>,
+}
+
+impl ScopeResolver {
+    /// Resolver with an explicit list of package directories
+    pub fn from_package_roots<I, S>(roots: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            repo_root: None,
+            package_roots: roots
+                .into_iter()
+                .map(|r| r.into().trim_matches('/').to_string())
+                .filter(|r| !r.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Discover workspace members from the root manifests of a checkout
+    ///
+    /// Reads `[workspace] members` from `Cargo.toml` and `workspaces` from
+    /// `package.json`. Packages not declared there are still found by
+    /// probing for manifests next to the changed files.
+    pub fn discover<P: AsRef<Path>>(repo_root: P) -> Self {
+        let root = repo_root.as_ref();
+        let mut patterns = Vec::new();
+
+        if let Ok(content) = std::fs::read_to_string(root.join("Cargo.toml")) {
+            patterns.extend(cargo_workspace_members(&content));
+        }
+        if let Ok(content) = std::fs::read_to_string(root.join("package.json")) {
+            patterns.extend(npm_workspaces(&content));
+        }
+
+        let mut package_roots = BTreeSet::new();
+        for pattern in patterns {
+            package_roots.extend(expand_member(root, &pattern));
+        }
+        debug!("📦 Discovered {} workspace member(s)", package_roots.len());
+
+        Self {
+            repo_root: Some(root.to_path_buf()),
+            package_roots,
+        }
+    }
+
+    /// Scope of a repository-relative file path
+    pub fn scope_of(&self, file: &str) -> String {
+        let file = file.trim_start_matches('/');
+
+        if let Some(root) = self
+            .package_roots
+            .iter()
+            .filter(|root| file.starts_with(root.as_str()) && file[root.len()..].starts_with('/'))
+            .max_by_key(|root| root.len())
+        {
+            return root.clone();
+        }
+
+        if let Some(repo_root) = &self.repo_root {
+            let mut dir = Path::new(file).parent();
+            while let Some(d) = dir.filter(|d| !d.as_os_str().is_empty()) {
+                if MANIFESTS.iter().any(|m| repo_root.join(d).join(m).is_file()) {
+                    return d.to_string_lossy().replace('\\', "/");
+                }
+                dir = d.parent();
+            }
+        }
+
+        file.split('/').next().unwrap_or(file).to_string()
+    }
+}
+
+/// Extract `members = [...]` from the `[workspace]` table of a Cargo.toml
+fn cargo_workspace_members(content: &str) -> Vec<String> {
+    let mut in_workspace = false;
+    let mut collecting = false;
+    let mut members = Vec::new();
+
+    for line in content.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+
+        if line.starts_with('[') && !collecting {
+            in_workspace = line == "[workspace]";
+            continue;
+        }
+        if !in_workspace {
+            continue;
+        }
+
+        let values = if collecting {
+            line
+        } else if let Some(rest) = line.strip_prefix("members") {
+            let Some(rest) = rest.trim_start().strip_prefix('=') else {
+                continue;
+            };
+            collecting = true;
+            rest.trim_start().trim_start_matches('[')
+        } else {
+            continue;
+        };
+
+        let (values, done) = match values.split_once(']') {
+            Some((v, _)) => (v, true),
+            None => (values, false),
+        };
+        members.extend(
+            values
+                .split(',')
+                .map(|v| v.trim().trim_matches('"').trim_matches('\''))
+                .filter(|v| !v.is_empty())
+                .map(String::from),
+        );
+        if done {
+            collecting = false;
+            in_workspace = false;
+        }
+    }
+
+    members
+}
+
+/// Extract `workspaces` (array or `{ packages: [...] }`) from a package.json
+fn npm_workspaces(content: &str) -> Vec<String> {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(content) else {
+        return Vec::new();
+    };
+
+    let workspaces = match json.get("workspaces") {
+        Some(serde_json::Value::Array(list)) => list,
+        Some(serde_json::Value::Object(obj)) => match obj.get("packages") {
+            Some(serde_json::Value::Array(list)) => list,
+            _ => return Vec::new(),
+        },
+        _ => return Vec::new(),
+    };
+
+    workspaces
+        .iter()
+        .filter_map(|v| v.as_str().map(String::from))
+        .collect()
+}
+
+/// Expand a member pattern (`crates/*`, `packages/**`) into directories
+fn expand_member(root: &Path, pattern: &str) -> Vec<String> {
+    let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+    let Some(prefix) = pattern
+        .strip_suffix("/**")
+        .or_else(|| pattern.strip_suffix("/*"))
+    else {
+        return vec![pattern.to_string()];
+    };
+
+    let Ok(entries) = std::fs::read_dir(root.join(prefix)) else {
+        return Vec::new();
+    };
+
+    entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_dir())
+        .map(|e| format!("{}/{}", prefix, e.file_name().to_string_lossy()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_test(path: &str) -> bool {
+        let file = ChangedFile::new(path);
+        default_test_detectors().iter().any(|d| d.is_test(&file))
+    }
+
+    #[test]
+    fn test_language_conventions() {
+        assert!(is_test("tests/integration.rs"));
+        assert!(is_test("pkg/server/handler_test.go"));
+        assert!(is_test("web/src/app.spec.ts"));
+        assert!(is_test("src/__tests__/app.tsx"));
+        assert!(is_test("lib/test_parser.py"));
+
+        assert!(!is_test("src/contest.rs"));
+        assert!(!is_test("docs/specification.md"));
+        assert!(!is_test("src/latest/mod.rs"));
+        assert!(!is_test("tests.md"));
+    }
+
+    #[test]
+    fn test_rust_cfg_test_hunk() {
+        let detector = RustTestDetector;
+
+        let file = ChangedFile::new("src/parser.rs")
+            .with_patch("@@ -10,3 +10,9 @@\n fn parse() {}\n+\n+#[cfg(test)]\n+mod tests {\n+}");
+        assert!(detector.is_test(&file));
+
+        // Removing a test module is not test coverage
+        let file = ChangedFile::new("src/parser.rs").with_patch("@@ -1,3 +1,1 @@\n-#[cfg(test)]\n-mod tests {}");
+        assert!(!detector.is_test(&file));
+    }
+
+    #[test]
+    fn test_scope_from_package_roots() {
+        let resolver = ScopeResolver::from_package_roots(["tools/git-core/crates/gc-cli", "tools/issue-syncer"]);
+
+        assert_eq!(resolver.scope_of("tools/git-core/crates/gc-cli/src/main.rs"), "tools/git-core/crates/gc-cli");
+        assert_eq!(resolver.scope_of("tools/issue-syncer/src/lib.rs"), "tools/issue-syncer");
+        assert_eq!(resolver.scope_of("tools/issue-syncer-extra/x.rs"), "tools");
+        assert_eq!(resolver.scope_of("README.md"), "README.md");
+    }
+
+    #[test]
+    fn test_cargo_workspace_members() {
+        let manifest = r#"
+[package]
+name = "root"
+
+[workspace]
+resolver = "2"
+members = [
+    "crates/gc-cli",  # the CLI
+    "crates/gc-core",
+]
+
+[workspace.dependencies]
+members = ["not-a-member"]
+"#;
+        assert_eq!(cargo_workspace_members(manifest), vec!["crates/gc-cli", "crates/gc-core"]);
+        assert_eq!(cargo_workspace_members("[workspace]\nmembers = [\"a\", \"b/*\"]"), vec!["a", "b/*"]);
+    }
+
+    #[test]
+    fn test_npm_workspaces() {
+        assert_eq!(npm_workspaces(r#"{"workspaces": ["packages/*"]}"#), vec!["packages/*"]);
+        assert_eq!(npm_workspaces(r#"{"workspaces": {"packages": ["apps/*"]}}"#), vec!["apps/*"]);
+        assert!(npm_workspaces(r#"{"name": "x"}"#).is_empty());
+    }
+}
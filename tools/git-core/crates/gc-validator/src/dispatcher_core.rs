@@ -1,7 +1,7 @@
 //! # Dispatcher Core - AI Agent Load Balancer
 //!
 //! High-performance Rust implementation for distributing GitHub Issues
-//! to AI coding agents (Copilot, Jules) based on various strategies.
+//! to AI coding agents based on various strategies.
 //!
 //! ## Performance
 //!
@@ -9,36 +9,79 @@
 //! - **Baseline:** 3-5 seconds (PowerShell)
 //! - **Expected Speedup:** 15-25x
 //!
+//! ## Agent Registry
+//!
+//! Agents are not hard-coded: they are loaded from a YAML file (by default
+//! `.github/dispatcher-agents.yml`) so new agents or human teams only need
+//! a config change. Without a file the built-in Copilot and Jules entries
+//! are used.
+//!
+//! ```yaml
+//! agents:
+//!   - name: copilot
+//!     label: copilot
+//!     assignee: Copilot
+//!     assignable: false      # label-only, the API cannot assign Copilot
+//!     weight: 2              # twice the share in round-robin and random
+//!     skills: [bug, enhancement, documentation, tests]
+//!     paths: [src/api/]
+//!     max_concurrent: 5
+//!     risk_ceiling: 60
+//!   - name: codex
+//!     label: codex
+//! escalation:                # where high-risk issues go
+//!   name: human
+//!   label: needs-human
+//! ```
+//!
 //! ## Dispatch Strategies
 //!
 //! ```text
 //! RoundRobin:
-//!   - Alternates between Copilot and Jules
-//!   - Ensures balanced workload
+//!   - Cycles through agents in proportion to their weight
+//!   - Smooth ordering (2:1 → A, B, A rather than A, A, B)
 //!
 //! Random:
-//!   - Random selection per issue
+//!   - Weighted random selection per issue
 //!   - Good for A/B testing
 //!
-//! CopilotOnly:
-//!   - All issues → Copilot
+//! Only(name):
+//!   - All issues → one agent (`<name>-only`)
 //!   - For testing or specific campaigns
 //!
-//! JulesOnly:
-//!   - All issues → Jules
-//!   - For batch CLI operations
+//! CapabilityAware:
+//!   - Risk above threshold → escalation agent (needs-human)
+//!   - Agents over their risk ceiling or concurrency limit are skipped
+//!   - Best skill match (labels, path areas) wins, ties → least loaded
 //! ```
 //!
+//! ## Dependencies
+//!
+//! Issues whose body names an open prerequisite (`blocked by #N`,
+//! `depends on #N`, unchecked `- [ ] #N`) are not dispatched. The rest go
+//! out in topological order, most urgent priority label first; dependency
+//! cycles are reported (see `dependency_graph`).
+//!
+//! ## State and Reclaim
+//!
+//! With `with_state_file` the dispatcher records every assignment and the
+//! round-robin position (see `dispatcher_state`), so rotation continues
+//! across runs. `reclaim_stale` removes agent labels from issues with no
+//! linked PR activity for N days; on re-dispatch those issues are steered
+//! away from the agent they were reclaimed from.
+//!
 //! ## Example
 //!
 //! ```rust,no_run
-//! use workflow_orchestrator::dispatcher_core::{DispatcherCore, Strategy};
+//! use workflow_orchestrator::dispatcher_core::{AgentRegistry, DispatcherCore, Strategy};
 //! use octocrab::Octocrab;
 //!
 //! #[tokio::main]
 //! async fn main() -> anyhow::Result<()> {
 //!     let github = Octocrab::builder().build()?;
-//!     let dispatcher = DispatcherCore::new(github, "owner".to_string(), "repo".to_string());
+//!     let registry = AgentRegistry::from_file(".github/dispatcher-agents.yml")?;
+//!     let dispatcher = DispatcherCore::new(github, "owner".to_string(), "repo".to_string())
+//!         .with_registry(registry);
 //!
 //!     let assignments = dispatcher.dispatch_issues(
 //!         Strategy::RoundRobin,
@@ -54,80 +97,381 @@
 
 use anyhow::{Result, Context};
 use octocrab::Octocrab;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Duration, Utc};
+use octocrab::models::{timelines::TimelineEvent, Event, IssueState};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
 
 use tracing::{debug, info, warn};
 use rand::Rng;
 
+use crate::dependency_graph::{self, DependencyGraph, DependencyPlan};
+use crate::dispatcher_state::{self, DispatcherState};
+
 /// Dispatch strategy for agent selection
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Strategy {
-    /// Alternate between Copilot and Jules
+    /// Weighted round-robin over all registered agents
     RoundRobin,
-    /// Random selection
+    /// Weighted random selection
     Random,
-    /// All issues to Copilot
-    CopilotOnly,
-    /// All issues to Jules
-    JulesOnly,
+    /// All issues to the named agent
+    Only(String),
+    /// Match issue skills and risk against agent profiles and load
+    CapabilityAware,
 }
 
 impl std::str::FromStr for Strategy {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self> {
-        match s.to_lowercase().as_str() {
-            "round-robin" | "roundrobin" => Ok(Strategy::RoundRobin),
+        let s = s.to_lowercase();
+        match s.as_str() {
+            "round-robin" | "roundrobin" | "weighted-round-robin" => Ok(Strategy::RoundRobin),
             "random" => Ok(Strategy::Random),
-            "copilot-only" | "copilot" => Ok(Strategy::CopilotOnly),
-            "jules-only" | "jules" => Ok(Strategy::JulesOnly),
-            _ => Err(anyhow::anyhow!("Invalid strategy: {}", s)),
+            "capability-aware" | "capability" => Ok(Strategy::CapabilityAware),
+            // Short aliases kept from the fixed two-agent days
+            "copilot" | "jules" => Ok(Strategy::Only(s)),
+            _ => match s.strip_suffix("-only") {
+                Some(name) if !name.is_empty() => Ok(Strategy::Only(name.to_string())),
+                _ => Err(anyhow::anyhow!("Invalid strategy: {}", s)),
+            },
         }
     }
 }
 
-/// AI coding agent
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
-pub enum Agent {
-    Copilot,
-    Jules,
+/// An issue assignee: AI coding agent or human team, defined in the registry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Agent {
+    /// Unique name used by strategies and load tracking
+    pub name: String,
+    /// Label added to dispatched issues
+    pub label: String,
+    /// GitHub login to assign
+    #[serde(default)]
+    pub assignee: Option<String>,
+    /// Whether `assignee` can be assigned through the API (otherwise label only)
+    #[serde(default)]
+    pub assignable: bool,
+    /// Relative share in round-robin and random dispatch (0 = never picked by them)
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+    /// Issue labels this agent handles well
+    #[serde(default)]
+    pub skills: Vec<String>,
+    /// Path prefixes (e.g. `src/api/`) this agent handles well
+    #[serde(default)]
+    pub paths: Vec<String>,
+    /// Maximum open issues assigned at once
+    #[serde(default = "default_max_concurrent")]
+    pub max_concurrent: usize,
+    /// Highest risk score this agent may take
+    #[serde(default = "default_risk_ceiling")]
+    pub risk_ceiling: u8,
+}
+
+fn default_weight() -> u32 {
+    1
+}
+
+fn default_max_concurrent() -> usize {
+    5
+}
+
+fn default_risk_ceiling() -> u8 {
+    50
 }
 
 impl Agent {
-    /// Get the label name for this agent
-    pub fn label(&self) -> &'static str {
-        match self {
-            Agent::Copilot => "copilot",
-            Agent::Jules => "jules",
+    /// Create an agent with default weight and limits
+    pub fn new(name: &str, label: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            label: label.to_string(),
+            assignee: None,
+            assignable: false,
+            weight: default_weight(),
+            skills: vec![],
+            paths: vec![],
+            max_concurrent: default_max_concurrent(),
+            risk_ceiling: default_risk_ceiling(),
         }
     }
 
+    /// Default escalation target for high-risk issues
+    pub fn human() -> Self {
+        Self::new("human", "needs-human")
+    }
+
+    /// Get the label name for this agent
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
     /// Get the assignee name (if supported)
-    pub fn assignee(&self) -> Option<&'static str> {
-        match self {
-            Agent::Copilot => Some("Copilot"),
-            Agent::Jules => None, // Jules uses labels only
+    pub fn assignee(&self) -> Option<&str> {
+        self.assignee.as_deref()
+    }
+}
+
+/// Registered agents plus the escalation target
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentRegistry {
+    agents: Vec<Agent>,
+    #[serde(default = "Agent::human")]
+    escalation: Agent,
+}
+
+impl AgentRegistry {
+    /// Load the registry from a YAML file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = std::fs::read_to_string(path.as_ref())
+            .with_context(|| format!("Failed to read {}", path.as_ref().display()))?;
+        Self::from_yaml(&content)
+            .with_context(|| format!("Invalid agent registry {}", path.as_ref().display()))
+    }
+
+    /// Parse and validate registry YAML
+    pub fn from_yaml(content: &str) -> Result<Self> {
+        let registry: Self = serde_yaml::from_str(content).context("Failed to parse agent registry")?;
+        registry.validate()?;
+        Ok(registry)
+    }
+
+    /// Built-in Copilot and Jules agents
+    pub fn defaults() -> Self {
+        let mut copilot = Agent::new("copilot", "copilot");
+        copilot.assignee = Some("Copilot".to_string());
+        copilot.skills = ["bug", "enhancement", "documentation", "tests"]
+            .iter().map(|s| s.to_string()).collect();
+        copilot.max_concurrent = 5;
+        copilot.risk_ceiling = 60;
+
+        let mut jules = Agent::new("jules", "jules");
+        jules.skills = ["refactor", "chore", "dependencies", "performance"]
+            .iter().map(|s| s.to_string()).collect();
+        jules.max_concurrent = 3;
+        jules.risk_ceiling = 50;
+
+        Self {
+            agents: vec![copilot, jules],
+            escalation: Agent::human(),
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.agents.is_empty() {
+            anyhow::bail!("At least one agent must be defined");
+        }
+
+        let mut names = HashSet::new();
+        let mut labels = HashSet::new();
+        for agent in self.agents.iter().chain(std::iter::once(&self.escalation)) {
+            if agent.name.trim().is_empty() || agent.label.trim().is_empty() {
+                anyhow::bail!("Agents need a non-empty name and label");
+            }
+            if !names.insert(agent.name.to_lowercase()) {
+                anyhow::bail!("Duplicate agent name: {}", agent.name);
+            }
+            if !labels.insert(agent.label.to_lowercase()) {
+                anyhow::bail!("Duplicate agent label: {}", agent.label);
+            }
+            if agent.assignable && agent.assignee.is_none() {
+                anyhow::bail!("Agent {} is assignable but has no assignee", agent.name);
+            }
+        }
+
+        if self.agents.iter().all(|a| a.weight == 0) {
+            anyhow::bail!("At least one agent needs a non-zero weight");
         }
+
+        Ok(())
+    }
+
+    /// Registered agents (excluding the escalation target)
+    pub fn agents(&self) -> &[Agent] {
+        &self.agents
+    }
+
+    /// Where issues go when no agent may take them
+    pub fn escalation(&self) -> &Agent {
+        &self.escalation
+    }
+
+    /// Look up an agent or the escalation target by name (case-insensitive)
+    pub fn get(&self, name: &str) -> Option<&Agent> {
+        self.agents
+            .iter()
+            .chain(std::iter::once(&self.escalation))
+            .find(|a| a.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Whether a label marks an issue as already dispatched
+    pub fn is_agent_label(&self, label: &str) -> bool {
+        self.agents
+            .iter()
+            .chain(std::iter::once(&self.escalation))
+            .any(|a| a.label.eq_ignore_ascii_case(label))
+    }
+
+    /// One weighted round-robin cycle as agent indices
+    ///
+    /// Uses smooth weighted round-robin so heavier agents are interleaved
+    /// rather than batched: weights 2:1 give `[0, 1, 0]`.
+    pub fn rotation(&self) -> Vec<usize> {
+        let total: i64 = self.agents.iter().map(|a| a.weight as i64).sum();
+        let mut current = vec![0i64; self.agents.len()];
+        let mut cycle = Vec::with_capacity(total as usize);
+
+        for _ in 0..total {
+            for (i, agent) in self.agents.iter().enumerate() {
+                current[i] += agent.weight as i64;
+            }
+            // Highest running weight wins; ties go to the earlier agent
+            let (pick, _) = current
+                .iter()
+                .enumerate()
+                .fold((0, i64::MIN), |best, (i, &w)| if w > best.1 { (i, w) } else { best });
+            current[pick] -= total;
+            cycle.push(pick);
+        }
+
+        cycle
+    }
+
+    /// Pick an agent at random in proportion to weight, skipping `avoid` when possible
+    fn weighted_random(&self, avoid: &HashSet<String>) -> &Agent {
+        let mut pool: Vec<&Agent> = self
+            .agents
+            .iter()
+            .filter(|a| a.weight > 0 && !avoid.contains(&a.name))
+            .collect();
+        if pool.is_empty() {
+            pool = self.agents.iter().filter(|a| a.weight > 0).collect();
+        }
+
+        let total: u32 = pool.iter().map(|a| a.weight).sum();
+        let mut roll = rand::thread_rng().gen_range(0..total);
+
+        for agent in &pool {
+            if roll < agent.weight {
+                return agent;
+            }
+            roll -= agent.weight;
+        }
+
+        // Unreachable for a validated registry
+        pool[0]
     }
 }
 
+impl Default for AgentRegistry {
+    fn default() -> Self {
+        Self::defaults()
+    }
+}
+
+/// Open assignments per agent name, updated as issues are dispatched
+pub type AgentLoads = HashMap<String, usize>;
+
 /// Issue assignment result
 #[derive(Debug, Clone, Serialize)]
 pub struct Assignment {
     pub issue_number: u64,
     pub issue_title: String,
-    pub agent: Agent,
+    /// Name of the chosen agent in the registry
+    pub agent: String,
     pub risk_score: u8,
     pub reason: String,
 }
 
+/// Issue returned to the pool by `reclaim_stale`
+#[derive(Debug, Clone, Serialize)]
+pub struct Reclaim {
+    pub issue_number: u64,
+    pub issue_title: String,
+    pub agent: String,
+    pub assigned_at: DateTime<Utc>,
+    pub last_activity: DateTime<Utc>,
+    pub idle_days: i64,
+}
+
 /// Simplified Issue representation
 #[derive(Debug, Clone)]
-struct Issue {
-    number: u64,
-    title: String,
-    body: Option<String>,
-    labels: Vec<String>,
+pub struct Issue {
+    pub number: u64,
+    pub title: String,
+    pub body: Option<String>,
+    pub labels: Vec<String>,
+}
+
+impl Issue {
+    /// Prerequisite issues declared in the body (`blocked by #N`, open task lists)
+    pub fn dependencies(&self) -> Vec<u64> {
+        self.body.as_deref().map(dependency_graph::parse_dependencies).unwrap_or_default()
+    }
+
+    /// Priority rank from labels (0 = most urgent)
+    pub fn priority(&self) -> u8 {
+        dependency_graph::priority_rank(&self.labels)
+    }
+
+    /// File paths mentioned in the title or body (e.g. `src/api/auth.rs`)
+    pub fn mentioned_paths(&self) -> Vec<String> {
+        static PATH_RE: OnceLock<Regex> = OnceLock::new();
+        let re = PATH_RE.get_or_init(|| {
+            Regex::new(r"(?:^|[\s`'\(\[])((?:[\w.-]+/)+[\w.-]*)").expect("valid path regex")
+        });
+
+        let text = format!("{}\n{}", self.title, self.body.as_deref().unwrap_or_default());
+        let mut paths: Vec<String> = re
+            .captures_iter(&text)
+            .map(|c| c[1].trim_start_matches("./").to_string())
+            .filter(|p| !p.starts_with("http") && !p.contains("//"))
+            .collect();
+        paths.sort();
+        paths.dedup();
+        paths
+    }
+}
+
+/// Render issue numbers as `#1, #2`
+fn format_refs(numbers: &[u64], separator: &str) -> String {
+    numbers
+        .iter()
+        .map(|n| format!("#{}", n))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// When `label` was last added to the issue
+fn last_labeled(events: &[TimelineEvent], label: &str) -> Option<DateTime<Utc>> {
+    events
+        .iter()
+        .filter(|e| e.event == Event::Labeled)
+        .filter(|e| e.label.as_ref().is_some_and(|l| l.name.eq_ignore_ascii_case(label)))
+        .filter_map(|e| e.created_at)
+        .max()
+}
+
+/// Last update of the pull requests that reference the issue
+///
+/// Only cross-references from pull requests count, dated by the PR's own
+/// `updated_at`, so a PR that still gets pushes keeps the issue active and
+/// mentions from other issues do not.
+fn last_pr_activity(events: &[TimelineEvent]) -> Option<DateTime<Utc>> {
+    events
+        .iter()
+        .filter(|e| e.event == Event::CrossReferenced)
+        // `Source` keeps its issue private
+        .filter_map(|e| serde_json::to_value(e.source.as_ref()?).ok())
+        .filter(|source| !source["issue"]["pull_request"].is_null())
+        .filter_map(|source| source["issue"]["updated_at"].as_str()?.parse().ok())
+        .max()
 }
 
 /// Dispatcher Core engine
@@ -136,18 +480,25 @@ pub struct DispatcherCore {
     owner: String,
     repo: String,
     high_risk_threshold: u8,
-    round_robin_index: std::sync::atomic::AtomicUsize,
+    round_robin_index: AtomicUsize,
+    registry: AgentRegistry,
+    rotation: Vec<usize>,
+    state: Option<(PathBuf, Mutex<DispatcherState>)>,
 }
 
 impl DispatcherCore {
     /// Create new Dispatcher instance
     pub fn new(github: Octocrab, owner: String, repo: String) -> Self {
+        let registry = AgentRegistry::defaults();
         Self {
             github,
             owner,
             repo,
             high_risk_threshold: 70,
-            round_robin_index: std::sync::atomic::AtomicUsize::new(0),
+            round_robin_index: AtomicUsize::new(0),
+            rotation: registry.rotation(),
+            registry,
+            state: None,
         }
     }
 
@@ -157,6 +508,32 @@ impl DispatcherCore {
         self
     }
 
+    /// Replace the agent registry used by every strategy
+    pub fn with_registry(mut self, registry: AgentRegistry) -> Self {
+        self.rotation = registry.rotation();
+        self.registry = registry;
+        self
+    }
+
+    /// Persist assignments and the round-robin position in a state file
+    pub fn with_state_file<P: AsRef<Path>>(mut self, path: P) -> Result<Self> {
+        let state = DispatcherState::load(path.as_ref())?;
+        info!(
+            "✅ Dispatcher state loaded from {} ({} active assignment(s))",
+            path.as_ref().display(),
+            state.active.len()
+        );
+
+        self.round_robin_index = AtomicUsize::new(state.round_robin_index);
+        self.state = Some((path.as_ref().to_path_buf(), Mutex::new(state)));
+        Ok(self)
+    }
+
+    /// Registered agents
+    pub fn registry(&self) -> &AgentRegistry {
+        &self.registry
+    }
+
     /// Main dispatch entry point
     pub async fn dispatch_issues(
         &self,
@@ -170,28 +547,58 @@ impl DispatcherCore {
             strategy, max_issues
         );
 
+        if let Strategy::Only(name) = &strategy {
+            if self.registry.get(name).is_none() {
+                let known: Vec<&str> = self.registry.agents().iter().map(|a| a.name.as_str()).collect();
+                anyhow::bail!("Unknown agent '{}' (registered: {})", name, known.join(", "));
+            }
+        }
+
         // Fetch unassigned issues
         let issues = self.fetch_unassigned_issues(&label_filter).await?;
         info!("📋 Found {} unassigned issues", issues.len());
 
+        // Drop blocked issues, order the rest by dependencies and priority
+        let plan = self.plan_dependencies(&issues).await?;
+        let mut by_number: HashMap<u64, Issue> = issues.into_iter().map(|i| (i.number, i)).collect();
+
         // Limit to max_issues
-        let candidates: Vec<Issue> = issues.into_iter().take(max_issues).collect();
+        let candidates: Vec<Issue> = plan
+            .ready
+            .iter()
+            .filter_map(|n| by_number.remove(n))
+            .take(max_issues)
+            .collect();
 
         if candidates.is_empty() {
             info!("✅ No issues to dispatch");
             return Ok(vec![]);
         }
 
+        let mut loads = if strategy == Strategy::CapabilityAware {
+            self.fetch_agent_loads().await?
+        } else {
+            AgentLoads::new()
+        };
+
         // Analyze and assign
         let mut assignments = Vec::new();
         for issue in candidates {
-            let assignment = self.analyze_and_assign(&issue, strategy)?;
-            assignments.push(assignment);
+            match self.analyze_and_assign(&issue, &strategy, &mut loads) {
+                Some(assignment) => assignments.push(assignment),
+                None => info!("⏸️  Issue #{} left in pool: all capable agents at capacity", issue.number),
+            }
         }
 
         // Execute assignments
         if !dry_run {
             self.execute_assignments(&assignments).await?;
+            self.save_state(|state| {
+                let now = Utc::now();
+                for a in &assignments {
+                    state.record_assignment(a.issue_number, &a.agent, &a.reason, now);
+                }
+            })?;
         } else {
             info!("🔍 Dry run - no assignments executed");
         }
@@ -218,10 +625,10 @@ impl DispatcherCore {
             .into_iter()
             .filter(|issue| {
                 // Check if issue has no agent labels and no assignees
-                let has_agent_label = issue.labels.iter().any(|l| {
-                    let name = l.name.to_lowercase();
-                    name == "copilot" || name == "jules"
-                });
+                let has_agent_label = issue
+                    .labels
+                    .iter()
+                    .any(|l| self.registry.is_agent_label(&l.name));
 
                 let has_assignees = !issue.assignees.is_empty();
 
@@ -239,27 +646,367 @@ impl DispatcherCore {
         Ok(unassigned)
     }
 
-    /// Analyze issue and create assignment
-    fn analyze_and_assign(&self, issue: &Issue, strategy: Strategy) -> Result<Assignment> {
+    /// Build the prerequisite graph of the candidates and order them
+    async fn plan_dependencies(&self, issues: &[Issue]) -> Result<DependencyPlan> {
+        let mut graph = DependencyGraph::new();
+        for issue in issues {
+            graph.add_issue(issue.number, issue.priority(), issue.dependencies());
+        }
+
+        // Prerequisites outside the candidate set may be closed already
+        let mut open_external = HashSet::new();
+        for number in graph.external_dependencies() {
+            match self.github.issues(&self.owner, &self.repo).get(number).await {
+                Ok(dep) if dep.state == IssueState::Open => {
+                    open_external.insert(number);
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    warn!("Failed to look up prerequisite #{}, treating as open: {}", number, e);
+                    open_external.insert(number);
+                }
+            }
+        }
+
+        let plan = graph.plan(|n| open_external.contains(&n));
+
+        for (number, deps) in &plan.blocked {
+            info!("🚧 Issue #{} blocked by {}", number, format_refs(deps, ", "));
+        }
+        for cycle in &plan.cycles {
+            warn!(
+                "🔁 Dependency cycle {} → #{}: none of these can be dispatched",
+                format_refs(cycle, " → "),
+                cycle[0]
+            );
+        }
+
+        Ok(plan)
+    }
+
+    /// Count open issues currently carrying each agent's label
+    async fn fetch_agent_loads(&self) -> Result<AgentLoads> {
+        let mut loads = AgentLoads::new();
+
+        for agent in self.registry.agents() {
+            let open = self
+                .open_issues_labeled(agent.label())
+                .await
+                .context(format!("Failed to count open issues for {}", agent.name))?
+                .len();
+            debug!("📊 {} has {} open assignment(s)", agent.name, open);
+            loads.insert(agent.name.clone(), open);
+        }
+
+        Ok(loads)
+    }
+
+    /// Every open issue (not PR) carrying `label`
+    async fn open_issues_labeled(&self, label: &str) -> Result<Vec<octocrab::models::issues::Issue>> {
+        let first = self
+            .github
+            .issues(&self.owner, &self.repo)
+            .list()
+            .state(octocrab::params::State::Open)
+            .labels(&[label.to_string()])
+            .per_page(100)
+            .send()
+            .await?;
+
+        let mut issues = self.github.all_pages(first).await?;
+        // The issues API lists PRs too
+        issues.retain(|i| i.pull_request.is_none());
+        Ok(issues)
+    }
+
+    /// Return stale agent assignments to the pool
+    ///
+    /// An assignment is stale when neither it nor any linked PR event is
+    /// newer than `stale_after_days`. The assignment time comes from the
+    /// state file, falling back to when the agent label was added.
+    pub async fn reclaim_stale(&self, stale_after_days: i64, dry_run: bool) -> Result<Vec<Reclaim>> {
+        info!("♻️  Reclaiming assignments idle for {}+ days", stale_after_days);
+
+        let now = Utc::now();
+        let stale_after = Duration::days(stale_after_days);
+        let issues_api = self.github.issues(&self.owner, &self.repo);
+        let mut reclaimed = Vec::new();
+
+        for agent in self.registry.agents() {
+            let issues = self
+                .open_issues_labeled(&agent.label)
+                .await
+                .context(format!("Failed to fetch issues for {}", agent.name))?;
+
+            for issue in issues {
+                let first = issues_api
+                    .list_timeline_events(issue.number)
+                    .per_page(100)
+                    .send()
+                    .await
+                    .context(format!("Failed to fetch timeline of issue #{}", issue.number))?;
+                let events = self.github.all_pages(first).await?;
+
+                let assigned_at = self
+                    .recorded_assignment(issue.number, &agent.name)
+                    .or_else(|| last_labeled(&events, &agent.label))
+                    .unwrap_or(issue.created_at);
+                let pr_activity = last_pr_activity(&events);
+
+                if !dispatcher_state::is_stale(assigned_at, pr_activity, now, stale_after) {
+                    debug!("⏳ Issue #{} still active with {}", issue.number, agent.name);
+                    continue;
+                }
+
+                let last_activity = pr_activity.map_or(assigned_at, |pr| pr.max(assigned_at));
+                let idle_days = (now - last_activity).num_days();
+                info!(
+                    "♻️  Issue #{} idle for {} days with {}, returning to pool",
+                    issue.number, idle_days, agent.name
+                );
+
+                if !dry_run {
+                    self.release(&issue, agent, idle_days).await?;
+                }
+
+                reclaimed.push(Reclaim {
+                    issue_number: issue.number,
+                    issue_title: issue.title.clone(),
+                    agent: agent.name.clone(),
+                    assigned_at,
+                    last_activity,
+                    idle_days,
+                });
+            }
+        }
+
+        if dry_run {
+            info!("🔍 Dry run - no assignments reclaimed");
+        } else {
+            self.save_state(|state| {
+                for r in &reclaimed {
+                    state.record_reclaim(r.issue_number, &r.agent, now);
+                }
+            })?;
+        }
+
+        info!("🎉 Reclaimed {} assignment(s)", reclaimed.len());
+        Ok(reclaimed)
+    }
+
+    /// Remove the agent label (and assignee) and explain why
+    async fn release(&self, issue: &octocrab::models::issues::Issue, agent: &Agent, idle_days: i64) -> Result<()> {
+        let issues_api = self.github.issues(&self.owner, &self.repo);
+
+        issues_api
+            .remove_label(issue.number, &agent.label)
+            .await
+            .context(format!("Failed to remove label from issue #{}", issue.number))?;
+
+        if let (true, Some(login)) = (agent.assignable, agent.assignee()) {
+            let remaining: Vec<String> = issue
+                .assignees
+                .iter()
+                .map(|a| a.login.clone())
+                .filter(|l| !l.eq_ignore_ascii_case(login))
+                .collect();
+            issues_api
+                .update(issue.number)
+                .assignees(&remaining)
+                .send()
+                .await
+                .context(format!("Failed to unassign {} from issue #{}", login, issue.number))?;
+        }
+
+        let comment = format!(
+            "🤖 **Dispatcher**: Returned to the pool\n\n\
+             No linked PR activity from **{}** for {} days. \
+             The issue is back in the pool for the next dispatch run.",
+            agent.name, idle_days
+        );
+        issues_api.create_comment(issue.number, comment).await?;
+
+        Ok(())
+    }
+
+    /// Assignment time recorded in the state file for this issue and agent
+    fn recorded_assignment(&self, issue_number: u64, agent: &str) -> Option<DateTime<Utc>> {
+        let (_, state) = self.state.as_ref()?;
+        let state = state.lock().expect("dispatcher state lock poisoned");
+        state
+            .active
+            .get(&issue_number)
+            .filter(|record| record.agent == agent)
+            .map(|record| record.assigned_at)
+    }
+
+    /// Analyze issue and create assignment (`None` when no agent has capacity)
+    fn analyze_and_assign(
+        &self,
+        issue: &Issue,
+        strategy: &Strategy,
+        loads: &mut AgentLoads,
+    ) -> Option<Assignment> {
         let risk_score = self.analyze_risk(issue);
-        let agent = self.select_agent(strategy, issue, risk_score);
+        let avoid = self.reclaimed_from(issue.number);
 
-        let reason = match strategy {
-            Strategy::RoundRobin => "Round-robin distribution".to_string(),
-            Strategy::Random => "Random selection".to_string(),
-            Strategy::CopilotOnly => "Copilot-only mode".to_string(),
-            Strategy::JulesOnly => "Jules-only mode".to_string(),
+        let (agent, mut reason) = match strategy {
+            Strategy::CapabilityAware => self.select_capable_agent_avoiding(issue, risk_score, loads, &avoid)?,
+            _ => {
+                let agent = self.select_agent(strategy, issue, risk_score, &avoid);
+                let reason = match strategy {
+                    Strategy::RoundRobin => format!("Weighted round-robin (weight {})", agent.weight),
+                    Strategy::Random => format!("Weighted random selection (weight {})", agent.weight),
+                    _ => format!("{}-only mode", agent.name),
+                };
+                (agent, reason)
+            }
         };
 
-        Ok(Assignment {
+        if !avoid.is_empty() && !avoid.contains(&agent.name) {
+            let mut previous: Vec<&str> = avoid.iter().map(|a| a.as_str()).collect();
+            previous.sort_unstable();
+            reason.push_str(&format!("; rebalanced away from {}", previous.join(", ")));
+        }
+
+        *loads.entry(agent.name.clone()).or_insert(0) += 1;
+
+        Some(Assignment {
             issue_number: issue.number,
             issue_title: issue.title.clone(),
-            agent,
+            agent: agent.name.clone(),
             risk_score,
             reason,
         })
     }
 
+    /// Pick the best-matching agent with spare capacity and a high enough risk ceiling
+    pub fn select_capable_agent(
+        &self,
+        issue: &Issue,
+        risk_score: u8,
+        loads: &AgentLoads,
+    ) -> Option<(&Agent, String)> {
+        self.select_capable_agent_avoiding(issue, risk_score, loads, &HashSet::new())
+    }
+
+    /// Capability-aware selection that skips agents in `avoid` unless none other qualifies
+    fn select_capable_agent_avoiding(
+        &self,
+        issue: &Issue,
+        risk_score: u8,
+        loads: &AgentLoads,
+        avoid: &HashSet<String>,
+    ) -> Option<(&Agent, String)> {
+        let escalation = self.registry.escalation();
+
+        if risk_score >= self.high_risk_threshold {
+            return Some((
+                escalation,
+                format!(
+                    "Risk {} ≥ threshold {}: escalated to {}",
+                    risk_score, self.high_risk_threshold, escalation.name
+                ),
+            ));
+        }
+
+        let labels: Vec<String> = issue.labels.iter().map(|l| l.to_lowercase()).collect();
+        let paths = issue.mentioned_paths();
+
+        let mut within_ceiling: Vec<&Agent> = self
+            .registry
+            .agents()
+            .iter()
+            .filter(|a| risk_score <= a.risk_ceiling)
+            .collect();
+
+        let fresh: Vec<&Agent> = within_ceiling
+            .iter()
+            .copied()
+            .filter(|a| !avoid.contains(&a.name) && loads.get(&a.name).copied().unwrap_or(0) < a.max_concurrent)
+            .collect();
+        if !fresh.is_empty() {
+            within_ceiling = fresh;
+        }
+
+        if within_ceiling.is_empty() {
+            return Some((
+                escalation,
+                format!(
+                    "Risk {} exceeds every agent's ceiling: escalated to {}",
+                    risk_score, escalation.name
+                ),
+            ));
+        }
+
+        let mut best: Option<(&Agent, Vec<&str>, Vec<&str>, usize)> = None;
+
+        for agent in within_ceiling {
+            let load = loads.get(&agent.name).copied().unwrap_or(0);
+            if load >= agent.max_concurrent {
+                debug!("⛔ {} at capacity ({}/{})", agent.name, load, agent.max_concurrent);
+                continue;
+            }
+
+            let skill_hits: Vec<&str> = agent
+                .skills
+                .iter()
+                .filter(|s| labels.contains(&s.to_lowercase()))
+                .map(|s| s.as_str())
+                .collect();
+            let path_hits: Vec<&str> = agent
+                .paths
+                .iter()
+                .filter(|area| paths.iter().any(|p| p.starts_with(area.trim_start_matches("./"))))
+                .map(|s| s.as_str())
+                .collect();
+
+            let score = skill_hits.len() + path_hits.len();
+            let better = match &best {
+                None => true,
+                Some((current, cs, cp, cload)) => {
+                    let current_score = cs.len() + cp.len();
+                    // Compare load ratios without floats: load/max < cload/cmax
+                    score > current_score
+                        || (score == current_score
+                            && load * current.max_concurrent < cload * agent.max_concurrent)
+                }
+            };
+
+            if better {
+                best = Some((agent, skill_hits, path_hits, load));
+            }
+        }
+
+        let (agent, skill_hits, path_hits, load) = best?;
+
+        let mut matched = Vec::new();
+        if !skill_hits.is_empty() {
+            matched.push(format!("labels [{}]", skill_hits.join(", ")));
+        }
+        if !path_hits.is_empty() {
+            matched.push(format!("paths [{}]", path_hits.join(", ")));
+        }
+
+        let reason = if matched.is_empty() {
+            format!(
+                "No skill match; least loaded agent ({}/{} open, risk {} ≤ {})",
+                load, agent.max_concurrent, risk_score, agent.risk_ceiling
+            )
+        } else {
+            format!(
+                "Matched {} ({}/{} open, risk {} ≤ {})",
+                matched.join(" and "),
+                load,
+                agent.max_concurrent,
+                risk_score,
+                agent.risk_ceiling
+            )
+        };
+
+        Some((agent, reason))
+    }
+
     /// Analyze issue risk score (0-100)
     fn analyze_risk(&self, issue: &Issue) -> u8 {
         let mut risk = 0u8;
@@ -297,45 +1044,82 @@ impl DispatcherCore {
         risk.min(100)
     }
 
-    /// Select agent based on strategy
-    fn select_agent(&self, strategy: Strategy, _issue: &Issue, risk_score: u8) -> Agent {
+    /// Select agent based on strategy, skipping agents in `avoid` when another is available
+    fn select_agent(
+        &self,
+        strategy: &Strategy,
+        issue: &Issue,
+        risk_score: u8,
+        avoid: &HashSet<String>,
+    ) -> &Agent {
         match strategy {
             Strategy::RoundRobin => {
                 // Atomic increment for thread-safe round-robin
-                let index = self
-                    .round_robin_index
-                    .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-                if index % 2 == 0 {
-                    Agent::Copilot
-                } else {
-                    Agent::Jules
-                }
-            }
-            Strategy::Random => {
-                let mut rng = rand::thread_rng();
-                if rng.gen_bool(0.5) {
-                    Agent::Copilot
-                } else {
-                    Agent::Jules
+                let mut pick = self.next_in_rotation();
+                for _ in 1..self.rotation.len() {
+                    if !avoid.contains(&pick.name) {
+                        break;
+                    }
+                    pick = self.next_in_rotation();
                 }
+                pick
             }
-            Strategy::CopilotOnly => Agent::Copilot,
-            Strategy::JulesOnly => Agent::Jules,
+            Strategy::Random => self.registry.weighted_random(avoid),
+            Strategy::Only(name) => self
+                .registry
+                .get(name)
+                .unwrap_or_else(|| self.registry.escalation()),
+            Strategy::CapabilityAware => self
+                .select_capable_agent(issue, risk_score, &AgentLoads::new())
+                .map_or(self.registry.escalation(), |(agent, _)| agent),
         }
     }
 
+    fn next_in_rotation(&self) -> &Agent {
+        let index = self.round_robin_index.fetch_add(1, Ordering::Relaxed);
+        &self.registry.agents()[self.rotation[index % self.rotation.len()]]
+    }
+
+    /// Agents this issue was previously reclaimed from
+    fn reclaimed_from(&self, issue_number: u64) -> HashSet<String> {
+        let Some((_, state)) = &self.state else {
+            return HashSet::new();
+        };
+        let state = state.lock().expect("dispatcher state lock poisoned");
+        state.reclaimed_from(issue_number).into_iter().map(String::from).collect()
+    }
+
+    /// Apply `update` to the persisted state and write it back (no-op without a state file)
+    fn save_state(&self, update: impl FnOnce(&mut DispatcherState)) -> Result<()> {
+        let Some((path, state)) = &self.state else {
+            return Ok(());
+        };
+
+        let mut state = state.lock().expect("dispatcher state lock poisoned");
+        update(&mut state);
+        state.round_robin_index = self.round_robin_index.load(Ordering::Relaxed);
+        state.save(path)?;
+        debug!("💾 Dispatcher state saved to {}", path.display());
+        Ok(())
+    }
+
     /// Execute assignments (add labels and assignees)
     async fn execute_assignments(&self, assignments: &[Assignment]) -> Result<()> {
         for assignment in assignments {
+            let Some(agent) = self.registry.get(&assignment.agent) else {
+                warn!("Unknown agent {} for issue #{}, skipping", assignment.agent, assignment.issue_number);
+                continue;
+            };
+
             info!(
-                "🏷️  Assigning issue #{} to {:?}",
-                assignment.issue_number, assignment.agent
+                "🏷️  Assigning issue #{} to {}",
+                assignment.issue_number, agent.name
             );
 
             // Add agent label
             self.github
                 .issues(&self.owner, &self.repo)
-                .add_labels(assignment.issue_number, &[assignment.agent.label().to_string()])
+                .add_labels(assignment.issue_number, &[agent.label().to_string()])
                 .await
                 .context(format!(
                     "Failed to add label to issue #{}",
@@ -343,9 +1127,19 @@ impl DispatcherCore {
                 ))?;
 
             // Add assignee if supported
-            if let Some(assignee) = assignment.agent.assignee() {
-                // Note: Copilot assignee may not work via API, handled via label
-                debug!("Would assign to: {}", assignee);
+            match agent.assignee() {
+                Some(assignee) if agent.assignable => {
+                    self.github
+                        .issues(&self.owner, &self.repo)
+                        .add_assignees(assignment.issue_number, &[assignee])
+                        .await
+                        .context(format!(
+                            "Failed to assign {} to issue #{}",
+                            assignee, assignment.issue_number
+                        ))?;
+                }
+                Some(assignee) => debug!("{} is label-only, not assigning {}", agent.name, assignee),
+                None => {}
             }
 
             debug!("✅ Issue #{} dispatched", assignment.issue_number);
@@ -369,19 +1163,217 @@ mod tests {
         assert_eq!("random".parse::<Strategy>().unwrap(), Strategy::Random);
         assert_eq!(
             "copilot-only".parse::<Strategy>().unwrap(),
-            Strategy::CopilotOnly
+            Strategy::Only("copilot".to_string())
+        );
+        assert_eq!(
+            "jules".parse::<Strategy>().unwrap(),
+            Strategy::Only("jules".to_string())
         );
         assert_eq!(
-            "jules-only".parse::<Strategy>().unwrap(),
-            Strategy::JulesOnly
+            "Codex-Only".parse::<Strategy>().unwrap(),
+            Strategy::Only("codex".to_string())
+        );
+        assert_eq!(
+            "capability-aware".parse::<Strategy>().unwrap(),
+            Strategy::CapabilityAware
         );
         assert!("invalid".parse::<Strategy>().is_err());
+        assert!("-only".parse::<Strategy>().is_err());
     }
 
     #[test]
     fn test_agent_labels() {
-        assert_eq!(Agent::Copilot.label(), "copilot");
-        assert_eq!(Agent::Jules.label(), "jules");
+        let registry = AgentRegistry::defaults();
+        assert_eq!(registry.get("copilot").unwrap().label(), "copilot");
+        assert_eq!(registry.get("jules").unwrap().label(), "jules");
+        assert_eq!(registry.get("copilot").unwrap().assignee(), Some("Copilot"));
+        assert_eq!(registry.escalation().label(), "needs-human");
+        assert!(registry.is_agent_label("Needs-Human"));
+        assert!(!registry.is_agent_label("bug"));
+    }
+
+    const REGISTRY_YAML: &str = r#"
+agents:
+  - name: copilot
+    label: copilot
+    assignee: Copilot
+    weight: 2
+    skills: [bug]
+  - name: codex
+    label: codex
+    assignee: codex-bot
+    assignable: true
+  - name: paused
+    label: paused-agent
+    weight: 0
+escalation:
+  name: platform-team
+  label: needs-platform
+"#;
+
+    #[test]
+    fn test_registry_from_yaml() {
+        let registry = AgentRegistry::from_yaml(REGISTRY_YAML).unwrap();
+
+        assert_eq!(registry.agents().len(), 3);
+        let codex = registry.get("codex").unwrap();
+        assert!(codex.assignable);
+        assert_eq!(codex.weight, 1);
+        assert_eq!(codex.max_concurrent, 5);
+        assert_eq!(registry.escalation().label, "needs-platform");
+        assert!(registry.is_agent_label("paused-agent"));
+    }
+
+    #[test]
+    fn test_registry_validation() {
+        assert!(AgentRegistry::from_yaml("agents: []").is_err());
+        assert!(AgentRegistry::from_yaml(
+            "agents:\n  - {name: a, label: x}\n  - {name: A, label: y}"
+        )
+        .is_err());
+        assert!(AgentRegistry::from_yaml(
+            "agents:\n  - {name: a, label: x}\n  - {name: b, label: x}"
+        )
+        .is_err());
+        assert!(AgentRegistry::from_yaml("agents:\n  - {name: a, label: x, assignable: true}").is_err());
+        assert!(AgentRegistry::from_yaml("agents:\n  - {name: a, label: x, weight: 0}").is_err());
+    }
+
+    #[test]
+    fn test_weighted_rotation() {
+        let registry = AgentRegistry::from_yaml(REGISTRY_YAML).unwrap();
+
+        // copilot:2, codex:1, paused:0 → interleaved, paused never picked
+        assert_eq!(registry.rotation(), vec![0, 1, 0]);
+        assert_eq!(AgentRegistry::defaults().rotation(), vec![0, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_round_robin_and_only_use_registry() {
+        let github = Octocrab::builder().build().unwrap();
+        let dispatcher = DispatcherCore::new(github, "owner".to_string(), "repo".to_string())
+            .with_registry(AgentRegistry::from_yaml(REGISTRY_YAML).unwrap());
+        let task = issue(&[], "");
+
+        let picks: Vec<String> = (0..6)
+            .map(|_| dispatcher.select_agent(&Strategy::RoundRobin, &task, 0, &HashSet::new()).name.clone())
+            .collect();
+        assert_eq!(picks, ["copilot", "codex", "copilot", "copilot", "codex", "copilot"]);
+
+        for _ in 0..20 {
+            assert_ne!(dispatcher.select_agent(&Strategy::Random, &task, 0, &HashSet::new()).name, "paused");
+        }
+
+        let only = Strategy::Only("codex".to_string());
+        assert_eq!(dispatcher.select_agent(&only, &task, 0, &HashSet::new()).name, "codex");
+    }
+
+    #[tokio::test]
+    async fn test_round_robin_continues_across_runs() {
+        let dir = std::env::temp_dir().join(format!("dispatcher-rr-{}", std::process::id()));
+        let path = dir.join("state.json");
+        let task = issue(&[], "");
+        let none = HashSet::new();
+
+        let first = DispatcherCore::new(Octocrab::builder().build().unwrap(), "o".into(), "r".into())
+            .with_state_file(&path)
+            .unwrap();
+        assert_eq!(first.select_agent(&Strategy::RoundRobin, &task, 0, &none).name, "copilot");
+        first.save_state(|_| {}).unwrap();
+
+        // A new run picks up where the last one stopped
+        let second = DispatcherCore::new(Octocrab::builder().build().unwrap(), "o".into(), "r".into())
+            .with_state_file(&path)
+            .unwrap();
+        assert_eq!(second.select_agent(&Strategy::RoundRobin, &task, 0, &none).name, "jules");
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_reclaimed_issue_rebalanced_to_other_agent() {
+        let github = Octocrab::builder().build().unwrap();
+        let dispatcher = DispatcherCore::new(github, "owner".to_string(), "repo".to_string());
+        let avoid: HashSet<String> = ["copilot".to_string()].into();
+        let task = issue(&["bug"], "");
+
+        // Round-robin would start with copilot
+        assert_eq!(dispatcher.select_agent(&Strategy::RoundRobin, &task, 0, &avoid).name, "jules");
+        for _ in 0..10 {
+            assert_eq!(dispatcher.select_agent(&Strategy::Random, &task, 0, &avoid).name, "jules");
+        }
+
+        // Capability-aware prefers copilot for bugs, but not after a reclaim
+        let (agent, _) = dispatcher
+            .select_capable_agent_avoiding(&task, 10, &AgentLoads::new(), &avoid)
+            .unwrap();
+        assert_eq!(agent.name, "jules");
+
+        // Only the avoided agent can take it: still assigned rather than stuck
+        let only = Strategy::Only("copilot".to_string());
+        assert_eq!(dispatcher.select_agent(&only, &task, 0, &avoid).name, "copilot");
+    }
+
+    /// A `cross-referenced` event from issue or PR #`number`, last updated at `updated_at`
+    fn cross_reference(linked_at: &str, number: u64, updated_at: &str, pull_request: bool) -> serde_json::Value {
+        let url = "https://api.github.com/x";
+        let user = serde_json::json!({
+            "login": "octocat", "id": 1, "node_id": "U", "avatar_url": url, "gravatar_id": "",
+            "url": url, "html_url": url, "followers_url": url, "following_url": url, "gists_url": url,
+            "starred_url": url, "subscriptions_url": url, "organizations_url": url, "repos_url": url,
+            "events_url": url, "received_events_url": url, "type": "User", "site_admin": false,
+        });
+        let mut issue = serde_json::json!({
+            "id": number, "node_id": "I", "url": url, "repository_url": url, "labels_url": url,
+            "comments_url": url, "events_url": url, "html_url": url, "number": number,
+            "state": "open", "title": "Linked", "user": user, "labels": [], "assignees": [],
+            "author_association": "OWNER", "locked": false, "comments": 0,
+            "created_at": linked_at, "updated_at": updated_at,
+        });
+        if pull_request {
+            issue["pull_request"] = serde_json::json!({ "url": url, "html_url": url, "diff_url": url, "patch_url": url });
+        }
+        serde_json::json!({
+            "event": "cross-referenced", "created_at": linked_at,
+            "source": { "type": "issue", "issue": issue },
+        })
+    }
+
+    #[test]
+    fn test_timeline_activity() {
+        let events: Vec<TimelineEvent> = serde_json::from_value(serde_json::json!([
+            { "event": "labeled", "created_at": "2026-01-01T00:00:00Z", "label": { "name": "jules", "color": "fff" } },
+            { "event": "labeled", "created_at": "2026-01-02T00:00:00Z", "label": { "name": "bug", "color": "fff" } },
+            { "event": "commented", "created_at": "2026-01-09T00:00:00Z" },
+            { "event": "connected", "created_at": "2026-01-03T00:00:00Z" },
+            cross_reference("2026-01-04T00:00:00Z", 7, "2026-01-05T00:00:00Z", true),
+            // A mention from another issue is not PR activity
+            cross_reference("2026-01-08T00:00:00Z", 8, "2026-01-10T00:00:00Z", false),
+        ]))
+        .unwrap();
+
+        assert_eq!(last_labeled(&events, "Jules"), "2026-01-01T00:00:00Z".parse().ok());
+        assert_eq!(last_labeled(&events, "copilot"), None);
+
+        // Comments are not PR activity
+        assert_eq!(last_pr_activity(&events), "2026-01-05T00:00:00Z".parse().ok());
+        assert_eq!(last_pr_activity(&events[..4]), None);
+    }
+
+    #[test]
+    fn test_old_link_to_recently_updated_pr_is_not_stale() {
+        let events: Vec<TimelineEvent> = serde_json::from_value(serde_json::json!([
+            cross_reference("2026-01-01T00:00:00Z", 7, "2026-02-27T00:00:00Z", true),
+        ]))
+        .unwrap();
+
+        let assigned_at: DateTime<Utc> = "2026-01-01T00:00:00Z".parse().unwrap();
+        let now: DateTime<Utc> = "2026-03-01T00:00:00Z".parse().unwrap();
+        let pr_activity = last_pr_activity(&events);
+
+        assert_eq!(pr_activity, "2026-02-27T00:00:00Z".parse().ok());
+        assert!(!dispatcher_state::is_stale(assigned_at, pr_activity, now, Duration::days(7)));
+        assert!(dispatcher_state::is_stale(assigned_at, None, now, Duration::days(7)));
     }
 
     #[tokio::test]
@@ -399,4 +1391,80 @@ mod tests {
         let risk = dispatcher.analyze_risk(&issue);
         assert!(risk >= 60, "High-risk issue should have risk >= 60");
     }
+
+    fn issue(labels: &[&str], body: &str) -> Issue {
+        Issue {
+            number: 7,
+            title: "Task".to_string(),
+            body: Some(body.to_string()),
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_capability_aware_matches_skills_and_paths() {
+        let github = Octocrab::builder().build().unwrap();
+        let mut registry = AgentRegistry::defaults();
+        registry.agents[1].paths = vec!["tools/issue-syncer/".to_string()];
+        let dispatcher = DispatcherCore::new(github, "owner".to_string(), "repo".to_string())
+            .with_registry(registry);
+        let loads = AgentLoads::new();
+
+        let (agent, reason) = dispatcher
+            .select_capable_agent(&issue(&["bug"], ""), 10, &loads)
+            .unwrap();
+        assert_eq!(agent.name, "copilot");
+        assert!(reason.contains("labels [bug]"));
+
+        let (agent, reason) = dispatcher
+            .select_capable_agent(&issue(&[], "Parser lives in `tools/issue-syncer/src/parser.rs`"), 10, &loads)
+            .unwrap();
+        assert_eq!(agent.name, "jules");
+        assert!(reason.contains("paths [tools/issue-syncer/]"));
+    }
+
+    #[tokio::test]
+    async fn test_capability_aware_respects_load_and_risk() {
+        let github = Octocrab::builder().build().unwrap();
+        let dispatcher = DispatcherCore::new(github, "owner".to_string(), "repo".to_string());
+
+        // Copilot is full: a bug goes to Jules instead
+        let mut loads = AgentLoads::new();
+        loads.insert("copilot".to_string(), 5);
+        let (agent, _) = dispatcher
+            .select_capable_agent(&issue(&["bug"], ""), 10, &loads)
+            .unwrap();
+        assert_eq!(agent.name, "jules");
+
+        // Everyone full: stays in the pool
+        loads.insert("jules".to_string(), 3);
+        assert!(dispatcher.select_capable_agent(&issue(&["bug"], ""), 10, &loads).is_none());
+
+        // Above Jules' ceiling but within Copilot's
+        let (agent, _) = dispatcher
+            .select_capable_agent(&issue(&["chore"], ""), 55, &AgentLoads::new())
+            .unwrap();
+        assert_eq!(agent.name, "copilot");
+
+        // High risk goes to humans
+        let (agent, reason) = dispatcher
+            .select_capable_agent(&issue(&["bug"], ""), 80, &AgentLoads::new())
+            .unwrap();
+        assert_eq!(agent.label, "needs-human");
+        assert!(reason.contains("human"));
+    }
+
+    #[test]
+    fn test_issue_dependencies_and_priority() {
+        let task = issue(&["P1", "bug"], "Blocked by #3\n- [ ] #5\n- [x] #6");
+        assert_eq!(task.dependencies(), vec![3, 5]);
+        assert_eq!(task.priority(), 1);
+        assert_eq!(format_refs(&task.dependencies(), ", "), "#3, #5");
+    }
+
+    #[test]
+    fn test_mentioned_paths() {
+        let issue = issue(&[], "See src/api/auth.rs and (docs/guide.md), not https://example.com/x");
+        assert_eq!(issue.mentioned_paths(), vec!["docs/guide.md", "src/api/auth.rs"]);
+    }
 }
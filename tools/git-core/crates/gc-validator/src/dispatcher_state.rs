@@ -0,0 +1,226 @@
+//! # Dispatcher State - Persistent Assignment History
+//!
+//! Keeps what the dispatcher did between runs in a small JSON file
+//! (by default `.✨/dispatcher-state.json`):
+//!
+//! - the round-robin position, so rotation continues where the last run
+//!   stopped instead of always starting with the first agent
+//! - the active assignment of each issue and when it was made
+//! - an append-only history of assignments and reclaims (capped)
+//!
+//! ```json
+//! {
+//!   "round_robin_index": 7,
+//!   "active": {
+//!     "42": { "agent": "copilot", "assigned_at": "2026-01-05T10:00:00Z", "reason": "..." }
+//!   },
+//!   "history": [
+//!     { "issue_number": 42, "agent": "copilot", "action": "assigned", "at": "2026-01-05T10:00:00Z" }
+//!   ]
+//! }
+//! ```
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+/// History entries kept in the state file
+const MAX_HISTORY: usize = 1000;
+
+/// Current assignment of an issue
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssignmentRecord {
+    pub agent: String,
+    pub assigned_at: DateTime<Utc>,
+    #[serde(default)]
+    pub reason: String,
+}
+
+/// What happened to an issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryAction {
+    /// Issue handed to an agent
+    Assigned,
+    /// Agent label removed after inactivity, issue back in the pool
+    Reclaimed,
+}
+
+/// One entry of the assignment history
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub issue_number: u64,
+    pub agent: String,
+    pub action: HistoryAction,
+    pub at: DateTime<Utc>,
+}
+
+/// Dispatcher state persisted between runs
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DispatcherState {
+    /// Next round-robin slot
+    #[serde(default)]
+    pub round_robin_index: usize,
+    /// Active assignments by issue number
+    #[serde(default)]
+    pub active: BTreeMap<u64, AssignmentRecord>,
+    /// Oldest first
+    #[serde(default)]
+    pub history: Vec<HistoryEntry>,
+}
+
+impl DispatcherState {
+    /// Load state, starting fresh when the file does not exist yet
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid dispatcher state {}", path.display()))
+    }
+
+    /// Write state atomically (temp file + rename)
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        std::fs::rename(&tmp, path)
+            .with_context(|| format!("Failed to replace {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Record a new assignment
+    pub fn record_assignment(&mut self, issue_number: u64, agent: &str, reason: &str, at: DateTime<Utc>) {
+        self.active.insert(
+            issue_number,
+            AssignmentRecord {
+                agent: agent.to_string(),
+                assigned_at: at,
+                reason: reason.to_string(),
+            },
+        );
+        self.push_history(issue_number, agent, HistoryAction::Assigned, at);
+    }
+
+    /// Record that an issue was taken back from an agent
+    pub fn record_reclaim(&mut self, issue_number: u64, agent: &str, at: DateTime<Utc>) {
+        self.active.remove(&issue_number);
+        self.push_history(issue_number, agent, HistoryAction::Reclaimed, at);
+    }
+
+    /// Agents an issue was already reclaimed from (avoided on re-dispatch)
+    pub fn reclaimed_from(&self, issue_number: u64) -> HashSet<&str> {
+        self.history
+            .iter()
+            .filter(|e| e.issue_number == issue_number && e.action == HistoryAction::Reclaimed)
+            .map(|e| e.agent.as_str())
+            .collect()
+    }
+
+    fn push_history(&mut self, issue_number: u64, agent: &str, action: HistoryAction, at: DateTime<Utc>) {
+        self.history.push(HistoryEntry {
+            issue_number,
+            agent: agent.to_string(),
+            action,
+            at,
+        });
+
+        if self.history.len() > MAX_HISTORY {
+            let excess = self.history.len() - MAX_HISTORY;
+            self.history.drain(..excess);
+        }
+    }
+}
+
+/// Whether an assignment has gone stale
+///
+/// Activity is the later of the assignment itself and the newest linked PR
+/// event; the assignment is stale once that is `stale_after` or older.
+pub fn is_stale(
+    assigned_at: DateTime<Utc>,
+    last_pr_activity: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+    stale_after: Duration,
+) -> bool {
+    let last = last_pr_activity.map_or(assigned_at, |pr| pr.max(assigned_at));
+    now - last >= stale_after
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(day: u32) -> DateTime<Utc> {
+        format!("2026-01-{:02}T00:00:00Z", day).parse().unwrap()
+    }
+
+    #[test]
+    fn test_state_round_trip() {
+        let dir = std::env::temp_dir().join(format!("dispatcher-state-{}", std::process::id()));
+        let path = dir.join("state.json");
+
+        assert_eq!(DispatcherState::load(&path).unwrap(), DispatcherState::default());
+
+        let mut state = DispatcherState {
+            round_robin_index: 5,
+            ..Default::default()
+        };
+        state.record_assignment(42, "copilot", "Weighted round-robin", at(1));
+        state.save(&path).unwrap();
+
+        let loaded = DispatcherState::load(&path).unwrap();
+        assert_eq!(loaded, state);
+        assert_eq!(loaded.active[&42].agent, "copilot");
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_reclaim_history() {
+        let mut state = DispatcherState::default();
+        state.record_assignment(7, "jules", "", at(1));
+        state.record_reclaim(7, "jules", at(9));
+
+        assert!(state.active.is_empty());
+        assert_eq!(state.history.len(), 2);
+        assert!(state.reclaimed_from(7).contains("jules"));
+        assert!(state.reclaimed_from(8).is_empty());
+    }
+
+    #[test]
+    fn test_history_is_capped() {
+        let mut state = DispatcherState::default();
+        for n in 0..(MAX_HISTORY as u64 + 10) {
+            state.record_assignment(n, "copilot", "", at(1));
+        }
+
+        assert_eq!(state.history.len(), MAX_HISTORY);
+        assert_eq!(state.history[0].issue_number, 10);
+    }
+
+    #[test]
+    fn test_is_stale() {
+        let week = Duration::days(7);
+
+        assert!(!is_stale(at(1), None, at(5), week));
+        assert!(is_stale(at(1), None, at(8), week));
+
+        // Recent PR activity keeps the assignment alive
+        assert!(!is_stale(at(1), Some(at(6)), at(10), week));
+
+        // PR activity older than the assignment does not count
+        assert!(is_stale(at(5), Some(at(1)), at(12), week));
+    }
+}
@@ -0,0 +1,536 @@
+//! # Flakiness Analysis - Telling Flaky Jobs from Broken Ones
+//!
+//! A failure is **flaky** when the same job (or step) also passed on the
+//! same commit. Two kinds of evidence are collected over a time window:
+//!
+//! ```text
+//! Re-run flip:  run #42 attempt 1 ❌ → attempt 2 ✅   (same run, same SHA)
+//! SHA flip:     push run on abc123 ❌, PR run on abc123 ✅
+//! ```
+//!
+//! Failures with no passing counterpart on their SHA are treated as real
+//! breakage and do not raise the score.
+//!
+//! ```text
+//! score = flaky failures / executions      (0.0 = stable, 1.0 = coin toss)
+//! ```
+//!
+//! Jobs scoring above a threshold can get a tracking issue (label `flaky`)
+//! that is updated in place on later runs.
+
+use crate::github::{GitHubClient, Job, WorkflowRun};
+use crate::run_store::RunStore;
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use tracing::info;
+
+/// Label on flaky-job tracking issues
+pub const FLAKY_LABEL: &str = "flaky";
+
+/// Outcome of one job (or step) execution
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Observation {
+    pub workflow: String,
+    pub job: String,
+    pub step: Option<String>,
+    pub run_id: u64,
+    pub attempt: u32,
+    pub head_sha: String,
+    pub passed: bool,
+}
+
+/// Flakiness of one job or step
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FlakyStat {
+    pub workflow: String,
+    pub job: String,
+    pub step: Option<String>,
+    pub executions: usize,
+    pub failures: usize,
+    /// Failures on a SHA where the same job/step also passed
+    pub flaky_failures: usize,
+    /// Runs where a re-run turned a failure into a pass
+    pub rerun_flips: usize,
+    pub score: f64,
+    /// Most recent run with a flaky failure
+    pub last_flaky_run: Option<u64>,
+}
+
+/// Result of a flakiness analysis
+#[derive(Debug, Clone, Serialize)]
+pub struct FlakinessReport {
+    pub window_days: i64,
+    pub runs_analyzed: usize,
+    pub jobs: Vec<FlakyStat>,
+    pub steps: Vec<FlakyStat>,
+}
+
+/// Turn the jobs of one run attempt into job and step observations
+pub fn observations_from_jobs(run: &WorkflowRun, attempt: u32, jobs: &[Job]) -> Vec<Observation> {
+    let passed = |conclusion: Option<&str>| match conclusion {
+        Some("success") => Some(true),
+        Some("failure") | Some("timed_out") => Some(false),
+        // Cancelled and skipped executions say nothing about flakiness
+        _ => None,
+    };
+
+    let mut observations = Vec::new();
+
+    for job in jobs {
+        let Some(job_passed) = passed(job.conclusion.as_deref()) else {
+            continue;
+        };
+
+        let observe = |step: Option<String>, passed: bool| Observation {
+            workflow: run.name.clone(),
+            job: job.name.clone(),
+            step,
+            run_id: run.id,
+            attempt,
+            head_sha: run.head_sha.clone(),
+            passed,
+        };
+
+        observations.push(observe(None, job_passed));
+
+        for step in job.steps.iter().flatten() {
+            if let Some(step_passed) = passed(step.conclusion.as_deref()) {
+                observations.push(observe(Some(step.name.clone()), step_passed));
+            }
+        }
+    }
+
+    observations
+}
+
+/// Score every job/step key found in the observations
+///
+/// Results are sorted by score, then flaky failure count, descending.
+pub fn score(observations: &[Observation]) -> Vec<FlakyStat> {
+    type Key = (String, String, Option<String>);
+
+    let mut groups: BTreeMap<Key, Vec<&Observation>> = BTreeMap::new();
+    for obs in observations {
+        groups
+            .entry((obs.workflow.clone(), obs.job.clone(), obs.step.clone()))
+            .or_default()
+            .push(obs);
+    }
+
+    let mut stats: Vec<FlakyStat> = groups
+        .into_iter()
+        .map(|((workflow, job, step), group)| {
+            let passed_shas: HashSet<&str> = group
+                .iter()
+                .filter(|o| o.passed)
+                .map(|o| o.head_sha.as_str())
+                .collect();
+
+            let flaky: Vec<&&Observation> = group
+                .iter()
+                .filter(|o| !o.passed && passed_shas.contains(o.head_sha.as_str()))
+                .collect();
+
+            // Per run: did a later attempt pass after an earlier failure?
+            let mut attempts: HashMap<u64, Vec<(u32, bool)>> = HashMap::new();
+            for o in &group {
+                attempts.entry(o.run_id).or_default().push((o.attempt, o.passed));
+            }
+            let rerun_flips = attempts
+                .values()
+                .filter(|runs| {
+                    runs.iter().any(|&(failed_at, passed)| {
+                        !passed && runs.iter().any(|&(a, p)| p && a > failed_at)
+                    })
+                })
+                .count();
+
+            let executions = group.len();
+            let failures = group.iter().filter(|o| !o.passed).count();
+
+            FlakyStat {
+                workflow,
+                job,
+                step,
+                executions,
+                failures,
+                flaky_failures: flaky.len(),
+                rerun_flips,
+                score: flaky.len() as f64 / executions as f64,
+                last_flaky_run: flaky.iter().map(|o| o.run_id).max(),
+            }
+        })
+        .collect();
+
+    stats.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then(b.flaky_failures.cmp(&a.flaky_failures))
+            .then_with(|| (&a.workflow, &a.job, &a.step).cmp(&(&b.workflow, &b.job, &b.step)))
+    });
+    stats
+}
+
+/// Build a report from observations, keeping the `top` flaky jobs and steps
+pub fn build_report(
+    observations: &[Observation],
+    window_days: i64,
+    runs_analyzed: usize,
+    min_executions: usize,
+    top: usize,
+) -> FlakinessReport {
+    let (jobs, steps): (Vec<FlakyStat>, Vec<FlakyStat>) = score(observations)
+        .into_iter()
+        .filter(|s| s.flaky_failures > 0 && s.executions >= min_executions)
+        .partition(|s| s.step.is_none());
+
+    FlakinessReport {
+        window_days,
+        runs_analyzed,
+        jobs: jobs.into_iter().take(top).collect(),
+        steps: steps.into_iter().take(top).collect(),
+    }
+}
+
+/// Observations of every stored attempt of the runs created in the last `days` days
+///
+/// Returns the observations and the number of runs they came from.
+pub fn observations_from_store(store: &RunStore, days: i64) -> Result<(Vec<Observation>, usize)> {
+    let analyses = store.analyses_since(Utc::now() - Duration::days(days))?;
+
+    let mut observations = Vec::new();
+    for analysis in &analyses {
+        let run = &analysis.run;
+        for (attempt, jobs) in store.attempts(run.id)? {
+            observations.extend(observations_from_jobs(run, attempt, &jobs));
+        }
+        observations.extend(observations_from_jobs(run, run.run_attempt.max(1), &analysis.jobs));
+    }
+
+    Ok((observations, analyses.len()))
+}
+
+/// Score the stored runs in the window and print the report
+pub async fn run_flakiness_analysis(
+    client: &GitHubClient,
+    store: &RunStore,
+    days: i64,
+    top: usize,
+    min_executions: usize,
+    track_threshold: Option<f64>,
+    output_format: &str,
+) -> Result<FlakinessReport> {
+    info!("🎲 Analyzing flakiness over the last {} days...", days);
+
+    let (observations, runs_analyzed) = observations_from_store(store, days)?;
+    let report = build_report(&observations, days, runs_analyzed, min_executions, top);
+
+    match output_format {
+        "json" => println!("{}", serde_json::to_string_pretty(&report)?),
+        "markdown" => println!("{}", report.to_markdown()),
+        _ => println!("{}", report.to_terminal()),
+    }
+
+    if let Some(threshold) = track_threshold {
+        track_flaky_jobs(client, &report, threshold).await?;
+    }
+
+    Ok(report)
+}
+
+/// Open or update one tracking issue per flaky job above `threshold`
+async fn track_flaky_jobs(client: &GitHubClient, report: &FlakinessReport, threshold: f64) -> Result<()> {
+    let existing: HashMap<String, u64> = client
+        .list_open_issues(FLAKY_LABEL)
+        .await?
+        .into_iter()
+        .map(|issue| (issue.title, issue.number))
+        .collect();
+
+    for stat in report.jobs.iter().filter(|s| s.score >= threshold) {
+        let title = tracking_title(stat);
+        let body = tracking_body(stat, report);
+
+        match existing.get(&title) {
+            Some(&number) => {
+                client.update_issue_body(number, &body).await?;
+                info!("🔄 Updated flaky-job issue #{}: {}", number, title);
+            }
+            None => {
+                let issue = client.create_issue(&title, &body, &[FLAKY_LABEL]).await?;
+                info!("🆕 Opened flaky-job issue #{}: {}", issue.number, issue.html_url);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn tracking_title(stat: &FlakyStat) -> String {
+    format!("🎲 Flaky job: {} / {}", stat.workflow, stat.job)
+}
+
+fn tracking_body(stat: &FlakyStat, report: &FlakinessReport) -> String {
+    let mut body = String::new();
+
+    body.push_str(&format!(
+        "Job **{}** in workflow **{}** failed and passed on the same commit.\n\n",
+        stat.job, stat.workflow
+    ));
+    body.push_str(&format!("### 📊 Last {} days\n\n", report.window_days));
+    body.push_str("| Metric | Value |\n");
+    body.push_str("|--------|-------|\n");
+    body.push_str(&format!("| Flakiness score | {:.1}% |\n", stat.score * 100.0));
+    body.push_str(&format!("| Executions | {} |\n", stat.executions));
+    body.push_str(&format!("| Failures | {} ({} flaky) |\n", stat.failures, stat.flaky_failures));
+    body.push_str(&format!("| Re-run flips | {} |\n", stat.rerun_flips));
+    if let Some(run) = stat.last_flaky_run {
+        body.push_str(&format!("| Last flaky run | {} |\n", run));
+    }
+
+    let steps: Vec<&FlakyStat> = report
+        .steps
+        .iter()
+        .filter(|s| s.workflow == stat.workflow && s.job == stat.job)
+        .collect();
+    if !steps.is_empty() {
+        body.push_str("\n### 🔬 Flaky steps\n\n");
+        for step in steps {
+            body.push_str(&format!(
+                "- `{}`: {:.1}% ({} of {} executions)\n",
+                step.step.as_deref().unwrap_or_default(),
+                step.score * 100.0,
+                step.flaky_failures,
+                step.executions
+            ));
+        }
+    }
+
+    body.push_str("\n---\n*Updated by Git-Core Protocol Workflow Orchestrator*\n");
+    body
+}
+
+impl FlakinessReport {
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::new();
+
+        md.push_str(&format!("# 🎲 Flakiness Report (last {} days)\n\n", self.window_days));
+        md.push_str(&format!("**Runs analyzed:** {}\n\n", self.runs_analyzed));
+
+        if self.jobs.is_empty() {
+            md.push_str("✅ No flaky jobs detected.\n");
+            return md;
+        }
+
+        md.push_str("## Flaky Jobs\n\n");
+        md.push_str("| Workflow | Job | Score | Flaky / Runs | Re-run flips |\n");
+        md.push_str("|----------|-----|-------|--------------|--------------|\n");
+        for s in &self.jobs {
+            md.push_str(&format!(
+                "| {} | {} | {:.1}% | {} / {} | {} |\n",
+                s.workflow, s.job, s.score * 100.0, s.flaky_failures, s.executions, s.rerun_flips
+            ));
+        }
+
+        if !self.steps.is_empty() {
+            md.push_str("\n## Flaky Steps\n\n");
+            md.push_str("| Job | Step | Score | Flaky / Runs |\n");
+            md.push_str("|-----|------|-------|--------------|\n");
+            for s in &self.steps {
+                md.push_str(&format!(
+                    "| {} | {} | {:.1}% | {} / {} |\n",
+                    s.job,
+                    s.step.as_deref().unwrap_or_default(),
+                    s.score * 100.0,
+                    s.flaky_failures,
+                    s.executions
+                ));
+            }
+        }
+
+        md
+    }
+
+    pub fn to_terminal(&self) -> String {
+        let mut out = String::new();
+        out.push_str(&format!(
+            "\n🎲 Flakiness Report: {} runs over {} days\n",
+            self.runs_analyzed, self.window_days
+        ));
+
+        if self.jobs.is_empty() {
+            out.push_str("   ✅ No flaky jobs detected\n");
+            return out;
+        }
+
+        for s in &self.jobs {
+            out.push_str(&format!(
+                "   {:>5.1}%  {} / {}  ({} flaky of {}, {} re-run flips)\n",
+                s.score * 100.0,
+                s.workflow,
+                s.job,
+                s.flaky_failures,
+                s.executions,
+                s.rerun_flips
+            ));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::WorkflowAnalysis;
+
+    fn obs(job: &str, run_id: u64, attempt: u32, sha: &str, passed: bool) -> Observation {
+        Observation {
+            workflow: "CI".to_string(),
+            job: job.to_string(),
+            step: None,
+            run_id,
+            attempt,
+            head_sha: sha.to_string(),
+            passed,
+        }
+    }
+
+    #[test]
+    fn test_flaky_vs_broken() {
+        let observations = vec![
+            // flaky: fails then passes on re-run
+            obs("test", 1, 1, "a", false),
+            obs("test", 1, 2, "a", true),
+            obs("test", 2, 1, "b", true),
+            obs("test", 3, 1, "c", true),
+            // broken: fails on every attempt of its SHA
+            obs("lint", 1, 1, "a", false),
+            obs("lint", 1, 2, "a", false),
+            obs("lint", 2, 1, "b", true),
+        ];
+
+        let stats = score(&observations);
+        let test = stats.iter().find(|s| s.job == "test").unwrap();
+        let lint = stats.iter().find(|s| s.job == "lint").unwrap();
+
+        assert_eq!(test.flaky_failures, 1);
+        assert_eq!(test.rerun_flips, 1);
+        assert_eq!(test.score, 0.25);
+        assert_eq!(test.last_flaky_run, Some(1));
+
+        assert_eq!(lint.failures, 2);
+        assert_eq!(lint.flaky_failures, 0);
+        assert_eq!(lint.score, 0.0);
+    }
+
+    #[test]
+    fn test_sha_flip_across_runs() {
+        // Push and PR runs on the same commit disagree
+        let stats = score(&[obs("e2e", 10, 1, "x", false), obs("e2e", 11, 1, "x", true)]);
+
+        assert_eq!(stats[0].flaky_failures, 1);
+        assert_eq!(stats[0].rerun_flips, 0);
+        assert_eq!(stats[0].score, 0.5);
+    }
+
+    #[test]
+    fn test_build_report_filters_and_splits() {
+        let mut observations = vec![
+            obs("test", 1, 1, "a", false),
+            obs("test", 1, 2, "a", true),
+            obs("test", 2, 1, "b", true),
+            obs("rare", 5, 1, "q", false),
+            obs("rare", 5, 2, "q", true),
+        ];
+        let mut step = obs("test", 1, 1, "a", false);
+        step.step = Some("Run tests".to_string());
+        observations.push(step.clone());
+        step.attempt = 2;
+        step.passed = true;
+        observations.push(step);
+
+        let report = build_report(&observations, 14, 3, 3, 10);
+
+        // "rare" has too few executions to judge
+        assert_eq!(report.jobs.len(), 1);
+        assert_eq!(report.jobs[0].job, "test");
+        assert!(report.steps.is_empty());
+
+        let report = build_report(&observations, 14, 3, 2, 10);
+        assert_eq!(report.jobs.len(), 2);
+        assert_eq!(report.steps[0].step.as_deref(), Some("Run tests"));
+        assert!(report.to_markdown().contains("| CI | rare | 50.0% | 1 / 2 | 1 |"));
+    }
+
+    fn run(id: u64, attempt: u32, created_at: &str) -> WorkflowRun {
+        WorkflowRun {
+            id,
+            name: "CI".to_string(),
+            status: "completed".to_string(),
+            conclusion: Some("failure".to_string()),
+            html_url: String::new(),
+            created_at: created_at.to_string(),
+            updated_at: created_at.to_string(),
+            run_attempt: attempt,
+            workflow_id: 1,
+            head_branch: "main".to_string(),
+            head_sha: "abc".to_string(),
+            path: None,
+        }
+    }
+
+    fn job(name: &str, conclusion: &str) -> Job {
+        Job {
+            id: 1,
+            name: name.to_string(),
+            status: "completed".to_string(),
+            conclusion: Some(conclusion.to_string()),
+            created_at: None,
+            started_at: None,
+            completed_at: None,
+            steps: None,
+        }
+    }
+
+    #[test]
+    fn test_observations_ignore_cancelled() {
+        let observations = observations_from_jobs(&run(7, 1, ""), 1, &[job("a", "failure"), job("b", "cancelled")]);
+
+        assert_eq!(observations.len(), 1);
+        assert_eq!(observations[0].head_sha, "abc");
+        assert!(!observations[0].passed);
+    }
+
+    #[test]
+    fn test_observations_from_store() {
+        let mut store = RunStore::open_in_memory().unwrap();
+        let now = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        let analysis = |run: WorkflowRun, jobs: Vec<Job>| WorkflowAnalysis {
+            run,
+            jobs,
+            logs: None,
+            errors: vec![],
+            warnings: vec![],
+            findings: vec![],
+            cache: Default::default(),
+            timeline: Default::default(),
+            duration_seconds: None,
+        };
+
+        // Attempt 1 failed, the re-run passed
+        store.upsert(&analysis(run(1, 2, &now), vec![job("test", "success")])).unwrap();
+        store.upsert_attempt(1, 1, &[job("test", "failure")]).unwrap();
+        // Outside the window
+        let old = Job { id: 2, ..job("test", "failure") };
+        store.upsert(&analysis(run(2, 1, "2020-01-01T00:00:00Z"), vec![old])).unwrap();
+
+        let (observations, runs) = observations_from_store(&store, 14).unwrap();
+        assert_eq!(runs, 1);
+
+        let stats = score(&observations);
+        assert_eq!(stats[0].executions, 2);
+        assert_eq!(stats[0].rerun_flips, 1);
+    }
+}
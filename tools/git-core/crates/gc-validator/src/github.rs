@@ -8,6 +8,9 @@ use tokio::sync::Semaphore;
 use futures::future::join_all;
 use tracing::{info, debug, warn};
 
+use crate::log_analysis::{self, CacheStats, LogAnalyzer, LogFinding};
+use crate::timeline::{RunTimeline, TimelineMetrics};
+
 /// GitHub API client with rate limiting and parallel execution
 pub struct GitHubClient {
     client: Client,
@@ -29,6 +32,8 @@ pub struct WorkflowRun {
     pub workflow_id: u64,
     pub head_branch: String,
     pub head_sha: String,
+    /// Workflow file, e.g. `.github/workflows/ci.yml`
+    pub path: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -37,18 +42,20 @@ pub struct WorkflowRunsResponse {
     pub workflow_runs: Vec<WorkflowRun>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Job {
     pub id: u64,
     pub name: String,
     pub status: String,
     pub conclusion: Option<String>,
+    /// When the job was queued
+    pub created_at: Option<String>,
     pub started_at: Option<String>,
     pub completed_at: Option<String>,
     pub steps: Option<Vec<Step>>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Step {
     pub name: String,
     pub status: String,
@@ -98,6 +105,31 @@ pub struct CreateIssueCommentRequest {
     pub body: String,
 }
 
+#[derive(Debug, Serialize)]
+pub struct CreateIssueRequest {
+    pub title: String,
+    pub body: String,
+    pub labels: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct IssueSummary {
+    pub number: u64,
+    pub title: String,
+    pub html_url: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct CommentAuthor {
+    pub login: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct IssueComment {
+    pub body: String,
+    pub user: CommentAuthor,
+}
+
 #[derive(Debug, Clone)]
 pub struct WorkflowAnalysis {
     pub run: WorkflowRun,
@@ -105,6 +137,12 @@ pub struct WorkflowAnalysis {
     pub logs: Option<String>,
     pub errors: Vec<String>,
     pub warnings: Vec<String>,
+    /// Failing lines mined from the logs of failed jobs
+    pub findings: Vec<LogFinding>,
+    /// Cache restores from the logs of jobs with cache steps
+    pub cache: CacheStats,
+    /// Parallelism metrics from job timestamps
+    pub timeline: TimelineMetrics,
     pub duration_seconds: Option<i64>,
 }
 
@@ -168,6 +206,74 @@ impl GitHubClient {
         Ok(response.workflow_runs)
     }
 
+    /// Get workflow runs created since `since` (paginated, up to `max_runs`)
+    pub async fn get_workflow_runs_since(&self, since: chrono::DateTime<chrono::Utc>, max_runs: usize) -> Result<Vec<WorkflowRun>> {
+        let mut runs = Vec::new();
+        let mut page = 1;
+
+        while runs.len() < max_runs {
+            let url = self.api_url(&format!(
+                "/actions/runs?per_page=100&page={}&created=>={}",
+                page,
+                since.format("%Y-%m-%dT%H:%M:%SZ")
+            ));
+
+            let response: WorkflowRunsResponse = self.client
+                .get(&url)
+                .send()
+                .await?
+                .json()
+                .await
+                .context("Failed to parse workflow runs")?;
+
+            let count = response.workflow_runs.len();
+            runs.extend(response.workflow_runs);
+
+            if count < 100 {
+                break;
+            }
+            page += 1;
+        }
+
+        runs.truncate(max_runs);
+        info!("📊 Found {} workflow runs since {}", runs.len(), since.format("%Y-%m-%d"));
+        Ok(runs)
+    }
+
+    /// Get jobs for a specific attempt of a workflow run (all pages)
+    pub async fn get_attempt_jobs(&self, run_id: u64, attempt: u32) -> Result<Vec<Job>> {
+        let _permit = self.semaphore.acquire().await?;
+
+        let mut jobs = Vec::new();
+        let mut page = 1;
+
+        loop {
+            let url = self.api_url(&format!(
+                "/actions/runs/{}/attempts/{}/jobs?per_page=100&page={}",
+                run_id, attempt, page
+            ));
+
+            let response: JobsResponse = self.client
+                .get(&url)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await
+                .context("Failed to parse attempt jobs")?;
+
+            let count = response.jobs.len();
+            jobs.extend(response.jobs);
+
+            if count < 100 {
+                break;
+            }
+            page += 1;
+        }
+
+        Ok(jobs)
+    }
+
     /// Get jobs for a workflow run
     pub async fn get_jobs(&self, run_id: u64) -> Result<Vec<Job>> {
         let _permit = self.semaphore.acquire().await?;
@@ -246,19 +352,59 @@ impl GitHubClient {
 
         let mut errors = Vec::new();
         let mut warnings = Vec::new();
+        let mut findings = Vec::new();
+        let mut cache = CacheStats::default();
+        let analyzer = LogAnalyzer::new();
+        // Named cache steps can only be recognized from the workflow file
+        let cache_steps = run
+            .path
+            .as_deref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .map(|workflow| log_analysis::cache_step_names(&workflow))
+            .unwrap_or_default();
 
         // Collect errors and warnings from jobs
         for job in &jobs {
-            if job.conclusion.as_deref() == Some("failure") {
+            let failed = job.conclusion.as_deref() == Some("failure");
+
+            // Logs are only downloaded when there is something to mine
+            let log = if failed || log_analysis::may_use_cache(job, &cache_steps) {
+                match self.get_job_logs(job.id).await {
+                    Ok(log) => Some(log),
+                    Err(e) => {
+                        warn!("Failed to download logs for job {}: {}", job.id, e);
+                        None
+                    }
+                }
+            } else {
+                None
+            };
+
+            if let Some(log) = &log {
+                cache += log_analysis::cache_stats(log);
+            }
+
+            if failed {
                 errors.push(format!("Job '{}' failed", job.name));
 
+                // Mine the failed job's log for the actual error lines
+                let job_findings = log
+                    .as_deref()
+                    .map(|log| analyzer.analyze(job, log))
+                    .unwrap_or_default();
+
                 if let Some(steps) = &job.steps {
                     for step in steps {
                         if step.conclusion.as_deref() == Some("failure") {
-                            errors.push(format!("  - Step '{}' failed", step.name));
+                            match job_findings.iter().find(|f| f.step.as_deref() == Some(step.name.as_str())) {
+                                Some(finding) => errors.push(format!("  - Step '{}' failed: {}", step.name, finding.line)),
+                                None => errors.push(format!("  - Step '{}' failed", step.name)),
+                            }
                         }
                     }
                 }
+
+                findings.extend(job_findings);
             }
 
             if job.conclusion.as_deref() == Some("cancelled") {
@@ -266,17 +412,10 @@ impl GitHubClient {
             }
         }
 
-        // Calculate duration
-        let duration_seconds = if let (Some(start), Some(end)) = (
-            jobs.first().and_then(|j| j.started_at.as_ref()),
-            jobs.last().and_then(|j| j.completed_at.as_ref()),
-        ) {
-            chrono::DateTime::parse_from_rfc3339(end)
-                .ok()
-                .and_then(|e| chrono::DateTime::parse_from_rfc3339(start).ok().map(|s| (e - s).num_seconds()))
-        } else {
-            None
-        };
+        // Duration and parallelism from the job intervals (API order is not chronological)
+        let run_timeline = RunTimeline::from_jobs(&jobs);
+        let duration_seconds = run_timeline.duration_seconds();
+        let timeline = run_timeline.metrics();
 
         Ok(WorkflowAnalysis {
             run,
@@ -284,19 +423,22 @@ impl GitHubClient {
             logs: None, // Fetch on demand to save API calls
             errors,
             warnings,
+            findings,
+            cache,
+            timeline,
             duration_seconds,
         })
     }
 
-    /// Create a PR with validation results
-    pub async fn create_pr(&self, title: &str, body: &str, branch: &str) -> Result<PRResponse> {
+    /// Create a PR from `branch` into `base`
+    pub async fn create_pr(&self, title: &str, body: &str, branch: &str, base: &str) -> Result<PRResponse> {
         let url = self.api_url("/pulls");
 
         let request = CreatePRRequest {
             title: title.to_string(),
             body: body.to_string(),
             head: branch.to_string(),
-            base: "main".to_string(),
+            base: base.to_string(),
             draft: false,
         };
 
@@ -305,6 +447,8 @@ impl GitHubClient {
             .json(&request)
             .send()
             .await?
+            .error_for_status()
+            .context("Failed to create PR")?
             .json()
             .await?;
 
@@ -328,9 +472,201 @@ impl GitHubClient {
         Ok(())
     }
 
+    /// Comments on an issue or PR (all pages)
+    pub async fn list_issue_comments(&self, issue_number: u64) -> Result<Vec<IssueComment>> {
+        let mut comments = Vec::new();
+        let mut page = 1;
+
+        loop {
+            let url = self.api_url(&format!("/issues/{}/comments?per_page=100&page={}", issue_number, page));
+
+            let listed: Vec<IssueComment> = self.client
+                .get(&url)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await
+                .context("Failed to list comments")?;
+
+            let count = listed.len();
+            comments.extend(listed);
+
+            if count < 100 {
+                break;
+            }
+            page += 1;
+        }
+
+        Ok(comments)
+    }
+
+    /// Most recent comments across all issues and PRs of the repository
+    pub async fn list_recent_comments(&self) -> Result<Vec<IssueComment>> {
+        let url = self.api_url("/issues/comments?sort=created&direction=desc&per_page=100");
+
+        let comments = self.client
+            .get(&url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("Failed to list recent comments")?;
+
+        Ok(comments)
+    }
+
+    /// Authors of the reviews on the most recently updated pull requests
+    pub async fn recent_review_authors(&self) -> Result<Vec<String>> {
+        #[derive(Deserialize)]
+        struct PullRequest {
+            number: u64,
+        }
+
+        #[derive(Deserialize)]
+        struct Review {
+            user: Option<CommentAuthor>,
+        }
+
+        let url = self.api_url("/pulls?state=all&sort=updated&direction=desc&per_page=10");
+        let pulls: Vec<PullRequest> = self.client
+            .get(&url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("Failed to list pull requests")?;
+
+        let mut authors = Vec::new();
+        for pull in pulls {
+            let url = self.api_url(&format!("/pulls/{}/reviews?per_page=100", pull.number));
+            let reviews: Vec<Review> = self.client
+                .get(&url)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await
+                .context("Failed to list pull request reviews")?;
+            authors.extend(reviews.into_iter().filter_map(|r| r.user).map(|u| u.login));
+        }
+
+        Ok(authors)
+    }
+
+    /// Slugs of the GitHub Apps that created check suites on the default branch head
+    ///
+    /// Installed apps subscribed to check events get a suite on every push,
+    /// even before they reviewed or commented anything.
+    pub async fn default_branch_check_suite_apps(&self) -> Result<Vec<String>> {
+        #[derive(Deserialize)]
+        struct CheckSuitesResponse {
+            check_suites: Vec<CheckSuite>,
+        }
+
+        #[derive(Deserialize)]
+        struct CheckSuite {
+            app: Option<App>,
+        }
+
+        #[derive(Deserialize)]
+        struct App {
+            slug: String,
+        }
+
+        let branch = self.get_default_branch().await?;
+        let url = self.api_url(&format!("/commits/{}/check-suites?per_page=100", branch));
+
+        let response: CheckSuitesResponse = self.client
+            .get(&url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("Failed to list check suites")?;
+
+        Ok(response.check_suites.into_iter().filter_map(|s| s.app).map(|a| a.slug).collect())
+    }
+
+    /// List open issues carrying a label (all pages, pull requests excluded)
+    pub async fn list_open_issues(&self, label: &str) -> Result<Vec<IssueSummary>> {
+        #[derive(Deserialize)]
+        struct ListedIssue {
+            #[serde(flatten)]
+            summary: IssueSummary,
+            pull_request: Option<serde_json::Value>,
+        }
+
+        let mut issues = Vec::new();
+        let mut page = 1;
+
+        loop {
+            let url = self.api_url(&format!("/issues?state=open&per_page=100&page={}&labels={}", page, label));
+
+            let listed: Vec<ListedIssue> = self.client
+                .get(&url)
+                .send()
+                .await?
+                .error_for_status()
+                .context("Failed to list issues")?
+                .json()
+                .await
+                .context("Failed to list issues")?;
+
+            let count = listed.len();
+            issues.extend(listed.into_iter().filter(|i| i.pull_request.is_none()).map(|i| i.summary));
+
+            if count < 100 {
+                break;
+            }
+            page += 1;
+        }
+
+        Ok(issues)
+    }
+
+    /// Create an issue
+    pub async fn create_issue(&self, title: &str, body: &str, labels: &[&str]) -> Result<IssueSummary> {
+        let url = self.api_url("/issues");
+
+        let request = CreateIssueRequest {
+            title: title.to_string(),
+            body: body.to_string(),
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+        };
+
+        let issue = self.client
+            .post(&url)
+            .json(&request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(issue)
+    }
+
+    /// Replace the body of an issue
+    pub async fn update_issue_body(&self, issue_number: u64, body: &str) -> Result<()> {
+        let url = self.api_url(&format!("/issues/{}", issue_number));
+
+        self.client
+            .patch(&url)
+            .json(&serde_json::json!({ "body": body }))
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+
     /// Create a branch
     pub async fn create_branch(&self, branch_name: &str, from_sha: &str) -> Result<()> {
-        let url = format!("https://api.github.com/repos/{}/{}/git/refs", self.owner, self.repo);
+        let url = self.api_url("/git/refs");
 
         let body = serde_json::json!({
             "ref": format!("refs/heads/{}", branch_name),
@@ -341,14 +677,34 @@ impl GitHubClient {
             .post(&url)
             .json(&body)
             .send()
-            .await?;
+            .await?
+            .error_for_status()
+            .with_context(|| format!("Failed to create branch {}", branch_name))?;
 
         Ok(())
     }
 
-    /// Get default branch SHA
-    pub async fn get_default_branch_sha(&self) -> Result<String> {
-        let url = self.api_url("/git/refs/heads/main");
+    /// Name of the repository's default branch
+    pub async fn get_default_branch(&self) -> Result<String> {
+        #[derive(Deserialize)]
+        struct RepoResponse {
+            default_branch: String,
+        }
+
+        let response: RepoResponse = self.client
+            .get(self.api_url(""))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(response.default_branch)
+    }
+
+    /// Head SHA of a branch
+    pub async fn get_branch_sha(&self, branch: &str) -> Result<String> {
+        let url = self.api_url(&format!("/git/ref/heads/{}", branch));
 
         #[derive(Deserialize)]
         struct RefResponse {
@@ -364,12 +720,57 @@ impl GitHubClient {
             .get(&url)
             .send()
             .await?
+            .error_for_status()
+            .with_context(|| format!("Branch {} not found", branch))?
             .json()
             .await?;
 
         Ok(response.object.sha)
     }
 
+    /// Create or replace a file on a branch (one commit)
+    pub async fn put_file(&self, branch: &str, path: &str, content: &str, message: &str) -> Result<()> {
+        use base64::Engine;
+
+        let url = self.api_url(&format!("/contents/{}", path));
+
+        #[derive(Deserialize)]
+        struct ContentResponse {
+            sha: String,
+        }
+
+        // Replacing an existing file needs its blob SHA
+        let existing = self.client
+            .get(&url)
+            .query(&[("ref", branch)])
+            .send()
+            .await?;
+        let sha = if existing.status().is_success() {
+            Some(existing.json::<ContentResponse>().await?.sha)
+        } else {
+            None
+        };
+
+        let mut body = serde_json::json!({
+            "message": message,
+            "content": base64::engine::general_purpose::STANDARD.encode(content),
+            "branch": branch,
+        });
+        if let Some(sha) = sha {
+            body["sha"] = serde_json::Value::String(sha);
+        }
+
+        self.client
+            .put(&url)
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()
+            .with_context(|| format!("Failed to commit {}", path))?;
+
+        Ok(())
+    }
+
     fn clone_minimal(&self) -> Self {
         Self {
             client: self.client.clone(),
@@ -32,6 +32,21 @@
 //!   - CI failure
 //! ```
 //!
+//! ## Review Requirements
+//!
+//! Only the latest review of each human reviewer counts. Approvals must be
+//! on the current head commit (stale approvals are ignored), and bot or
+//! self reviews never count. When a CODEOWNERS file is loaded, every
+//! touched path needs approval from one of its owners.
+//!
+//! Test and scope detection are language-aware; see [`crate::detectors`].
+//!
+//! ## Merge Queue
+//!
+//! With `with_merge_queue(true)`, an AutoMerge decision labels the PR
+//! `merge-queue` instead of merging it. See [`crate::merge_queue`] for how
+//! queued PRs are updated, re-checked and merged one at a time.
+//!
 //! ## Example
 //!
 //! ```rust,no_run
@@ -54,11 +69,16 @@ use anyhow::Result;
 use octocrab::{Octocrab, models::pulls::ReviewState, params::repos::Commitish};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use tracing::{debug, info, warn};
 
+use crate::codeowners::{parse_team, CodeOwners};
+use crate::detectors::{default_test_detectors, ChangedFile, ScopeResolver, TestDetector};
+use crate::merge_queue::MergeQueue;
+
 const DEFAULT_THRESHOLD: u8 = 70;
+const DEFAULT_MIN_APPROVALS: usize = 1;
 
 /// Decision outcome from PR evaluation
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
@@ -85,20 +105,46 @@ impl Decision {
     }
 }
 
+/// Single review as returned by the GitHub API (chronological order)
+#[derive(Debug, Clone)]
+pub struct ReviewInfo {
+    pub reviewer: String,
+    pub state: ReviewState,
+    pub commit_id: Option<String>,
+    pub is_bot: bool,
+}
+
 /// PR data aggregated from GitHub API
 #[derive(Debug, Clone)]
 pub struct PrData {
     pub number: u64,
+    pub author: String,
+    pub head_sha: String,
     pub labels: Vec<String>,
-    pub reviews: Vec<ReviewState>,
+    pub reviews: Vec<ReviewInfo>,
     pub additions: u32,
     pub deletions: u32,
     pub changed_files: u32,
     pub files: Vec<String>,
+    /// Unified diff per file (absent for binary or very large files)
+    pub patches: HashMap<String, String>,
     pub checks_passed: bool,
     pub head_ref: String,
 }
 
+impl PrData {
+    /// Changed files paired with their diffs, for test detection
+    pub fn changed_files(&self) -> Vec<ChangedFile> {
+        self.files
+            .iter()
+            .map(|path| ChangedFile {
+                path: path.clone(),
+                patch: self.patches.get(path).cloned(),
+            })
+            .collect()
+    }
+}
+
 /// Risk map configuration loaded from .✨/risk-map.json
 #[derive(Debug, Clone, Deserialize)]
 pub struct RiskMap {
@@ -126,6 +172,11 @@ pub struct GuardianCore {
     repo: String,
     risk_map: Option<RiskMap>,
     threshold: u8,
+    merge_queue: bool,
+    codeowners: Option<CodeOwners>,
+    min_approvals: usize,
+    test_detectors: Vec<Box<dyn TestDetector>>,
+    scope_resolver: ScopeResolver,
 }
 
 impl GuardianCore {
@@ -137,6 +188,11 @@ impl GuardianCore {
             repo,
             risk_map: None,
             threshold: DEFAULT_THRESHOLD,
+            merge_queue: false,
+            codeowners: None,
+            min_approvals: DEFAULT_MIN_APPROVALS,
+            test_detectors: default_test_detectors(),
+            scope_resolver: ScopeResolver::default(),
         }
     }
 
@@ -146,6 +202,37 @@ impl GuardianCore {
         self
     }
 
+    /// Queue approved PRs instead of merging immediately (default: false)
+    pub fn with_merge_queue(mut self, enabled: bool) -> Self {
+        self.merge_queue = enabled;
+        self
+    }
+
+    /// Set minimum number of current approvals (default: 1)
+    pub fn with_min_approvals(mut self, min_approvals: usize) -> Self {
+        self.min_approvals = min_approvals;
+        self
+    }
+
+    /// Register an additional test detector
+    pub fn with_test_detector(mut self, detector: Box<dyn TestDetector>) -> Self {
+        self.test_detectors.push(detector);
+        self
+    }
+
+    /// Resolve scopes against workspace members (default: top-level directory)
+    pub fn with_scope_resolver(mut self, resolver: ScopeResolver) -> Self {
+        self.scope_resolver = resolver;
+        self
+    }
+
+    /// Require code owner approval using a CODEOWNERS file
+    pub fn with_codeowners(mut self, path: &str) -> Result<Self> {
+        self.codeowners = Some(CodeOwners::from_file(path)?);
+        info!("✅ CODEOWNERS loaded from {}", path);
+        Ok(self)
+    }
+
     /// Load risk map from file
     pub fn with_risk_map(mut self, path: &str) -> Result<Self> {
         self.risk_map = Some(RiskMap::from_file(path)?);
@@ -168,7 +255,8 @@ impl GuardianCore {
 
         // Calculate confidence score
         let ci_ok = pr_data.checks_passed;
-        let reviews_ok = self.check_reviews(&pr_data.reviews);
+        let team_members = self.fetch_owner_teams(&pr_data.files).await;
+        let review_blocker = self.check_reviews(&pr_data, &team_members);
         let risk_score = self.calculate_risk(&pr_data.files);
         let size_penalty = self.calculate_size_penalty(pr_data.additions, pr_data.deletions);
 
@@ -187,16 +275,12 @@ impl GuardianCore {
         }
 
         // Reviews (required)
-        if reviews_ok {
-            confidence += 40;
-            debug!("✅ Reviews approved: +40 confidence");
-        } else {
-            warn!("❌ No approvals or changes requested");
-            return Ok(Decision::Escalate {
-                reason: "No approved reviews".to_string(),
-                confidence,
-            });
+        if let Some(reason) = review_blocker {
+            warn!("❌ Review requirements not met: {}", reason);
+            return Ok(Decision::Escalate { reason, confidence });
         }
+        confidence += 40;
+        debug!("✅ Reviews approved: +40 confidence");
 
         // Risk analysis (penalty)
         let risk_penalty = (risk_score / 10).min(10); // Max -10
@@ -208,7 +292,7 @@ impl GuardianCore {
         debug!("📏 Size penalty: -{}", size_penalty);
 
         // Tests bonus
-        if self.has_tests(&pr_data.files) {
+        if self.has_tests_in(&pr_data.changed_files()) {
             confidence = confidence.saturating_add(15).min(100);
             debug!("🧪 Tests included: +15 confidence");
         }
@@ -237,20 +321,31 @@ impl GuardianCore {
         let pr = pulls.get(pr_number).await?;
 
         // Fetch reviews
-        let reviews = pulls.list_reviews(pr_number).send().await?;
-        let review_states: Vec<ReviewState> = reviews
+        let reviews = pulls.list_reviews(pr_number).per_page(100).send().await?;
+        let reviews: Vec<ReviewInfo> = reviews
             .items
             .into_iter()
-            .filter_map(|r| r.state)
+            .filter_map(|r| {
+                let user = r.user?;
+                Some(ReviewInfo {
+                    is_bot: user.r#type == "Bot" || user.login.ends_with("[bot]"),
+                    reviewer: user.login,
+                    state: r.state?,
+                    commit_id: r.commit_id,
+                })
+            })
             .collect();
 
         // Fetch files
         let files_page = pulls.list_files(pr_number).await?;
-        let files: Vec<String> = files_page
-            .items
-            .into_iter()
-            .map(|f| f.filename)
-            .collect();
+        let mut files = Vec::with_capacity(files_page.items.len());
+        let mut patches = HashMap::new();
+        for entry in files_page.items {
+            if let Some(patch) = entry.patch {
+                patches.insert(entry.filename.clone(), patch);
+            }
+            files.push(entry.filename);
+        }
 
         // Fetch checks
         let head_ref = pr.head.ref_field.clone();
@@ -267,12 +362,15 @@ impl GuardianCore {
 
         Ok(PrData {
             number: pr_number,
+            author: pr.user.map(|u| u.login).unwrap_or_default(),
+            head_sha: pr.head.sha.clone(),
             labels: pr.labels.unwrap_or_default().into_iter().map(|l| l.name).collect(),
-            reviews: review_states,
+            reviews,
             additions: pr.additions.unwrap_or(0) as u32,
             deletions: pr.deletions.unwrap_or(0) as u32,
             changed_files: pr.changed_files.unwrap_or(0) as u32,
             files,
+            patches,
             checks_passed,
             head_ref: pr.head.ref_field,
         })
@@ -289,15 +387,136 @@ impl GuardianCore {
         None
     }
 
-    /// Check review status
-    fn check_reviews(&self, reviews: &[ReviewState]) -> bool {
-        let approved = reviews.iter().filter(|r| **r == ReviewState::Approved).count();
-        let changes_requested = reviews
+    /// Latest effective review state per human reviewer (lowercase login)
+    ///
+    /// Comments don't override an earlier verdict, matching GitHub.
+    fn latest_reviews<'a>(&self, pr: &'a PrData) -> HashMap<String, &'a ReviewInfo> {
+        let author = pr.author.to_lowercase();
+        let mut latest = HashMap::new();
+
+        for review in &pr.reviews {
+            let reviewer = review.reviewer.to_lowercase();
+            if review.is_bot || reviewer == author {
+                continue;
+            }
+            if matches!(
+                review.state,
+                ReviewState::Approved | ReviewState::ChangesRequested | ReviewState::Dismissed
+            ) {
+                latest.insert(reviewer, review);
+            }
+        }
+
+        latest
+    }
+
+    /// Reviewers whose latest review approves the current head commit
+    pub fn current_approvers(&self, pr: &PrData) -> HashSet<String> {
+        self.latest_reviews(pr)
+            .into_iter()
+            .filter(|(_, r)| {
+                r.state == ReviewState::Approved && r.commit_id.as_deref() == Some(pr.head_sha.as_str())
+            })
+            .map(|(reviewer, _)| reviewer)
+            .collect()
+    }
+
+    /// Check review requirements, returning the reason when unmet
+    ///
+    /// `team_members` maps `@org/team` owners to their lowercase logins.
+    pub fn check_reviews(
+        &self,
+        pr: &PrData,
+        team_members: &HashMap<String, HashSet<String>>,
+    ) -> Option<String> {
+        let mut changes_requested: Vec<String> = self
+            .latest_reviews(pr)
+            .into_iter()
+            .filter(|(_, r)| r.state == ReviewState::ChangesRequested)
+            .map(|(_, r)| format!("@{}", r.reviewer))
+            .collect();
+        if !changes_requested.is_empty() {
+            changes_requested.sort();
+            return Some(format!("Changes requested by {}", changes_requested.join(", ")));
+        }
+
+        let approvers = self.current_approvers(pr);
+        if approvers.len() < self.min_approvals {
+            return Some(format!(
+                "{} approval(s) on head commit, {} required",
+                approvers.len(),
+                self.min_approvals
+            ));
+        }
+
+        let Some(codeowners) = &self.codeowners else {
+            return None;
+        };
+
+        let mut missing: Vec<String> = Vec::new();
+        for file in &pr.files {
+            let Some(rule) = codeowners.rule_for(file) else {
+                continue;
+            };
+            let owners = &rule.owners;
+            if owners.is_empty() {
+                continue;
+            }
+
+            let satisfied = owners.iter().any(|owner| {
+                if parse_team(owner).is_some() {
+                    team_members
+                        .get(owner)
+                        .is_some_and(|members| !members.is_disjoint(&approvers))
+                } else {
+                    owner
+                        .strip_prefix('@')
+                        .is_some_and(|login| approvers.contains(&login.to_lowercase()))
+                }
+            });
+
+            if !satisfied {
+                debug!("👥 {} needs approval from {} (CODEOWNERS `{}`)", file, owners.join(" "), rule.pattern);
+                missing.push(file.clone());
+            }
+        }
+
+        if missing.is_empty() {
+            None
+        } else {
+            Some(format!("Missing code owner approval for: {}", missing.join(", ")))
+        }
+    }
+
+    /// Resolve members of every team owning a touched path
+    async fn fetch_owner_teams(&self, files: &[String]) -> HashMap<String, HashSet<String>> {
+        let mut members = HashMap::new();
+        let Some(codeowners) = &self.codeowners else {
+            return members;
+        };
+
+        let teams: HashSet<&String> = files
             .iter()
-            .filter(|r| **r == ReviewState::ChangesRequested)
-            .count();
+            .filter_map(|f| codeowners.owners_for(f))
+            .flatten()
+            .filter(|owner| parse_team(owner).is_some())
+            .collect();
 
-        approved > 0 && changes_requested == 0
+        for owner in teams {
+            let Some((org, team)) = parse_team(owner) else {
+                continue;
+            };
+
+            match self.github.teams(org).members(team).per_page(100).send().await {
+                Ok(page) => {
+                    let logins = page.items.into_iter().map(|a| a.login.to_lowercase()).collect();
+                    members.insert(owner.clone(), logins);
+                }
+                Err(e) => warn!("⚠️  Could not resolve team {}: {}", owner, e),
+            }
+        }
+
+        members
     }
 
     /// Calculate risk score from changed files
@@ -348,8 +567,20 @@ impl GuardianCore {
 
     /// Check if PR includes tests
     pub fn has_tests(&self, files: &[String]) -> bool {
-        files.iter().any(|f| {
-            f.contains("test") || f.contains("spec") || f.starts_with("tests/")
+        let files: Vec<ChangedFile> = files.iter().map(ChangedFile::new).collect();
+        self.has_tests_in(&files)
+    }
+
+    /// Check if PR includes tests, inspecting diffs where available
+    pub fn has_tests_in(&self, files: &[ChangedFile]) -> bool {
+        files.iter().any(|file| {
+            self.test_detectors.iter().any(|detector| {
+                let matched = detector.is_test(file);
+                if matched {
+                    debug!("🧪 {} detected test in {}", detector.name(), file.path);
+                }
+                matched
+            })
         })
     }
 
@@ -359,19 +590,23 @@ impl GuardianCore {
             return false;
         }
 
-        // Extract first directory from each file
-        let scopes: Vec<&str> = files
+        let scopes: HashSet<String> = files
             .iter()
-            .filter_map(|f| f.split('/').next())
+            .map(|f| self.scope_resolver.scope_of(f))
             .collect();
 
-        // Check if all files share the same top-level directory
-        scopes.windows(2).all(|w| w[0] == w[1])
+        debug!("🎯 Scopes touched: {:?}", scopes);
+        scopes.len() == 1
     }
 
     /// Execute the decision (merge or escalate)
     async fn execute_decision(&self, pr_number: u64, decision: &Decision) -> Result<()> {
         match decision {
+            Decision::AutoMerge { confidence } if self.merge_queue => {
+                MergeQueue::new(self.github.clone(), self.owner.clone(), self.repo.clone())
+                    .enqueue(pr_number, *confidence)
+                    .await?;
+            }
             Decision::AutoMerge { confidence } => {
                 info!("✅ Auto-merging PR #{} (confidence: {})", pr_number, confidence);
 
@@ -490,4 +725,178 @@ mod tests {
         let files = vec!["src/main.rs".to_string(), "tests/test.rs".to_string()];
         assert!(!guardian.is_single_scope(&files));
     }
+
+    fn review(reviewer: &str, state: ReviewState, commit: &str) -> ReviewInfo {
+        ReviewInfo {
+            reviewer: reviewer.to_string(),
+            state,
+            commit_id: Some(commit.to_string()),
+            is_bot: reviewer.ends_with("[bot]"),
+        }
+    }
+
+    fn pr_with_reviews(reviews: Vec<ReviewInfo>, files: &[&str]) -> PrData {
+        PrData {
+            number: 1,
+            author: "author".to_string(),
+            head_sha: "head".to_string(),
+            labels: vec![],
+            reviews,
+            additions: 10,
+            deletions: 5,
+            changed_files: files.len() as u32,
+            files: files.iter().map(|f| f.to_string()).collect(),
+            patches: HashMap::new(),
+            checks_passed: true,
+            head_ref: "feature".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_check_reviews_ignores_stale_bot_and_self() {
+        let github = Octocrab::builder().build().unwrap();
+        let guardian = GuardianCore::new(github, "owner".to_string(), "repo".to_string());
+        let teams = HashMap::new();
+
+        let pr = pr_with_reviews(
+            vec![
+                review("alice", ReviewState::Approved, "old"),
+                review("renovate[bot]", ReviewState::Approved, "head"),
+                review("author", ReviewState::Approved, "head"),
+            ],
+            &["src/lib.rs"],
+        );
+        assert!(guardian.check_reviews(&pr, &teams).is_some());
+
+        let pr = pr_with_reviews(vec![review("alice", ReviewState::Approved, "head")], &["src/lib.rs"]);
+        assert!(guardian.check_reviews(&pr, &teams).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_check_reviews_latest_verdict_wins() {
+        let github = Octocrab::builder().build().unwrap();
+        let guardian = GuardianCore::new(github, "owner".to_string(), "repo".to_string());
+        let teams = HashMap::new();
+
+        // Re-approval after requesting changes
+        let pr = pr_with_reviews(
+            vec![
+                review("alice", ReviewState::ChangesRequested, "old"),
+                review("alice", ReviewState::Approved, "head"),
+                review("alice", ReviewState::Commented, "head"),
+            ],
+            &["src/lib.rs"],
+        );
+        assert!(guardian.check_reviews(&pr, &teams).is_none());
+
+        let pr = pr_with_reviews(
+            vec![
+                review("alice", ReviewState::Approved, "head"),
+                review("bob", ReviewState::ChangesRequested, "head"),
+            ],
+            &["src/lib.rs"],
+        );
+        let reason = guardian.check_reviews(&pr, &teams).unwrap();
+        assert!(reason.contains("@bob"));
+    }
+
+    #[tokio::test]
+    async fn test_check_reviews_min_approvals() {
+        let github = Octocrab::builder().build().unwrap();
+        let guardian = GuardianCore::new(github, "owner".to_string(), "repo".to_string())
+            .with_min_approvals(2);
+        let teams = HashMap::new();
+
+        let pr = pr_with_reviews(vec![review("alice", ReviewState::Approved, "head")], &["src/lib.rs"]);
+        assert!(guardian.check_reviews(&pr, &teams).is_some());
+
+        let pr = pr_with_reviews(
+            vec![
+                review("alice", ReviewState::Approved, "head"),
+                review("bob", ReviewState::Approved, "head"),
+            ],
+            &["src/lib.rs"],
+        );
+        assert!(guardian.check_reviews(&pr, &teams).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_check_reviews_code_owners() {
+        let github = Octocrab::builder().build().unwrap();
+        let mut guardian = GuardianCore::new(github, "owner".to_string(), "repo".to_string());
+        guardian.codeowners = Some(CodeOwners::parse("/docs/ @alice\n/src/ @org/core").unwrap());
+
+        let mut teams = HashMap::new();
+        teams.insert("@org/core".to_string(), HashSet::from(["bob".to_string()]));
+
+        let files = ["docs/guide.md", "src/lib.rs", "README.md"];
+
+        let pr = pr_with_reviews(vec![review("Alice", ReviewState::Approved, "head")], &files);
+        let reason = guardian.check_reviews(&pr, &teams).unwrap();
+        assert!(reason.contains("src/lib.rs"));
+        assert!(!reason.contains("docs/guide.md"));
+
+        let pr = pr_with_reviews(
+            vec![
+                review("alice", ReviewState::Approved, "head"),
+                review("bob", ReviewState::Approved, "head"),
+            ],
+            &files,
+        );
+        assert!(guardian.check_reviews(&pr, &teams).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_has_tests_ignores_lookalikes() {
+        let github = Octocrab::builder().build().unwrap();
+        let guardian = GuardianCore::new(github, "owner".to_string(), "repo".to_string());
+
+        let files = vec!["src/contest.rs".to_string(), "docs/specification.md".to_string()];
+        assert!(!guardian.has_tests(&files));
+
+        let mut pr = pr_with_reviews(vec![], &["src/parser.rs"]);
+        pr.patches.insert(
+            "src/parser.rs".to_string(),
+            "@@ -1,1 +1,4 @@\n+#[cfg(test)]\n+mod tests {\n+}".to_string(),
+        );
+        assert!(guardian.has_tests_in(&pr.changed_files()));
+    }
+
+    #[tokio::test]
+    async fn test_custom_test_detector() {
+        struct SnapshotDetector;
+
+        impl TestDetector for SnapshotDetector {
+            fn name(&self) -> &'static str {
+                "snapshot"
+            }
+
+            fn is_test(&self, file: &ChangedFile) -> bool {
+                file.path.ends_with(".snap")
+            }
+        }
+
+        let github = Octocrab::builder().build().unwrap();
+        let files = vec!["src/render.rs".to_string(), "snapshots/render.snap".to_string()];
+
+        let guardian = GuardianCore::new(github.clone(), "owner".to_string(), "repo".to_string());
+        assert!(!guardian.has_tests(&files));
+
+        let guardian = GuardianCore::new(github, "owner".to_string(), "repo".to_string())
+            .with_test_detector(Box::new(SnapshotDetector));
+        assert!(guardian.has_tests(&files));
+    }
+
+    #[tokio::test]
+    async fn test_single_scope_with_workspace_members() {
+        let github = Octocrab::builder().build().unwrap();
+        let guardian = GuardianCore::new(github, "owner".to_string(), "repo".to_string())
+            .with_scope_resolver(ScopeResolver::from_package_roots(["tools/a", "tools/b"]));
+
+        let files = vec!["tools/a/src/lib.rs".to_string(), "tools/a/tests/it.rs".to_string()];
+        assert!(guardian.is_single_scope(&files));
+
+        let files = vec!["tools/a/src/lib.rs".to_string(), "tools/b/src/lib.rs".to_string()];
+        assert!(!guardian.is_single_scope(&files));
+    }
 }
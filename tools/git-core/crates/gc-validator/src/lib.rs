@@ -3,9 +3,21 @@
 //! Public API for testing and external usage
 
 pub mod guardian_core;
+pub mod codeowners;
+pub mod detectors;
+pub mod dependency_graph;
 pub mod dispatcher_core;
+pub mod dispatcher_state;
+pub mod merge_queue;
 pub mod github;
+pub mod log_analysis;
+pub mod timeline;
+pub mod workflow_security;
+pub mod flakiness;
 pub mod analyzer;
 pub mod validator;
+pub mod review_bots;
 pub mod reporter;
+pub mod run_store;
+pub mod trends;
 pub mod parallel;
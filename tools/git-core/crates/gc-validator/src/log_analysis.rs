@@ -0,0 +1,608 @@
+//! # Log Analysis - Failing Lines from GitHub Actions Job Logs
+//!
+//! Turns a raw job log into the lines that actually explain a failure.
+//!
+//! ## Pipeline
+//!
+//! ```text
+//! raw log ──► strip timestamps / ANSI ──► split by `##[group]Run ...`
+//!         ──► attribute segments to API steps (by timestamp)
+//!         ──► extract error lines (##[error], rustc/cargo, npm ERR!, pytest)
+//!         ──► classify against failure signatures (fix suggestion)
+//! ```
+//!
+//! Only segments belonging to a failed step are mined when the step can be
+//! identified, so `continue-on-error` noise does not leak into reports.
+//! Custom signatures are registered with [`LogAnalyzer::with_signature`].
+
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+use crate::github::{Job, Step};
+
+/// Findings kept per step (the first errors are the useful ones)
+const MAX_FINDINGS_PER_STEP: usize = 5;
+
+/// One log line without its timestamp prefix
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogLine {
+    pub number: usize,
+    pub timestamp: Option<DateTime<Utc>>,
+    pub text: String,
+}
+
+/// Log lines belonging to one step
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepLog {
+    /// `Run ...` command, `Set up job` or `Post job cleanup`
+    pub name: String,
+    pub lines: Vec<LogLine>,
+}
+
+impl StepLog {
+    fn started_at(&self) -> Option<DateTime<Utc>> {
+        self.lines.iter().find_map(|l| l.timestamp)
+    }
+}
+
+/// A known kind of failure with a fix suggestion
+#[derive(Debug, Clone)]
+pub struct FailureSignature {
+    pub id: String,
+    pub category: String,
+    pub suggestion: String,
+    regex: Regex,
+}
+
+impl FailureSignature {
+    /// Create a signature; `pattern` is matched against single error lines
+    pub fn new(id: &str, category: &str, pattern: &str, suggestion: &str) -> anyhow::Result<Self> {
+        Ok(Self {
+            id: id.to_string(),
+            category: category.to_string(),
+            suggestion: suggestion.to_string(),
+            regex: Regex::new(pattern)?,
+        })
+    }
+
+    pub fn matches(&self, line: &str) -> bool {
+        self.regex.is_match(line)
+    }
+}
+
+/// Built-in signature library, most specific first
+pub fn default_signatures() -> Vec<FailureSignature> {
+    const LIBRARY: &[(&str, &str, &str, &str)] = &[
+        ("disk-full", "infrastructure", r"(?i)no space left on device",
+         "Free disk space on the runner (remove unused toolchains, clean target/ or node_modules) or use a larger runner"),
+        ("out-of-memory", "infrastructure", r"(?i)out of memory|exit code 137|\bKilled\b|JavaScript heap out of memory",
+         "The process ran out of memory: reduce parallelism (e.g. CARGO_BUILD_JOBS, --maxWorkers) or use a larger runner"),
+        ("job-timeout", "infrastructure", r"(?i)exceeded the maximum execution time|operation was canceled|timed out after",
+         "The job hit its time limit: cache dependencies, split the job, or raise timeout-minutes"),
+        ("network", "transient", r"(?i)ETIMEDOUT|ECONNRESET|EAI_AGAIN|could not resolve host|connection (reset|refused)|503 Service Unavailable|502 Bad Gateway",
+         "Transient network failure: re-run the job and consider retries or dependency caching"),
+        ("rate-limit", "transient", r"(?i)api rate limit exceeded|secondary rate limit|429 Too Many Requests",
+         "GitHub API rate limit hit: authenticate requests with GITHUB_TOKEN and reduce call volume"),
+        ("token-permissions", "permissions", r"(?i)resource not accessible by integration|permission denied|403 Forbidden",
+         "Grant the workflow token the required `permissions:` or use a token with access"),
+        ("missing-input", "configuration", r"(?i)input required and not supplied|secret .* (is )?not (set|found)",
+         "Provide the required action input or repository secret"),
+        ("rustfmt", "lint", r"^Diff in .* at line \d+",
+         "Run `cargo fmt --all` and commit the result"),
+        ("clippy", "lint", r"clippy::|-D warnings|#\[deny\(",
+         "Fix the clippy lint shown (run `cargo clippy --all-targets -- -D warnings` locally)"),
+        ("rust-compile", "build", r"^error(\[E\d{4}\])?: ",
+         "Fix the compiler error at the reported location (run `cargo check` locally)"),
+        ("rust-test", "test", r"panicked at|^test .+ \.\.\. FAILED|test result: FAILED",
+         "Run the failing test locally with `cargo test <name> -- --nocapture`"),
+        ("npm-resolve", "dependencies", r"(?i)ERESOLVE|peer dep|Cannot find module|Module not found",
+         "Fix the dependency tree: align versions in package.json and refresh the lockfile with `npm install`"),
+        ("npm-lockfile", "dependencies", r"(?i)`?npm ci`? can only install|lockfile.*(out of date|not in sync)|package-lock\.json.*not in sync",
+         "package-lock.json is out of sync: run `npm install` and commit the lockfile"),
+        ("npm", "build", r"^npm (ERR!|error)",
+         "Check the npm script output above; reproduce with `npm ci && npm run <script>`"),
+        ("python-import", "dependencies", r"ModuleNotFoundError|ImportError",
+         "Add the missing package to requirements/pyproject and reinstall"),
+        ("pytest", "test", r"^(FAILED|ERROR) \S+::|^E\s+\w*(Error|Exception)|AssertionError",
+         "Run the failing test locally with `pytest <path>::<test> -x`"),
+        ("exit-code", "generic", r"Process completed with exit code \d+",
+         "See the preceding error lines in this step"),
+    ];
+
+    LIBRARY
+        .iter()
+        .map(|(id, category, pattern, suggestion)| {
+            FailureSignature::new(id, category, pattern, suggestion).expect("valid built-in signature")
+        })
+        .collect()
+}
+
+/// An error line mined from a job log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LogFinding {
+    pub job: String,
+    pub step: Option<String>,
+    /// The failing line, cleaned of timestamps and annotation markers
+    pub line: String,
+    pub line_number: usize,
+    /// Source location reported next to the error (`src/lib.rs:10:5`)
+    pub location: Option<String>,
+    pub signature: Option<String>,
+    pub category: Option<String>,
+    pub suggestion: Option<String>,
+}
+
+/// Job log analyzer with a pluggable signature library
+#[derive(Debug, Clone)]
+pub struct LogAnalyzer {
+    signatures: Vec<FailureSignature>,
+}
+
+impl Default for LogAnalyzer {
+    fn default() -> Self {
+        Self {
+            signatures: default_signatures(),
+        }
+    }
+}
+
+impl LogAnalyzer {
+    /// Analyzer with the built-in signatures
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a signature, checked before the built-in ones
+    pub fn with_signature(mut self, signature: FailureSignature) -> Self {
+        self.signatures.insert(0, signature);
+        self
+    }
+
+    /// First signature matching a line
+    pub fn classify(&self, line: &str) -> Option<&FailureSignature> {
+        self.signatures.iter().find(|s| s.matches(line))
+    }
+
+    /// Mine the failing lines of a job log
+    pub fn analyze(&self, job: &Job, raw_log: &str) -> Vec<LogFinding> {
+        let segments = split_steps(raw_log);
+        let api_steps = job.steps.as_deref().unwrap_or_default();
+        let any_failed_step = api_steps.iter().any(|s| s.conclusion.as_deref() == Some("failure"));
+
+        let mut findings = Vec::new();
+
+        for segment in &segments {
+            let step = attribute_step(segment, api_steps);
+
+            // Only mine steps that failed, unless the log cannot be mapped
+            if let Some(step) = step {
+                if any_failed_step && step.conclusion.as_deref() != Some("failure") {
+                    continue;
+                }
+            }
+
+            let step_name = step.map(|s| s.name.clone()).or_else(|| Some(segment.name.clone()));
+            let mut step_findings: Vec<LogFinding> = extract_errors(&segment.lines)
+                .into_iter()
+                .map(|(line, location)| {
+                    let signature = self.classify(&line.text);
+                    LogFinding {
+                        job: job.name.clone(),
+                        step: step_name.clone(),
+                        line: line.text.clone(),
+                        line_number: line.number,
+                        location,
+                        signature: signature.map(|s| s.id.clone()),
+                        category: signature.map(|s| s.category.clone()),
+                        suggestion: signature.map(|s| s.suggestion.clone()),
+                    }
+                })
+                .collect();
+
+            // The exit-code line only matters when nothing better was found
+            if step_findings.iter().any(|f| !is_exit_code_line(&f.line)) {
+                step_findings.retain(|f| !is_exit_code_line(&f.line));
+            }
+            step_findings.truncate(MAX_FINDINGS_PER_STEP);
+            findings.extend(step_findings);
+        }
+
+        findings
+    }
+}
+
+/// Parse a raw log into lines, removing timestamps and ANSI colors
+pub fn parse_lines(raw_log: &str) -> Vec<LogLine> {
+    static TIMESTAMP_RE: OnceLock<Regex> = OnceLock::new();
+    static ANSI_RE: OnceLock<Regex> = OnceLock::new();
+
+    let timestamp_re = TIMESTAMP_RE.get_or_init(|| {
+        Regex::new(r"^\u{feff}?(\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(?:\.\d+)?Z) ?").expect("valid timestamp regex")
+    });
+    let ansi_re = ANSI_RE.get_or_init(|| Regex::new(r"\x1b\[[0-9;]*[A-Za-z]").expect("valid ansi regex"));
+
+    raw_log
+        .lines()
+        .enumerate()
+        .map(|(i, raw)| {
+            let (timestamp, rest) = match timestamp_re.captures(raw) {
+                Some(caps) => (
+                    caps[1].parse::<DateTime<Utc>>().ok(),
+                    &raw[caps.get(0).map_or(0, |m| m.end())..],
+                ),
+                None => (None, raw),
+            };
+
+            LogLine {
+                number: i + 1,
+                timestamp,
+                text: ansi_re.replace_all(rest, "").trim_end().to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Split a job log into step segments at `##[group]Run ...` markers
+pub fn split_steps(raw_log: &str) -> Vec<StepLog> {
+    let mut segments = vec![StepLog {
+        name: "Set up job".to_string(),
+        lines: Vec::new(),
+    }];
+
+    for line in parse_lines(raw_log) {
+        let starts_step = if let Some(command) = line.text.strip_prefix("##[group]Run ") {
+            Some(format!("Run {}", command.trim()))
+        } else if line.text.starts_with("Post job cleanup.") {
+            Some("Post job cleanup".to_string())
+        } else {
+            None
+        };
+
+        if let Some(name) = starts_step {
+            segments.push(StepLog { name, lines: Vec::new() });
+        }
+        segments.last_mut().expect("at least one segment").lines.push(line);
+    }
+
+    segments.retain(|s| !s.lines.is_empty());
+    segments
+}
+
+/// Cache restores seen in job logs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct CacheStats {
+    pub hits: usize,
+    pub misses: usize,
+}
+
+impl std::ops::AddAssign for CacheStats {
+    fn add_assign(&mut self, other: Self) {
+        self.hits += other.hits;
+        self.misses += other.misses;
+    }
+}
+
+/// Whether a job has a step that restores a cache
+///
+/// `cache_steps` are the cache step names from the run's workflow file (see
+/// [`cache_step_names`]). Without it, only unnamed cache steps are found,
+/// which GitHub reports as `Run actions/cache@v4`.
+pub fn may_use_cache(job: &Job, cache_steps: &HashSet<String>) -> bool {
+    job.steps
+        .iter()
+        .flatten()
+        .any(|step| cache_steps.contains(&step.name) || step.name.strip_prefix("Run ").is_some_and(is_cache_action))
+}
+
+/// Display names of the steps in a workflow file that restore a cache
+///
+/// These are `actions/cache` (and other `*cache` actions) and `setup-*`
+/// actions with a `cache:` input. Unnamed steps are named `Run <uses>`.
+pub fn cache_step_names(workflow: &str) -> HashSet<String> {
+    let Ok(doc) = serde_yaml::from_str::<serde_yaml::Value>(workflow) else {
+        return HashSet::new();
+    };
+    let Some(jobs) = doc.get("jobs").and_then(|j| j.as_mapping()) else {
+        return HashSet::new();
+    };
+
+    jobs.values()
+        .filter_map(|job| job.get("steps")?.as_sequence())
+        .flatten()
+        .filter_map(|step| {
+            let uses = step.get("uses")?.as_str()?;
+            let cache_input = step
+                .get("with")
+                .and_then(|with| with.get("cache"))
+                .is_some_and(|cache| !matches!(cache, serde_yaml::Value::Bool(false) | serde_yaml::Value::Null));
+            let setup = uses.split('@').next()?.rsplit('/').next()?.starts_with("setup-");
+
+            if !(is_cache_action(uses) || (setup && cache_input)) {
+                return None;
+            }
+            Some(match step.get("name").and_then(|n| n.as_str()) {
+                Some(name) => name.to_string(),
+                None => format!("Run {}", uses),
+            })
+        })
+        .collect()
+}
+
+/// `actions/cache`, `actions/cache/restore`, `Swatinem/rust-cache`, ...
+fn is_cache_action(uses: &str) -> bool {
+    let action = uses.split('@').next().unwrap_or(uses).to_lowercase();
+    action.split('/').skip(1).any(|part| part == "cache" || part.ends_with("-cache"))
+}
+
+/// Count cache restores (hits) and lookups that found nothing (misses)
+///
+/// Recognizes `actions/cache`, the `setup-*` actions' built-in caching and
+/// `Swatinem/rust-cache`.
+pub fn cache_stats(raw_log: &str) -> CacheStats {
+    static HIT_RE: OnceLock<Regex> = OnceLock::new();
+    static MISS_RE: OnceLock<Regex> = OnceLock::new();
+
+    let hit_re = HIT_RE.get_or_init(|| {
+        Regex::new(r"(?i)^(?:cache restored from key|restored from cache key)").expect("valid cache hit regex")
+    });
+    let miss_re = MISS_RE.get_or_init(|| {
+        Regex::new(r"(?i)^(?:cache not found for input keys|no cache found|\S+ cache is not found)")
+            .expect("valid cache miss regex")
+    });
+
+    parse_lines(raw_log).iter().fold(CacheStats::default(), |mut stats, line| {
+        if hit_re.is_match(&line.text) {
+            stats.hits += 1;
+        } else if miss_re.is_match(&line.text) {
+            stats.misses += 1;
+        }
+        stats
+    })
+}
+
+/// API step whose time window contains the segment start (name as fallback)
+fn attribute_step<'a>(segment: &StepLog, steps: &'a [Step]) -> Option<&'a Step> {
+    let parse = |t: &Option<String>| t.as_deref().and_then(|t| t.parse::<DateTime<Utc>>().ok());
+
+    if let Some(start) = segment.started_at() {
+        // API timestamps have second precision
+        let start = start.timestamp();
+        let by_time = steps.iter().find(|step| {
+            match (parse(&step.started_at), parse(&step.completed_at)) {
+                (Some(s), Some(e)) => s.timestamp() <= start && start <= e.timestamp(),
+                _ => false,
+            }
+        });
+        if by_time.is_some() {
+            return by_time;
+        }
+    }
+
+    steps.iter().find(|step| step.name == segment.name)
+}
+
+/// Error lines of a segment with the source location that follows them
+fn extract_errors(lines: &[LogLine]) -> Vec<(&LogLine, Option<String>)> {
+    static ERROR_RE: OnceLock<Regex> = OnceLock::new();
+    static LOCATION_RE: OnceLock<Regex> = OnceLock::new();
+
+    let error_re = ERROR_RE.get_or_init(|| {
+        Regex::new(concat!(
+            r"^##\[error\]",
+            r"|^error(\[E\d{4}\])?: ",
+            r"|^thread '.*' panicked at",
+            r"|^test .+ \.\.\. FAILED$",
+            r"|^npm (ERR!|error) ",
+            r"|^(FAILED|ERROR) \S+::",
+            r"|^E\s+\w*(Error|Exception)",
+            r"|^Error: ",
+        ))
+        .expect("valid error regex")
+    });
+    let location_re = LOCATION_RE.get_or_init(|| {
+        Regex::new(r"^\s*--> (\S+:\d+:\d+)").expect("valid location regex")
+    });
+
+    let mut seen = HashSet::new();
+    let mut errors = Vec::new();
+
+    for (i, line) in lines.iter().enumerate() {
+        if !error_re.is_match(&line.text) {
+            continue;
+        }
+
+        // Annotations repeat the tool's own output: report each message once
+        let message = line.text.trim_start_matches("##[error]").trim();
+        if message.is_empty() || !seen.insert(message.to_string()) {
+            continue;
+        }
+        // Summary lines that only restate earlier errors
+        if message.starts_with("error: could not compile") && !errors.is_empty() {
+            continue;
+        }
+
+        let location = lines[i + 1..]
+            .iter()
+            .take(3)
+            .find_map(|l| location_re.captures(&l.text).map(|c| c[1].to_string()));
+
+        errors.push((line, location));
+    }
+
+    errors
+}
+
+fn is_exit_code_line(line: &str) -> bool {
+    line.contains("Process completed with exit code")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOG: &str = "\
+2026-01-05T10:00:00.1000000Z ##[group]Operating System
+2026-01-05T10:00:00.2000000Z Ubuntu
+2026-01-05T10:00:00.3000000Z ##[endgroup]
+2026-01-05T10:00:05.0000000Z ##[group]Run cargo fmt --check
+2026-01-05T10:00:05.1000000Z ##[endgroup]
+2026-01-05T10:00:06.0000000Z error: this line is from a step that passed
+2026-01-05T10:00:10.0000000Z ##[group]Run cargo build
+2026-01-05T10:00:10.1000000Z \x1b[1m\x1b[91merror[E0308]\x1b[0m: mismatched types
+2026-01-05T10:00:10.2000000Z   --> src/lib.rs:10:5
+2026-01-05T10:00:10.3000000Z error: could not compile `app` due to 1 previous error
+2026-01-05T10:00:10.4000000Z ##[error]error[E0308]: mismatched types
+2026-01-05T10:00:10.5000000Z ##[error]Process completed with exit code 101.
+2026-01-05T10:00:20.0000000Z Post job cleanup.
+";
+
+    fn step(number: u32, name: &str, conclusion: &str, start: &str, end: &str) -> Step {
+        Step {
+            name: name.to_string(),
+            status: "completed".to_string(),
+            conclusion: Some(conclusion.to_string()),
+            number,
+            started_at: Some(format!("2026-01-05T10:00:{}Z", start)),
+            completed_at: Some(format!("2026-01-05T10:00:{}Z", end)),
+        }
+    }
+
+    fn job() -> Job {
+        Job {
+            id: 1,
+            name: "build".to_string(),
+            status: "completed".to_string(),
+            conclusion: Some("failure".to_string()),
+            created_at: None,
+            started_at: None,
+            completed_at: None,
+            steps: Some(vec![
+                step(1, "Set up job", "success", "00", "04"),
+                step(2, "Format", "success", "05", "09"),
+                step(3, "Build", "failure", "10", "19"),
+                step(4, "Complete job", "success", "20", "21"),
+            ]),
+        }
+    }
+
+    #[test]
+    fn test_split_steps() {
+        let segments = split_steps(LOG);
+        let names: Vec<&str> = segments.iter().map(|s| s.name.as_str()).collect();
+
+        assert_eq!(
+            names,
+            ["Set up job", "Run cargo fmt --check", "Run cargo build", "Post job cleanup"]
+        );
+        assert_eq!(segments[0].lines[1].text, "Ubuntu");
+        assert!(segments[0].lines[0].timestamp.is_some());
+    }
+
+    #[test]
+    fn test_analyze_rust_failure() {
+        let findings = LogAnalyzer::new().analyze(&job(), LOG);
+
+        // Only the failed step, each message once, exit-code noise dropped
+        assert_eq!(findings.len(), 1, "{:#?}", findings);
+        let finding = &findings[0];
+        assert_eq!(finding.step.as_deref(), Some("Build"));
+        assert_eq!(finding.line, "error[E0308]: mismatched types");
+        assert_eq!(finding.location.as_deref(), Some("src/lib.rs:10:5"));
+        assert_eq!(finding.signature.as_deref(), Some("rust-compile"));
+        assert!(finding.suggestion.as_deref().unwrap().contains("cargo check"));
+    }
+
+    #[test]
+    fn test_extracts_npm_and_pytest_errors() {
+        let log = "\
+##[group]Run npm ci
+npm ERR! code ERESOLVE
+npm ERR! ERESOLVE unable to resolve dependency tree
+##[group]Run pytest
+FAILED tests/test_api.py::test_login - AssertionError: 401 != 200
+E       AssertionError: 401 != 200
+##[error]Process completed with exit code 1.
+";
+        let mut job = job();
+        job.steps = None;
+
+        let findings = LogAnalyzer::new().analyze(&job, log);
+        let signatures: Vec<_> = findings.iter().map(|f| f.signature.as_deref().unwrap()).collect();
+
+        assert_eq!(signatures, ["npm-resolve", "npm-resolve", "pytest", "pytest"]);
+        assert_eq!(findings[2].step.as_deref(), Some("Run pytest"));
+    }
+
+    #[test]
+    fn test_exit_code_kept_when_nothing_else() {
+        let log = "##[group]Run ./deploy.sh\nDeploying...\n##[error]Process completed with exit code 2.\n";
+        let mut job = job();
+        job.steps = None;
+
+        let findings = LogAnalyzer::new().analyze(&job, log);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].signature.as_deref(), Some("exit-code"));
+    }
+
+    #[test]
+    fn test_cache_stats() {
+        let log = "\
+2026-01-05T10:00:01.0000000Z ##[group]Run actions/cache@v4
+2026-01-05T10:00:02.0000000Z Cache restored from key: Linux-cargo-3f2a
+2026-01-05T10:00:03.0000000Z ##[group]Run actions/setup-node@v4
+2026-01-05T10:00:04.0000000Z npm cache is not found
+2026-01-05T10:00:05.0000000Z ##[group]Run actions/cache@v4
+2026-01-05T10:00:06.0000000Z Cache not found for input keys: Linux-pip-9c1d
+Post job cleanup.
+Cache hit occurred on the primary key Linux-cargo-3f2a, not saving cache.
+";
+
+        assert_eq!(cache_stats(log), CacheStats { hits: 1, misses: 2 });
+    }
+
+    #[test]
+    fn test_cache_steps() {
+        let workflow = r#"
+jobs:
+  build:
+    steps:
+      - uses: actions/checkout@v4
+      - uses: actions/setup-node@v4
+        with:
+          node-version: 20
+      - name: Set up Python
+        uses: actions/setup-python@v5
+        with:
+          cache: pip
+      - name: Cargo cache
+        uses: Swatinem/rust-cache@v2
+      - uses: actions/cache/restore@v4
+"#;
+        let names = cache_step_names(workflow);
+        let mut sorted: Vec<&str> = names.iter().map(String::as_str).collect();
+        sorted.sort();
+        assert_eq!(sorted, ["Cargo cache", "Run actions/cache/restore@v4", "Set up Python"]);
+
+        // "Set up job" and other setup steps no longer count
+        let mut job = job();
+        assert!(!may_use_cache(&job, &names));
+        job.steps.as_mut().unwrap()[1].name = "Set up Python".to_string();
+        assert!(may_use_cache(&job, &names));
+        job.steps.as_mut().unwrap()[1].name = "Run actions/cache@v4".to_string();
+        assert!(may_use_cache(&job, &HashSet::new()));
+    }
+
+    #[test]
+    fn test_custom_signature_wins() {
+        let analyzer = LogAnalyzer::new().with_signature(
+            FailureSignature::new("flaky-db", "transient", r"database is locked", "Retry with a fresh DB").unwrap(),
+        );
+
+        assert_eq!(analyzer.classify("Error: database is locked").unwrap().id, "flaky-db");
+        assert_eq!(analyzer.classify("No space left on device").unwrap().id, "disk-full");
+        assert!(analyzer.classify("all good").is_none());
+    }
+}
@@ -4,18 +4,30 @@
 //! Executes GitHub Actions analysis, validation, and continuous improvement tasks
 //! with maximum parallelism using Tokio.
 
-use clap::{Parser, Subcommand};
+use clap::{ArgAction, Parser, Subcommand};
 use anyhow::Result;
 use tracing::{info, Level};
 use tracing_subscriber::FmtSubscriber;
 
 mod github;
+mod log_analysis;
+mod timeline;
+mod workflow_security;
+mod flakiness;
 mod analyzer;
 mod validator;
+mod review_bots;
 mod reporter;
+mod run_store;
+mod trends;
 mod parallel;
 mod guardian_core;
+mod codeowners;
+mod detectors;
+mod dependency_graph;
 mod dispatcher_core;
+mod dispatcher_state;
+mod merge_queue;
 
 #[derive(Parser, Debug)]
 #[command(
@@ -48,6 +60,14 @@ struct Cli {
     /// Maximum parallel tasks
     #[arg(long, default_value = "10")]
     max_parallel: usize,
+
+    /// Local store of analyzed runs (SQLite)
+    #[arg(long, default_value = run_store::DEFAULT_STORE_PATH)]
+    store: String,
+
+    /// Days of history fetched into an empty run store
+    #[arg(long, default_value = "30")]
+    backfill_days: i64,
 }
 
 #[derive(Subcommand, Debug)]
@@ -62,9 +82,21 @@ enum Commands {
         #[arg(long)]
         last_hours: Option<u64>,
 
-        /// Create PR with validation results
-        #[arg(long, default_value = "true")]
-        create_pr: bool,
+        /// Publish results: pr (report committed under docs/agent-docs/reports), issue (one per workflow) or none
+        #[arg(long, default_value = "pr")]
+        publish: String,
+
+        /// Base branch for validation PRs (default: the repository's default branch)
+        #[arg(long)]
+        base: Option<String>,
+
+        /// Request reviews from the configured reviewer bots on validation PRs
+        #[arg(long, default_value_t = true, action = ArgAction::Set)]
+        ai_review: bool,
+
+        /// Path to the reviewer bot registry (built-in reviewers if missing)
+        #[arg(long, default_value = review_bots::DEFAULT_REVIEWERS_PATH)]
+        reviewers: String,
     },
 
     /// Analyze all workflows in parallel
@@ -84,9 +116,21 @@ enum Commands {
         #[arg(short, long)]
         run_id: String,
 
-        /// Request AI reviews (CodeRabbit, Gemini, Copilot)
-        #[arg(long, default_value = "true")]
+        /// Request reviews from the configured reviewer bots
+        #[arg(long, default_value_t = true, action = ArgAction::Set)]
         ai_review: bool,
+
+        /// Path to the reviewer bot registry (built-in reviewers if missing)
+        #[arg(long, default_value = review_bots::DEFAULT_REVIEWERS_PATH)]
+        reviewers: String,
+
+        /// Publish results: pr, issue or none
+        #[arg(long, default_value = "pr")]
+        publish: String,
+
+        /// Base branch for validation PRs (default: the repository's default branch)
+        #[arg(long)]
+        base: Option<String>,
     },
 
     /// Generate comprehensive report
@@ -98,6 +142,10 @@ enum Commands {
         /// Time range in hours
         #[arg(long, default_value = "168")]
         hours: u64,
+
+        /// Compare two selections: CURRENT..BASELINE (e.g. last-7d..prev-7d, v1.3.0..v1.2.0, SHA..SHA)
+        #[arg(long)]
+        compare: Option<String>,
     },
 
     /// Health check for all workflows
@@ -107,6 +155,43 @@ enum Commands {
         quick: bool,
     },
 
+    /// Fetch new and updated workflow runs into the local store
+    Sync,
+
+    /// Static security audit of local workflow files (no API calls)
+    Audit {
+        /// Workflows directory
+        #[arg(long, default_value = ".github/workflows")]
+        dir: String,
+
+        /// Exit with an error when the score is below this value (0-100)
+        #[arg(long)]
+        min_score: Option<f64>,
+    },
+
+    /// Detect flaky jobs from re-runs and same-commit pass/fail flips
+    Flaky {
+        /// Time window in days
+        #[arg(long, default_value = "14")]
+        days: i64,
+
+        /// Number of flaky jobs/steps to list
+        #[arg(long, default_value = "10")]
+        top: usize,
+
+        /// Minimum executions before a job is scored
+        #[arg(long, default_value = "3")]
+        min_runs: usize,
+
+        /// Open or update a tracking issue per flaky job
+        #[arg(long, default_value = "false")]
+        track_issues: bool,
+
+        /// Flakiness score (0.0-1.0) at or above which a job gets a tracking issue
+        #[arg(long, default_value = "0.1")]
+        threshold: f64,
+    },
+
     /// Guardian Agent - Auto-merge PR evaluation
     Guardian {
         /// Pull Request number to evaluate
@@ -128,6 +213,87 @@ enum Commands {
         /// CI mode (exit with error code on escalate/block)
         #[arg(long, default_value = "false")]
         ci_mode: bool,
+
+        /// Queue approved PRs for rebase-and-recheck instead of merging
+        #[arg(long, default_value = "false")]
+        merge_queue: bool,
+
+        /// Path to CODEOWNERS (owners must approve the paths they own)
+        #[arg(long, default_value = ".github/CODEOWNERS")]
+        codeowners: String,
+
+        /// Minimum approvals on the head commit
+        #[arg(long, default_value = "1")]
+        min_approvals: usize,
+    },
+
+    /// Merge Queue - Update, re-check and merge queued PRs one at a time
+    MergeQueue {
+        /// Seconds between check polls
+        #[arg(long, default_value = "30")]
+        poll_interval: u64,
+
+        /// Maximum seconds to wait for fresh checks per PR
+        #[arg(long, default_value = "1800")]
+        timeout: u64,
+
+        /// Process only the PR at the head of the queue
+        #[arg(long, default_value = "false")]
+        once: bool,
+
+        /// Dry run mode (don't update, merge or dequeue)
+        #[arg(long, default_value = "false")]
+        dry_run: bool,
+    },
+
+    /// Dispatcher - Distribute open issues across registered agents
+    Dispatch {
+        /// Strategy (round-robin, random, capability-aware, <agent>-only)
+        #[arg(short, long, default_value = "round-robin")]
+        strategy: String,
+
+        /// Maximum issues to dispatch in this run
+        #[arg(long, default_value = "5")]
+        max_issues: usize,
+
+        /// Only dispatch issues carrying this label
+        #[arg(long, default_value = "ai-agent")]
+        label: String,
+
+        /// Path to the agent registry (built-in agents if missing)
+        #[arg(long, default_value = ".github/dispatcher-agents.yml")]
+        agents: String,
+
+        /// Risk score at or above which issues go to the escalation agent
+        #[arg(long, default_value = "70")]
+        risk_threshold: u8,
+
+        /// Path to the dispatcher state file (assignments, round-robin position)
+        #[arg(long, default_value = ".✨/dispatcher-state.json")]
+        state: String,
+
+        /// Dry run mode (don't label or assign)
+        #[arg(long, default_value = "false")]
+        dry_run: bool,
+    },
+
+    /// Reclaim - Return issues without linked PR activity to the dispatch pool
+    Reclaim {
+        /// Days without linked PR activity before an assignment is stale
+        #[arg(long, default_value = "7")]
+        days: i64,
+
+        /// Path to the agent registry (built-in agents if missing)
+        #[arg(long, default_value = ".github/dispatcher-agents.yml")]
+        agents: String,
+
+        /// Path to the dispatcher state file
+        #[arg(long, default_value = ".✨/dispatcher-state.json")]
+        state: String,
+
+        /// Dry run mode (don't remove labels)
+        #[arg(long, default_value = "false")]
+        dry_run: bool,
     },
 }
 
@@ -147,6 +313,24 @@ async fn main() -> Result<()> {
 
     info!("🚀 Workflow Orchestrator v{}", env!("CARGO_PKG_VERSION"));
 
+    // Offline commands (no token or repository needed)
+    if let Commands::Audit { dir, min_score } = &cli.command {
+        let audit = workflow_security::audit_dir(dir)?;
+
+        match cli.output.as_str() {
+            "json" => println!("{}", serde_json::to_string_pretty(&audit)?),
+            "markdown" => println!("{}", audit.to_markdown()),
+            _ => println!("{}", audit.to_terminal()),
+        }
+
+        if let Some(min) = min_score {
+            if audit.score() < *min {
+                anyhow::bail!("Workflow security score {:.1} is below {:.1}", audit.score(), min);
+            }
+        }
+        return Ok(());
+    }
+
     let token = cli.token.or_else(|| std::env::var("GITHUB_TOKEN").ok())
         .expect("GITHUB_TOKEN required");
 
@@ -156,20 +340,41 @@ async fn main() -> Result<()> {
     let github_client = github::GitHubClient::new(&token, &repo, cli.max_parallel);
 
     match cli.command {
-        Commands::Validate { run_id, last_hours, create_pr } => {
-            validator::run_validation(&github_client, &run_id, last_hours, create_pr, &cli.output).await?;
+        Commands::Sync => {
+            synced_store(&github_client, &cli.store, cli.backfill_days).await?;
+        }
+        Commands::Validate { run_id, last_hours, publish, base, ai_review, reviewers } => {
+            let reviewers = if ai_review { Some(review_bots::ReviewerRegistry::load_or_default(&reviewers)?) } else { None };
+            let store = synced_store(&github_client, &cli.store, cli.backfill_days).await?;
+            let publish = validator::PublishOptions { target: publish.parse()?, base: base.as_deref(), reviewers: reviewers.as_ref() };
+            validator::run_validation(&github_client, &store, &run_id, last_hours, publish, &cli.output).await?;
         }
         Commands::Analyze { types, include_success } => {
-            analyzer::run_analysis(&github_client, &types, include_success, &cli.output).await?;
+            let store = synced_store(&github_client, &cli.store, cli.backfill_days).await?;
+            analyzer::run_analysis(&store, &types, include_success, &cli.output).await?;
         }
-        Commands::PostRun { run_id, ai_review } => {
-            validator::post_run_validation(&github_client, &run_id, ai_review).await?;
+        Commands::PostRun { run_id, ai_review, reviewers, publish, base } => {
+            let reviewers = if ai_review { Some(review_bots::ReviewerRegistry::load_or_default(&reviewers)?) } else { None };
+            let publish = validator::PublishOptions { target: publish.parse()?, base: base.as_deref(), reviewers: reviewers.as_ref() };
+            validator::post_run_validation(&github_client, &run_id, publish).await?;
         }
-        Commands::Report { report_type, hours } => {
-            reporter::generate_report(&github_client, &report_type, hours, &cli.output).await?;
+        Commands::Report { report_type, hours, compare } => {
+            let store = synced_store(&github_client, &cli.store, cli.backfill_days).await?;
+            reporter::generate_report(&store, &report_type, hours, compare.as_deref(), &cli.output).await?;
         }
         Commands::Health { quick } => {
-            analyzer::health_check(&github_client, quick).await?;
+            let store = if quick {
+                None
+            } else {
+                Some(synced_store(&github_client, &cli.store, cli.backfill_days).await?)
+            };
+            analyzer::health_check(&github_client, store.as_ref(), quick).await?;
+        }
+        Commands::Audit { .. } => unreachable!("handled before authentication"),
+        Commands::Flaky { days, top, min_runs, track_issues, threshold } => {
+            let track_threshold = track_issues.then_some(threshold);
+            let store = synced_store(&github_client, &cli.store, cli.backfill_days).await?;
+            flakiness::run_flakiness_analysis(&github_client, &store, days, top, min_runs, track_threshold, &cli.output).await?;
         }
         Commands::Guardian {
             pr_number,
@@ -177,6 +382,9 @@ async fn main() -> Result<()> {
             risk_map,
             dry_run,
             ci_mode,
+            merge_queue,
+            codeowners,
+            min_approvals,
         } => {
             // Create Octocrab client for Guardian
             let octocrab = octocrab::Octocrab::builder()
@@ -190,7 +398,10 @@ async fn main() -> Result<()> {
                 owner.to_string(),
                 repo_name.to_string(),
             )
-            .with_threshold(threshold);
+            .with_threshold(threshold)
+            .with_merge_queue(merge_queue)
+            .with_min_approvals(min_approvals)
+            .with_scope_resolver(detectors::ScopeResolver::discover("."));
 
             // Load risk map if exists
             if std::path::Path::new(&risk_map).exists() {
@@ -199,6 +410,13 @@ async fn main() -> Result<()> {
                 info!("⚠️  Risk map not found: {}, skipping risk analysis", risk_map);
             }
 
+            // Load CODEOWNERS if exists
+            if std::path::Path::new(&codeowners).exists() {
+                guardian = guardian.with_codeowners(&codeowners)?;
+            } else {
+                info!("⚠️  CODEOWNERS not found: {}, skipping owner approval", codeowners);
+            }
+
             let decision = guardian.evaluate_pr(pr_number, dry_run).await?;
 
             // Output decision as JSON for CI consumption
@@ -215,8 +433,115 @@ async fn main() -> Result<()> {
                 }
             }
         }
+        Commands::MergeQueue {
+            poll_interval,
+            timeout,
+            once,
+            dry_run,
+        } => {
+            let octocrab = octocrab::Octocrab::builder()
+                .personal_token(token.clone())
+                .build()?;
+
+            let (owner, repo_name) = repo.split_once('/').expect("Invalid repo format (expected owner/repo)");
+
+            let queue = merge_queue::MergeQueue::new(
+                octocrab,
+                owner.to_string(),
+                repo_name.to_string(),
+            )
+            .with_poll_interval(poll_interval)
+            .with_check_timeout(timeout);
+
+            let outcomes = if once {
+                vec![queue.process_next(dry_run).await?]
+            } else {
+                queue.process_all(dry_run).await?
+            };
+
+            if cli.output == "json" {
+                println!("{}", serde_json::to_string_pretty(&outcomes)?);
+            }
+        }
+        Commands::Dispatch {
+            strategy,
+            max_issues,
+            label,
+            agents,
+            risk_threshold,
+            state,
+            dry_run,
+        } => {
+            let octocrab = octocrab::Octocrab::builder()
+                .personal_token(token.clone())
+                .build()?;
+
+            let (owner, repo_name) = repo.split_once('/').expect("Invalid repo format (expected owner/repo)");
+            let strategy: dispatcher_core::Strategy = strategy.parse()?;
+
+            let dispatcher = dispatcher_core::DispatcherCore::new(
+                octocrab,
+                owner.to_string(),
+                repo_name.to_string(),
+            )
+            .with_risk_threshold(risk_threshold)
+            .with_registry(load_agent_registry(&agents)?)
+            .with_state_file(&state)?;
+            info!("🤖 {} agent(s) registered", dispatcher.registry().agents().len());
+
+            let assignments = dispatcher
+                .dispatch_issues(strategy, max_issues, label, dry_run)
+                .await?;
+
+            if cli.output == "json" {
+                println!("{}", serde_json::to_string_pretty(&assignments)?);
+            }
+        }
+        Commands::Reclaim {
+            days,
+            agents,
+            state,
+            dry_run,
+        } => {
+            let octocrab = octocrab::Octocrab::builder()
+                .personal_token(token.clone())
+                .build()?;
+
+            let (owner, repo_name) = repo.split_once('/').expect("Invalid repo format (expected owner/repo)");
+
+            let dispatcher = dispatcher_core::DispatcherCore::new(
+                octocrab,
+                owner.to_string(),
+                repo_name.to_string(),
+            )
+            .with_registry(load_agent_registry(&agents)?)
+            .with_state_file(&state)?;
+
+            let reclaimed = dispatcher.reclaim_stale(days, dry_run).await?;
+
+            if cli.output == "json" {
+                println!("{}", serde_json::to_string_pretty(&reclaimed)?);
+            }
+        }
     }
 
     info!("✅ Orchestrator completed successfully");
     Ok(())
 }
+
+/// Open the run store and bring it up to date
+async fn synced_store(client: &github::GitHubClient, path: &str, backfill_days: i64) -> Result<run_store::RunStore> {
+    let mut store = run_store::RunStore::open(path)?;
+    run_store::sync(client, &mut store, backfill_days).await?;
+    Ok(store)
+}
+
+/// Load the agent registry if it exists, otherwise the built-in agents
+fn load_agent_registry(path: &str) -> Result<dispatcher_core::AgentRegistry> {
+    if std::path::Path::new(path).exists() {
+        dispatcher_core::AgentRegistry::from_file(path)
+    } else {
+        info!("⚠️  Agent registry not found: {}, using built-in agents", path);
+        Ok(dispatcher_core::AgentRegistry::defaults())
+    }
+}
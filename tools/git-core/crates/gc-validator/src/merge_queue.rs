@@ -0,0 +1,540 @@
+//! # Merge Queue - Serialized Rebase-and-Recheck Merging
+//!
+//! Optional Guardian mode: instead of squash-merging as soon as confidence
+//! passes, approved PRs are labeled `merge-queue` and merged one at a time
+//! after being brought up to date with the base branch and re-checked.
+//!
+//! ## Queue Processing
+//!
+//! ```text
+//! Guardian (AutoMerge)  →  label `merge-queue`
+//!
+//! process_next():
+//!   1. Pick the oldest open PR carrying the queue label
+//!   2. Wait until GitHub has computed its mergeable state
+//!   3. Behind base?  → update branch, wait for the new head SHA, back to 2
+//!   4. Conflicts or draft? → dequeue + needs-human
+//!   5. Wait for all check runs and commit statuses on the head SHA
+//!   6. Green and still mergeable → squash-merge pinned to the checked SHA
+//!      Red/timeout/blocked       → dequeue + needs-human
+//! ```
+//!
+//! The queue state lives entirely in labels, so concurrent workflow runs
+//! and restarts see the same queue.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use workflow_orchestrator::merge_queue::MergeQueue;
+//! use octocrab::Octocrab;
+//!
+//! #[tokio::main]
+//! async fn main() -> anyhow::Result<()> {
+//!     let github = Octocrab::builder().build()?;
+//!     let queue = MergeQueue::new(github, "owner".to_string(), "repo".to_string());
+//!
+//!     let outcomes = queue.process_all(false).await?;
+//!     println!("{:?}", outcomes);
+//!     Ok(())
+//! }
+//! ```
+
+use anyhow::{Context, Result};
+use octocrab::models::{pulls::MergeableState, StatusState};
+use octocrab::params::repos::{Commitish, Reference};
+use octocrab::Octocrab;
+use serde::Serialize;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+/// Label marking a PR as queued for merge
+pub const QUEUE_LABEL: &str = "merge-queue";
+
+const DEFAULT_POLL_INTERVAL_SECS: u64 = 30;
+const DEFAULT_CHECK_TIMEOUT_SECS: u64 = 1800;
+
+/// Branch updates per PR before giving up on a base that keeps moving
+const MAX_UPDATE_ROUNDS: usize = 3;
+
+/// What a PR's mergeable state allows the queue to do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Readiness {
+    /// GitHub is still computing the state
+    Computing,
+    Conflicts,
+    Behind,
+    Draft,
+    /// Branch protection blocks the merge (e.g. required checks or reviews)
+    Blocked,
+    Ready,
+}
+
+impl Readiness {
+    fn of(state: Option<&MergeableState>) -> Self {
+        match state {
+            Some(MergeableState::Dirty) => Readiness::Conflicts,
+            Some(MergeableState::Behind) => Readiness::Behind,
+            Some(MergeableState::Draft) => Readiness::Draft,
+            Some(MergeableState::Blocked) => Readiness::Blocked,
+            // Unstable: non-required checks fail, which the queue checks itself
+            Some(MergeableState::Clean | MergeableState::HasHooks | MergeableState::Unstable) => Readiness::Ready,
+            _ => Readiness::Computing,
+        }
+    }
+}
+
+/// Aggregated state of the check runs on a commit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum CheckStatus {
+    /// No checks reported yet, or some are still running
+    Pending,
+    /// Every check completed successfully (or was skipped/neutral)
+    Passed,
+    /// At least one check completed with a failing conclusion
+    Failed,
+}
+
+impl CheckStatus {
+    /// Aggregate check-run conclusions (`None` = still running)
+    ///
+    /// A failure wins over pending checks so a red PR leaves the queue
+    /// without waiting for slower jobs.
+    pub fn from_conclusions<'a, I>(conclusions: I) -> Self
+    where
+        I: IntoIterator<Item = Option<&'a str>>,
+    {
+        let mut seen = false;
+        let mut pending = false;
+
+        for conclusion in conclusions {
+            seen = true;
+            match conclusion {
+                None => pending = true,
+                Some("success") | Some("skipped") | Some("neutral") => {}
+                Some(_) => return CheckStatus::Failed,
+            }
+        }
+
+        if !seen || pending {
+            CheckStatus::Pending
+        } else {
+            CheckStatus::Passed
+        }
+    }
+}
+
+/// Result of processing one queue entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum QueueOutcome {
+    /// No PRs waiting in the queue
+    Empty,
+    /// PR was updated, re-checked and merged
+    Merged { pr_number: u64, sha: String },
+    /// PR was removed from the queue and escalated
+    Dequeued { pr_number: u64, reason: String },
+}
+
+/// Merge Queue engine
+pub struct MergeQueue {
+    github: Octocrab,
+    owner: String,
+    repo: String,
+    poll_interval: Duration,
+    check_timeout: Duration,
+}
+
+impl MergeQueue {
+    /// Create new Merge Queue instance
+    pub fn new(github: Octocrab, owner: String, repo: String) -> Self {
+        Self {
+            github,
+            owner,
+            repo,
+            poll_interval: Duration::from_secs(DEFAULT_POLL_INTERVAL_SECS),
+            check_timeout: Duration::from_secs(DEFAULT_CHECK_TIMEOUT_SECS),
+        }
+    }
+
+    /// Set interval between check polls (default: 30s)
+    pub fn with_poll_interval(mut self, seconds: u64) -> Self {
+        self.poll_interval = Duration::from_secs(seconds);
+        self
+    }
+
+    /// Set maximum time to wait for fresh checks (default: 30 min)
+    pub fn with_check_timeout(mut self, seconds: u64) -> Self {
+        self.check_timeout = Duration::from_secs(seconds);
+        self
+    }
+
+    /// Add a PR to the queue
+    pub async fn enqueue(&self, pr_number: u64, confidence: u8) -> Result<()> {
+        info!("📥 Queueing PR #{} for merge (confidence: {})", pr_number, confidence);
+
+        let issues = self.github.issues(&self.owner, &self.repo);
+
+        issues
+            .add_labels(pr_number, &[QUEUE_LABEL.to_string()])
+            .await
+            .context(format!("Failed to add {} label to PR #{}", QUEUE_LABEL, pr_number))?;
+
+        let comment = format!(
+            "🤖 **Guardian Agent**: Queued for merge (confidence: {}%)\n\n\
+             This PR will be updated against the base branch and merged once \
+             fresh checks pass.",
+            confidence
+        );
+        issues.create_comment(pr_number, comment).await?;
+
+        Ok(())
+    }
+
+    /// List queued PR numbers, oldest first
+    pub async fn queued_prs(&self) -> Result<Vec<u64>> {
+        let page = self
+            .github
+            .issues(&self.owner, &self.repo)
+            .list()
+            .state(octocrab::params::State::Open)
+            .labels(&[QUEUE_LABEL.to_string()])
+            .per_page(100)
+            .send()
+            .await
+            .context("Failed to fetch queued PRs")?;
+
+        let mut numbers: Vec<u64> = page
+            .items
+            .into_iter()
+            .filter(|issue| issue.pull_request.is_some())
+            .map(|issue| issue.number)
+            .collect();
+        numbers.sort_unstable();
+
+        debug!("📋 {} PR(s) in merge queue", numbers.len());
+        Ok(numbers)
+    }
+
+    /// Process queued PRs one at a time until the queue is empty
+    pub async fn process_all(&self, dry_run: bool) -> Result<Vec<QueueOutcome>> {
+        let mut outcomes = Vec::new();
+
+        loop {
+            let outcome = self.process_next(dry_run).await?;
+            if outcome == QueueOutcome::Empty {
+                break;
+            }
+            outcomes.push(outcome);
+
+            // A dry run never changes labels, so the head would repeat forever
+            if dry_run {
+                break;
+            }
+        }
+
+        info!("🎉 Merge queue drained ({} processed)", outcomes.len());
+        Ok(outcomes)
+    }
+
+    /// Update, re-check and merge the PR at the head of the queue
+    pub async fn process_next(&self, dry_run: bool) -> Result<QueueOutcome> {
+        let Some(pr_number) = self.queued_prs().await?.into_iter().next() else {
+            info!("✅ Merge queue is empty");
+            return Ok(QueueOutcome::Empty);
+        };
+
+        info!("🔄 Processing queued PR #{}", pr_number);
+
+        let pulls = self.github.pulls(&self.owner, &self.repo);
+
+        for _ in 0..MAX_UPDATE_ROUNDS {
+            let Some((head_sha, readiness)) = self.wait_for_mergeable_state(pr_number).await? else {
+                return self
+                    .dequeue(pr_number, "GitHub did not compute the mergeable state in time", dry_run)
+                    .await;
+            };
+
+            match readiness {
+                Readiness::Conflicts => {
+                    return self
+                        .dequeue(pr_number, "Merge conflicts with base branch", dry_run)
+                        .await;
+                }
+                Readiness::Draft => {
+                    return self.dequeue(pr_number, "PR is a draft", dry_run).await;
+                }
+                Readiness::Behind if !dry_run => {
+                    info!("⬆️  PR #{} is behind base, updating branch", pr_number);
+                    pulls
+                        .update_branch(pr_number)
+                        .await
+                        .context(format!("Failed to update branch of PR #{}", pr_number))?;
+
+                    if self.wait_for_new_head(pr_number, &head_sha).await?.is_none() {
+                        return self
+                            .dequeue(pr_number, "Branch update did not complete in time", dry_run)
+                            .await;
+                    }
+                    // Recheck the state of the new head
+                    continue;
+                }
+                Readiness::Behind => info!("🔍 Dry run - PR #{} is behind base, branch not updated", pr_number),
+                // Blocked before checks usually means required checks are still running
+                Readiness::Blocked | Readiness::Ready | Readiness::Computing => {}
+            }
+
+            match self.wait_for_checks(&head_sha).await? {
+                CheckStatus::Passed => {}
+                CheckStatus::Failed => {
+                    return self
+                        .dequeue(pr_number, "Checks failed after updating against base", dry_run)
+                        .await;
+                }
+                CheckStatus::Pending => {
+                    return self
+                        .dequeue(pr_number, "Timed out waiting for fresh checks", dry_run)
+                        .await;
+                }
+            }
+
+            if dry_run {
+                info!("🔍 Dry run - PR #{} would be merged at {}", pr_number, head_sha);
+                return Ok(QueueOutcome::Merged { pr_number, sha: head_sha });
+            }
+
+            // Checks are green: anything still blocking is a review or protection rule
+            let Some((sha, readiness)) = self.wait_for_mergeable_state(pr_number).await? else {
+                return self
+                    .dequeue(pr_number, "GitHub did not compute the mergeable state in time", dry_run)
+                    .await;
+            };
+            match readiness {
+                Readiness::Ready if sha == head_sha => {}
+                // A new push or a moved base: check again
+                Readiness::Ready | Readiness::Behind => continue,
+                Readiness::Blocked => {
+                    return self
+                        .dequeue(pr_number, "Blocked by branch protection after checks passed", dry_run)
+                        .await;
+                }
+                Readiness::Conflicts | Readiness::Draft | Readiness::Computing => {
+                    return self
+                        .dequeue(pr_number, &format!("Not mergeable after checks ({:?})", readiness), dry_run)
+                        .await;
+                }
+            }
+
+            // Pin the merge to the SHA we checked so a late push cannot slip in
+            pulls
+                .merge(pr_number)
+                .method(octocrab::params::pulls::MergeMethod::Squash)
+                .sha(head_sha.clone())
+                .send()
+                .await
+                .context(format!("Failed to merge PR #{}", pr_number))?;
+
+            self.remove_queue_label(pr_number).await;
+            info!("✅ PR #{} merged from queue at {}", pr_number, head_sha);
+
+            return Ok(QueueOutcome::Merged { pr_number, sha: head_sha });
+        }
+
+        self.dequeue(pr_number, "Base branch kept moving while re-checking", dry_run)
+            .await
+    }
+
+    /// Poll the PR until GitHub has computed its mergeable state
+    ///
+    /// Returns the head SHA and readiness, or `None` on timeout.
+    async fn wait_for_mergeable_state(&self, pr_number: u64) -> Result<Option<(String, Readiness)>> {
+        let deadline = Instant::now() + self.check_timeout;
+
+        loop {
+            let pr = self.github.pulls(&self.owner, &self.repo).get(pr_number).await?;
+            let readiness = Readiness::of(pr.mergeable_state.as_ref());
+            if readiness != Readiness::Computing {
+                debug!("🔎 PR #{} at {}: {:?}", pr_number, pr.head.sha, readiness);
+                return Ok(Some((pr.head.sha, readiness)));
+            }
+            if Instant::now() >= deadline {
+                return Ok(None);
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+
+    /// Poll until the PR head moves past `previous_sha`
+    async fn wait_for_new_head(&self, pr_number: u64, previous_sha: &str) -> Result<Option<String>> {
+        let deadline = Instant::now() + self.check_timeout;
+
+        while Instant::now() < deadline {
+            tokio::time::sleep(self.poll_interval).await;
+
+            let pr = self.github.pulls(&self.owner, &self.repo).get(pr_number).await?;
+            if pr.head.sha != previous_sha {
+                debug!("🔀 PR #{} head moved to {}", pr_number, pr.head.sha);
+                return Ok(Some(pr.head.sha));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Poll check runs and commit statuses on `sha` until they settle or the timeout expires
+    async fn wait_for_checks(&self, sha: &str) -> Result<CheckStatus> {
+        let deadline = Instant::now() + self.check_timeout;
+
+        loop {
+            let mut conclusions = self.check_run_conclusions(sha).await?;
+            conclusions.extend(self.status_conclusion(sha).await?);
+
+            let status = CheckStatus::from_conclusions(conclusions.iter().map(|c| c.as_deref()));
+            debug!("⏳ Checks on {}: {:?}", sha, status);
+
+            if status != CheckStatus::Pending || Instant::now() >= deadline {
+                return Ok(status);
+            }
+
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+
+    /// Conclusions of every check run on `sha` (all pages)
+    async fn check_run_conclusions(&self, sha: &str) -> Result<Vec<Option<String>>> {
+        let mut conclusions = Vec::new();
+        let mut page = 1u32;
+
+        loop {
+            let checks = self
+                .github
+                .checks(&self.owner, &self.repo)
+                .list_check_runs_for_git_ref(Commitish(sha.to_string()))
+                .per_page(100)
+                .page(page)
+                .send()
+                .await?;
+
+            let count = checks.check_runs.len();
+            conclusions.extend(checks.check_runs.into_iter().map(|c| c.conclusion));
+            if count < 100 || conclusions.len() as u64 >= checks.total_count {
+                return Ok(conclusions);
+            }
+            page += 1;
+        }
+    }
+
+    /// Combined commit status on `sha` as a check conclusion (`None` without statuses)
+    async fn status_conclusion(&self, sha: &str) -> Result<Option<Option<String>>> {
+        let combined = self
+            .github
+            .repos(&self.owner, &self.repo)
+            .combined_status_for_ref(&Reference::Commit(sha.to_string()))
+            .await?;
+
+        Ok(status_conclusion(combined.total_count, &combined.state))
+    }
+
+    /// Remove a PR from the queue and hand it to a human
+    async fn dequeue(&self, pr_number: u64, reason: &str, dry_run: bool) -> Result<QueueOutcome> {
+        warn!("⚠️ Dequeuing PR #{}: {}", pr_number, reason);
+
+        if !dry_run {
+            let issues = self.github.issues(&self.owner, &self.repo);
+
+            let comment = format!(
+                "🤖 **Guardian Agent**: Removed from merge queue\n\n\
+                 **Reason:** {}\n\n\
+                 A human reviewer must re-approve this PR for merge.",
+                reason
+            );
+            issues.create_comment(pr_number, comment).await?;
+            issues
+                .add_labels(pr_number, &[String::from("needs-human")])
+                .await?;
+
+            self.remove_queue_label(pr_number).await;
+        }
+
+        Ok(QueueOutcome::Dequeued {
+            pr_number,
+            reason: reason.to_string(),
+        })
+    }
+
+    async fn remove_queue_label(&self, pr_number: u64) {
+        if let Err(e) = self
+            .github
+            .issues(&self.owner, &self.repo)
+            .remove_label(pr_number, QUEUE_LABEL)
+            .await
+        {
+            warn!("Failed to remove {} label from PR #{}: {}", QUEUE_LABEL, pr_number, e);
+        }
+    }
+}
+
+/// A combined commit status as a check conclusion
+///
+/// Without statuses GitHub reports `pending`, so there is nothing to wait for.
+fn status_conclusion(total_count: i64, state: &StatusState) -> Option<Option<String>> {
+    if total_count == 0 {
+        return None;
+    }
+    Some(match state {
+        StatusState::Success => Some("success".to_string()),
+        StatusState::Pending => None,
+        _ => Some("failure".to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_status_passed() {
+        let status = CheckStatus::from_conclusions([Some("success"), Some("skipped"), Some("neutral")]);
+        assert_eq!(status, CheckStatus::Passed);
+    }
+
+    #[test]
+    fn test_check_status_pending() {
+        assert_eq!(CheckStatus::from_conclusions([]), CheckStatus::Pending);
+        assert_eq!(
+            CheckStatus::from_conclusions([Some("success"), None]),
+            CheckStatus::Pending
+        );
+    }
+
+    #[test]
+    fn test_readiness() {
+        assert_eq!(Readiness::of(None), Readiness::Computing);
+        assert_eq!(Readiness::of(Some(&MergeableState::Unknown)), Readiness::Computing);
+        assert_eq!(Readiness::of(Some(&MergeableState::Blocked)), Readiness::Blocked);
+        assert_eq!(Readiness::of(Some(&MergeableState::Behind)), Readiness::Behind);
+        assert_eq!(Readiness::of(Some(&MergeableState::Dirty)), Readiness::Conflicts);
+        assert_eq!(Readiness::of(Some(&MergeableState::Unstable)), Readiness::Ready);
+    }
+
+    #[test]
+    fn test_commit_statuses() {
+        assert_eq!(status_conclusion(0, &StatusState::Pending), None);
+        assert_eq!(status_conclusion(2, &StatusState::Pending), Some(None));
+        assert_eq!(status_conclusion(1, &StatusState::Error), Some(Some("failure".to_string())));
+
+        // A failing status fails the PR even when every check run passed
+        let conclusions = [Some("success".to_string()), status_conclusion(1, &StatusState::Failure).unwrap()];
+        assert_eq!(
+            CheckStatus::from_conclusions(conclusions.iter().map(|c| c.as_deref())),
+            CheckStatus::Failed
+        );
+    }
+
+    #[test]
+    fn test_check_status_failure_wins() {
+        let status = CheckStatus::from_conclusions([None, Some("success"), Some("failure")]);
+        assert_eq!(status, CheckStatus::Failed);
+
+        let status = CheckStatus::from_conclusions([Some("timed_out")]);
+        assert_eq!(status, CheckStatus::Failed);
+    }
+}
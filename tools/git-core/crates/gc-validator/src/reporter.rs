@@ -1,47 +1,83 @@
-//! Report generation with parallel data gathering
+//! Report generation from the local run store
 
-use crate::github::{GitHubClient, WorkflowRun};
+use crate::github::WorkflowAnalysis;
+use crate::run_store::RunStore;
+use crate::trends::{self, Selector, TrendReport};
 use anyhow::Result;
 use chrono::{Utc, Duration};
-use futures::future::join_all;
 use tracing::info;
 
-/// Generate comprehensive report
+/// Generate comprehensive report from the local run store
 pub async fn generate_report(
-    client: &GitHubClient,
+    store: &RunStore,
     report_type: &str,
     hours: u64,
+    compare: Option<&str>,
     output_format: &str,
 ) -> Result<()> {
-    info!("📝 Generating {} report for last {} hours...", report_type, hours);
+    if let Some(spec) = compare {
+        let (current, baseline) = trends::parse_compare(spec)?;
+        return generate_comparison_report(store, &current, &baseline, output_format);
+    }
 
-    let runs = client.get_workflow_runs(100).await?;
-    let cutoff = Utc::now() - Duration::hours(hours as i64);
+    if report_type == "diff" {
+        // Last `hours` against the `hours` before
+        let now = Utc::now();
+        let window = Duration::hours(hours as i64);
+        let current = store.analyses_between(now - window, now)?;
+        let baseline = store.analyses_between(now - window * 2, now - window)?;
+        let report = trends::compare(
+            &current,
+            &baseline,
+            &format!("last {}h", hours),
+            &format!("previous {}h", hours),
+        );
+        return print_trend_report(&report, output_format);
+    }
 
-    let filtered_runs: Vec<_> = runs.into_iter()
-        .filter(|r| {
-            chrono::DateTime::parse_from_rfc3339(&r.created_at)
-                .map(|dt| dt > cutoff)
-                .unwrap_or(false)
-        })
-        .collect();
+    info!("📝 Generating {} report for last {} hours...", report_type, hours);
 
-    info!("📊 Found {} runs in time range", filtered_runs.len());
+    let cutoff = Utc::now() - Duration::hours(hours as i64);
+    let analyses = store.analyses_since(cutoff)?;
 
-    // Parallel analysis
-    let analyses = client.analyze_runs_parallel(filtered_runs).await?;
+    info!("📊 Found {} runs in time range", analyses.len());
 
     match report_type {
         "summary" => generate_summary_report(&analyses, output_format),
         "detailed" => generate_detailed_report(&analyses, output_format),
-        "diff" => generate_diff_report(&analyses, output_format),
         _ => generate_summary_report(&analyses, output_format),
     }
 
     Ok(())
 }
 
-fn generate_summary_report(analyses: &[crate::github::WorkflowAnalysis], format: &str) {
+/// Compare two selections of stored runs (`last-7d..prev-7d`, `v1.3.0..v1.2.0`)
+pub fn generate_comparison_report(
+    store: &RunStore,
+    current: &Selector,
+    baseline: &Selector,
+    output_format: &str,
+) -> Result<()> {
+    info!("📝 Comparing {} against {}...", current.label(), baseline.label());
+
+    let current_runs = select(store, current)?;
+    let baseline_runs = select(store, baseline)?;
+
+    info!("📊 {} runs vs {} runs", current_runs.len(), baseline_runs.len());
+
+    let report = trends::compare(&current_runs, &baseline_runs, &current.label(), &baseline.label());
+    print_trend_report(&report, output_format)
+}
+
+fn select(store: &RunStore, selector: &Selector) -> Result<Vec<WorkflowAnalysis>> {
+    match (selector, selector.window(Utc::now())) {
+        (Selector::Ref(git_ref), _) => store.analyses_for_ref(git_ref),
+        (_, Some((from, to))) => store.analyses_between(from, to),
+        (_, None) => Ok(Vec::new()),
+    }
+}
+
+fn generate_summary_report(analyses: &[WorkflowAnalysis], format: &str) {
     let total = analyses.len();
     let success = analyses.iter().filter(|a| a.run.conclusion.as_deref() == Some("success")).count();
     let failed = analyses.iter().filter(|a| a.run.conclusion.as_deref() == Some("failure")).count();
@@ -82,7 +118,7 @@ fn generate_summary_report(analyses: &[crate::github::WorkflowAnalysis], format:
     }
 }
 
-fn generate_detailed_report(analyses: &[crate::github::WorkflowAnalysis], format: &str) {
+fn generate_detailed_report(analyses: &[WorkflowAnalysis], format: &str) {
     match format {
         "markdown" => {
             println!("# 📋 Detailed Workflow Report\n");
@@ -155,59 +191,11 @@ fn generate_detailed_report(analyses: &[crate::github::WorkflowAnalysis], format
     }
 }
 
-fn generate_diff_report(analyses: &[crate::github::WorkflowAnalysis], format: &str) {
-    // Compare recent runs with older runs to show trends
-    let half = analyses.len() / 2;
-    let recent = &analyses[..half.min(analyses.len())];
-    let older = &analyses[half.min(analyses.len())..];
-
-    let recent_success = recent.iter().filter(|a| a.run.conclusion.as_deref() == Some("success")).count();
-    let older_success = older.iter().filter(|a| a.run.conclusion.as_deref() == Some("success")).count();
-
-    let recent_avg_duration: i64 = recent.iter()
-        .filter_map(|a| a.duration_seconds)
-        .sum::<i64>() / recent.len().max(1) as i64;
-
-    let older_avg_duration: i64 = older.iter()
-        .filter_map(|a| a.duration_seconds)
-        .sum::<i64>() / older.len().max(1) as i64;
-
-    let success_trend = if recent.len() > 0 && older.len() > 0 {
-        (recent_success as f64 / recent.len() as f64) - (older_success as f64 / older.len() as f64)
-    } else {
-        0.0
-    };
-
-    let duration_trend = recent_avg_duration - older_avg_duration;
-
+fn print_trend_report(report: &TrendReport, format: &str) -> Result<()> {
     match format {
-        "markdown" => {
-            println!("# 📈 Workflow Trend Report\n");
-            println!("| Metric | Recent | Previous | Trend |");
-            println!("|--------|--------|----------|-------|");
-            println!("| Success Rate | {:.1}% | {:.1}% | {} |",
-                if recent.len() > 0 { recent_success as f64 / recent.len() as f64 * 100.0 } else { 0.0 },
-                if older.len() > 0 { older_success as f64 / older.len() as f64 * 100.0 } else { 0.0 },
-                if success_trend > 0.0 { "📈" } else if success_trend < 0.0 { "📉" } else { "➡️" }
-            );
-            println!("| Avg Duration | {}s | {}s | {} |",
-                recent_avg_duration,
-                older_avg_duration,
-                if duration_trend < 0 { "📈 Faster" } else if duration_trend > 0 { "📉 Slower" } else { "➡️ Same" }
-            );
-        }
-        _ => {
-            println!("\n📈 Workflow Trends\n");
-            println!("Success Rate: {:.1}% → {:.1}% {}",
-                if older.len() > 0 { older_success as f64 / older.len() as f64 * 100.0 } else { 0.0 },
-                if recent.len() > 0 { recent_success as f64 / recent.len() as f64 * 100.0 } else { 0.0 },
-                if success_trend > 0.0 { "📈" } else if success_trend < 0.0 { "📉" } else { "➡️" }
-            );
-            println!("Avg Duration: {}s → {}s {}",
-                older_avg_duration,
-                recent_avg_duration,
-                if duration_trend < 0 { "📈" } else if duration_trend > 0 { "📉" } else { "➡️" }
-            );
-        }
+        "json" => println!("{}", serde_json::to_string_pretty(report)?),
+        "markdown" => println!("{}", report.to_markdown()),
+        _ => println!("{}", report.to_terminal()),
     }
+    Ok(())
 }
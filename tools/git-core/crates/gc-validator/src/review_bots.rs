@@ -0,0 +1,387 @@
+//! # Review Bots - AI Reviewer Registry
+//!
+//! Validation PRs ask AI review bots for a review by posting a trigger
+//! comment. Which bots exist is configuration, loaded from
+//! `.github/review-bots.yml` (built-in Gemini, CodeRabbit and Copilot
+//! entries without a file):
+//!
+//! ```yaml
+//! reviewers:
+//!   - name: coderabbit
+//!     trigger: "@coderabbitai review"
+//!     detect: review         # the bot reviewed a recent pull request
+//!     login: coderabbitai[bot]
+//!   - name: gemini
+//!     trigger: "/gemini review"
+//!     detect: comment        # the bot commented somewhere recently
+//!     login: gemini-code-assist[bot]
+//!   - name: reviewpad
+//!     trigger: "/reviewpad review"
+//!     detect: app            # the app ran a check suite on the default branch
+//!     login: reviewpad[bot]
+//!   - name: sourcery
+//!     trigger: "@sourcery-ai review"
+//!     detect: always
+//!     enabled: false
+//! ```
+//!
+//! `{pr}` in a trigger is replaced with the PR number. Every request
+//! carries a hidden `<!-- review-request:NAME -->` marker so a reviewer is
+//! asked at most once per PR.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+use tracing::{info, warn};
+
+use crate::github::{GitHubClient, IssueComment};
+
+/// Default registry location
+pub const DEFAULT_REVIEWERS_PATH: &str = ".github/review-bots.yml";
+
+/// How to tell whether a reviewer bot is present on the repository
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Detect {
+    /// `login` submitted a review on a recently updated pull request
+    #[default]
+    Review,
+    /// `login` commented on an issue or PR recently
+    Comment,
+    /// The GitHub App behind `login` (without `[bot]`) created a check suite
+    /// on the default branch head, so it is installed even without history
+    App,
+    /// Assume present
+    Always,
+}
+
+/// A review bot and how to ask it for a review
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Reviewer {
+    pub name: String,
+    /// Comment that triggers the review (`{pr}` = PR number)
+    pub trigger: String,
+    #[serde(default)]
+    pub detect: Detect,
+    /// Bot login for `detect: review`, `comment` and `app`
+    #[serde(default)]
+    pub login: Option<String>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl Reviewer {
+    /// Trigger comment for a PR, tagged with the request marker
+    pub fn request_body(&self, pr_number: u64) -> String {
+        format!("{}\n\n{}", self.trigger.replace("{pr}", &pr_number.to_string()), self.marker())
+    }
+
+    fn marker(&self) -> String {
+        format!("<!-- review-request:{} -->", self.name)
+    }
+
+    /// Whether one of the comments already requested this review
+    pub fn already_requested(&self, comments: &[IssueComment]) -> bool {
+        let marker = self.marker();
+        let trigger = self.trigger.trim();
+        comments.iter().any(|c| {
+            c.body.contains(&marker)
+                || (!trigger.contains("{pr}") && c.body.trim_start().starts_with(trigger))
+        })
+    }
+
+    /// Whether the bot is present given the observed review and comment authors
+    pub fn is_present(&self, presence: &Presence) -> bool {
+        let seen_in = |logins: &HashSet<String>| {
+            self.login.as_ref().is_some_and(|login| logins.contains(&login.to_lowercase()))
+        };
+        match self.detect {
+            Detect::Review => seen_in(&presence.reviewers),
+            Detect::Comment => seen_in(&presence.commenters),
+            Detect::App => self
+                .login
+                .as_ref()
+                .is_some_and(|login| presence.apps.contains(&app_slug(login))),
+            Detect::Always => true,
+        }
+    }
+}
+
+/// App slug of a bot login (`coderabbitai[bot]` → `coderabbitai`)
+fn app_slug(login: &str) -> String {
+    login.trim_end_matches("[bot]").to_lowercase()
+}
+
+/// Review and comment authors and check suite apps seen on the repository (lowercase)
+#[derive(Debug, Clone, Default)]
+pub struct Presence {
+    pub reviewers: HashSet<String>,
+    pub commenters: HashSet<String>,
+    pub apps: HashSet<String>,
+}
+
+/// Configured review bots
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewerRegistry {
+    reviewers: Vec<Reviewer>,
+}
+
+impl ReviewerRegistry {
+    /// Load the registry from a YAML file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = std::fs::read_to_string(path.as_ref())
+            .with_context(|| format!("Failed to read {}", path.as_ref().display()))?;
+        Self::from_yaml(&content)
+            .with_context(|| format!("Invalid reviewer registry {}", path.as_ref().display()))
+    }
+
+    /// Parse and validate registry YAML
+    pub fn from_yaml(content: &str) -> Result<Self> {
+        let registry: Self = serde_yaml::from_str(content).context("Failed to parse reviewer registry")?;
+        registry.validate()?;
+        Ok(registry)
+    }
+
+    /// Load `path` if it exists, otherwise the built-in reviewers
+    pub fn load_or_default(path: &str) -> Result<Self> {
+        if Path::new(path).exists() {
+            Self::from_file(path)
+        } else {
+            info!("⚠️  Reviewer registry not found: {}, using built-in reviewers", path);
+            Ok(Self::defaults())
+        }
+    }
+
+    /// Built-in Gemini, CodeRabbit and Copilot reviewers
+    pub fn defaults() -> Self {
+        let reviewer = |name: &str, trigger: &str, detect: Detect, login: &str| Reviewer {
+            name: name.to_string(),
+            trigger: trigger.to_string(),
+            detect,
+            login: Some(login.to_string()),
+            enabled: true,
+        };
+
+        Self {
+            reviewers: vec![
+                reviewer(
+                    "gemini",
+                    "/gemini review\n\nPlease analyze this workflow validation report: failures, \
+                     optimization opportunities, security concerns and whether the suggested fixes are appropriate.",
+                    Detect::Review,
+                    "gemini-code-assist[bot]",
+                ),
+                reviewer(
+                    "coderabbit",
+                    "@coderabbitai review\n\nPlease analyze this validation report for pattern improvements, \
+                     best practices violations and potential reliability issues.",
+                    Detect::Review,
+                    "coderabbitai[bot]",
+                ),
+                reviewer(
+                    "copilot",
+                    "@copilot please review the AI reviews above and open follow-up PRs for the fixes.",
+                    Detect::Comment,
+                    "Copilot",
+                ),
+            ],
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        let mut names = HashSet::new();
+        for reviewer in &self.reviewers {
+            if reviewer.name.trim().is_empty() || reviewer.trigger.trim().is_empty() {
+                anyhow::bail!("Reviewers need a non-empty name and trigger");
+            }
+            if !names.insert(reviewer.name.to_lowercase()) {
+                anyhow::bail!("Duplicate reviewer name: {}", reviewer.name);
+            }
+            if reviewer.detect != Detect::Always && reviewer.login.is_none() {
+                anyhow::bail!("Reviewer {} uses detect: {:?} but has no login", reviewer.name, reviewer.detect);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn reviewers(&self) -> &[Reviewer] {
+        &self.reviewers
+    }
+
+    /// Enabled reviewers that are present and not yet asked on the PR
+    pub fn pending<'a>(&'a self, presence: &Presence, comments: &[IssueComment]) -> Vec<&'a Reviewer> {
+        self.reviewers
+            .iter()
+            .filter(|r| r.enabled && r.is_present(presence) && !r.already_requested(comments))
+            .collect()
+    }
+}
+
+impl Default for ReviewerRegistry {
+    fn default() -> Self {
+        Self::defaults()
+    }
+}
+
+/// Look up which of the enabled reviewers are active on the repository
+pub async fn detect_presence(client: &GitHubClient, registry: &ReviewerRegistry) -> Presence {
+    let enabled: Vec<&Reviewer> = registry.reviewers().iter().filter(|r| r.enabled).collect();
+    let mut presence = Presence::default();
+
+    if enabled.iter().any(|r| r.detect == Detect::Review) {
+        match client.recent_review_authors().await {
+            Ok(logins) => presence.reviewers = logins.into_iter().map(|l| l.to_lowercase()).collect(),
+            Err(e) => warn!("⚠️  Could not list recent pull request reviews: {}", e),
+        }
+    }
+    if enabled.iter().any(|r| r.detect == Detect::Comment) {
+        match client.list_recent_comments().await {
+            Ok(comments) => presence.commenters = comments.into_iter().map(|c| c.user.login.to_lowercase()).collect(),
+            Err(e) => warn!("⚠️  Could not list recent comments: {}", e),
+        }
+    }
+    if enabled.iter().any(|r| r.detect == Detect::App) {
+        match client.default_branch_check_suite_apps().await {
+            Ok(slugs) => presence.apps = slugs.into_iter().map(|s| s.to_lowercase()).collect(),
+            Err(e) => warn!("⚠️  Could not list check suites: {}", e),
+        }
+    }
+
+    for reviewer in enabled.iter().filter(|r| !r.is_present(&presence)) {
+        info!("⏭️  Reviewer {} not detected on this repository, skipped", reviewer.name);
+    }
+
+    presence
+}
+
+/// Ask the present reviewers for a review on a PR, skipping requests already made
+pub async fn request_reviews(
+    client: &GitHubClient,
+    registry: &ReviewerRegistry,
+    presence: &Presence,
+    pr_number: u64,
+) -> Result<Vec<String>> {
+    let comments = client.list_issue_comments(pr_number).await?;
+    let mut requested = Vec::new();
+
+    for reviewer in registry.pending(presence, &comments) {
+        client.add_pr_comment(pr_number, &reviewer.request_body(pr_number)).await?;
+        requested.push(reviewer.name.clone());
+    }
+
+    Ok(requested)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::CommentAuthor;
+
+    const REGISTRY_YAML: &str = r#"
+reviewers:
+  - name: coderabbit
+    trigger: "@coderabbitai review"
+    login: coderabbitai[bot]
+  - name: gemini
+    trigger: "/gemini review"
+    detect: comment
+    login: gemini-code-assist[bot]
+  - name: sourcery
+    trigger: "@sourcery-ai review PR #{pr}"
+    detect: always
+  - name: disabled
+    trigger: "@nobody review"
+    detect: always
+    enabled: false
+"#;
+
+    fn comment(login: &str, body: &str) -> IssueComment {
+        IssueComment {
+            body: body.to_string(),
+            user: CommentAuthor { login: login.to_string() },
+        }
+    }
+
+    fn names(reviewers: Vec<&Reviewer>) -> Vec<&str> {
+        reviewers.iter().map(|r| r.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_registry_from_yaml() {
+        let registry = ReviewerRegistry::from_yaml(REGISTRY_YAML).unwrap();
+        assert_eq!(registry.reviewers().len(), 4);
+        assert_eq!(registry.reviewers()[0].detect, Detect::Review);
+        assert!(!registry.reviewers()[3].enabled);
+
+        assert!(ReviewerRegistry::from_yaml("reviewers:\n  - {name: a, trigger: x, detect: review}").is_err());
+        assert!(ReviewerRegistry::from_yaml("reviewers:\n  - {name: a, trigger: x, detect: comment}").is_err());
+        assert!(ReviewerRegistry::from_yaml(
+            "reviewers:\n  - {name: a, trigger: x, detect: always}\n  - {name: A, trigger: y, detect: always}"
+        ).is_err());
+        assert!(ReviewerRegistry::defaults().validate().is_ok());
+    }
+
+    #[test]
+    fn test_pending_only_present_reviewers() {
+        let registry = ReviewerRegistry::from_yaml(REGISTRY_YAML).unwrap();
+
+        let nothing = Presence::default();
+        assert_eq!(names(registry.pending(&nothing, &[])), ["sourcery"]);
+
+        let presence = Presence {
+            reviewers: HashSet::from(["coderabbitai[bot]".to_string()]),
+            commenters: HashSet::from(["gemini-code-assist[bot]".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(names(registry.pending(&presence, &[])), ["coderabbit", "gemini", "sourcery"]);
+
+        // A comment does not count as a review
+        let presence = Presence {
+            commenters: HashSet::from(["coderabbitai[bot]".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(names(registry.pending(&presence, &[])), ["sourcery"]);
+    }
+
+    #[test]
+    fn test_freshly_installed_app_is_present() {
+        let registry = ReviewerRegistry::from_yaml(
+            "reviewers:\n  - {name: coderabbit, trigger: '@coderabbitai review', detect: app, login: 'CodeRabbitAI[bot]'}",
+        )
+        .unwrap();
+
+        assert!(registry.pending(&Presence::default(), &[]).is_empty());
+
+        let presence = Presence {
+            apps: HashSet::from(["coderabbitai".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(names(registry.pending(&presence, &[])), ["coderabbit"]);
+    }
+
+    #[test]
+    fn test_requests_are_not_duplicated() {
+        let registry = ReviewerRegistry::from_yaml(REGISTRY_YAML).unwrap();
+        let sourcery = &registry.reviewers()[2];
+
+        let body = sourcery.request_body(7);
+        assert!(body.starts_with("@sourcery-ai review PR #7"));
+        assert!(body.contains("<!-- review-request:sourcery -->"));
+
+        let presence = Presence {
+            reviewers: HashSet::from(["coderabbitai[bot]".to_string()]),
+            ..Default::default()
+        };
+        let comments = [
+            comment("github-actions[bot]", &body),
+            // Asked by hand before the registry existed
+            comment("maintainer", "@coderabbitai review\nplease"),
+        ];
+        assert!(registry.pending(&presence, &comments).is_empty());
+    }
+}
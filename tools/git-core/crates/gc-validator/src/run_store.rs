@@ -0,0 +1,653 @@
+//! # Run Store - Local History of Workflow Runs
+//!
+//! An embedded SQLite database (default `.✨/workflow-runs.db`) holding every
+//! analyzed run with its jobs, steps and extracted errors, plus the jobs of
+//! earlier attempts of re-run runs. Analyzers read from the store instead
+//! of refetching the last 50-100 runs, so trend reports can span thousands
+//! of runs.
+//!
+//! ## Incremental sync
+//!
+//! ```text
+//! cursor = oldest run still in progress
+//!        | newest stored run − 1 day
+//!        | now − 30 days                 (GitHub's re-run window)
+//!        | now − backfill days           (empty store)
+//!
+//! GET /actions/runs?created=>=cursor  (paginated)
+//!   └─ skip runs stored as completed with the same updated_at
+//!   └─ analyze the rest (jobs, logs of failed / cached jobs) and upsert
+//!   └─ re-run runs: fetch and store the jobs of every earlier attempt
+//! ```
+//!
+//! A re-run keeps the run's `created_at` but bumps its `updated_at`, so the
+//! cursor never moves past the re-run window.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use futures::future::join_all;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use std::collections::HashMap;
+use std::path::Path;
+use tracing::{info, warn};
+
+use crate::github::{GitHubClient, Job, Step, WorkflowAnalysis, WorkflowRun};
+use crate::log_analysis::{CacheStats, LogFinding};
+use crate::timeline::RunTimeline;
+
+/// Default store location (next to the other `.✨` state files)
+pub const DEFAULT_STORE_PATH: &str = ".✨/workflow-runs.db";
+
+/// Days fetched when the store is empty
+pub const DEFAULT_BACKFILL_DAYS: i64 = 30;
+
+/// Upper bound on runs fetched in one sync
+const MAX_SYNC_RUNS: usize = 5000;
+
+/// Days after which GitHub no longer allows re-running a run
+const RERUN_WINDOW_DAYS: i64 = 30;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS runs (
+    id               INTEGER PRIMARY KEY,
+    name             TEXT NOT NULL,
+    workflow_id      INTEGER NOT NULL,
+    path             TEXT,
+    status           TEXT NOT NULL,
+    conclusion       TEXT,
+    html_url         TEXT NOT NULL,
+    created_at       TEXT NOT NULL,
+    updated_at       TEXT NOT NULL,
+    run_attempt      INTEGER NOT NULL,
+    head_branch      TEXT NOT NULL,
+    head_sha         TEXT NOT NULL,
+    duration_seconds INTEGER,
+    cache_hits       INTEGER NOT NULL DEFAULT 0,
+    cache_misses     INTEGER NOT NULL DEFAULT 0,
+    errors           TEXT NOT NULL DEFAULT '[]',
+    warnings         TEXT NOT NULL DEFAULT '[]'
+);
+CREATE INDEX IF NOT EXISTS runs_created_at ON runs (created_at);
+
+CREATE TABLE IF NOT EXISTS jobs (
+    id           INTEGER PRIMARY KEY,
+    run_id       INTEGER NOT NULL REFERENCES runs (id) ON DELETE CASCADE,
+    name         TEXT NOT NULL,
+    status       TEXT NOT NULL,
+    conclusion   TEXT,
+    created_at   TEXT,
+    started_at   TEXT,
+    completed_at TEXT
+);
+CREATE INDEX IF NOT EXISTS jobs_run_id ON jobs (run_id);
+
+CREATE TABLE IF NOT EXISTS steps (
+    job_id       INTEGER NOT NULL REFERENCES jobs (id) ON DELETE CASCADE,
+    number       INTEGER NOT NULL,
+    name         TEXT NOT NULL,
+    status       TEXT NOT NULL,
+    conclusion   TEXT,
+    started_at   TEXT,
+    completed_at TEXT,
+    PRIMARY KEY (job_id, number)
+);
+
+CREATE TABLE IF NOT EXISTS errors (
+    run_id      INTEGER NOT NULL REFERENCES runs (id) ON DELETE CASCADE,
+    job         TEXT NOT NULL,
+    step        TEXT,
+    line        TEXT NOT NULL,
+    line_number INTEGER NOT NULL,
+    location    TEXT,
+    signature   TEXT,
+    category    TEXT,
+    suggestion  TEXT
+);
+CREATE INDEX IF NOT EXISTS errors_run_id ON errors (run_id);
+
+CREATE TABLE IF NOT EXISTS attempts (
+    run_id  INTEGER NOT NULL REFERENCES runs (id) ON DELETE CASCADE,
+    attempt INTEGER NOT NULL,
+    jobs    TEXT NOT NULL,
+    PRIMARY KEY (run_id, attempt)
+);
+";
+
+/// Local store of analyzed workflow runs
+pub struct RunStore {
+    conn: Connection,
+}
+
+impl RunStore {
+    /// Open (or create) the store at `path`
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+
+        let conn = Connection::open(path)
+            .with_context(|| format!("Failed to open run store {}", path.display()))?;
+        Self::init(conn)
+    }
+
+    /// Store that lives only as long as the process
+    pub fn open_in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self> {
+        conn.execute_batch("PRAGMA foreign_keys = ON; PRAGMA journal_mode = WAL;")?;
+        conn.execute_batch(SCHEMA).context("Failed to create run store schema")?;
+        Ok(Self { conn })
+    }
+
+    /// Number of stored runs
+    pub fn run_count(&self) -> Result<usize> {
+        let count: i64 = self.conn.query_row("SELECT COUNT(*) FROM runs", [], |r| r.get(0))?;
+        Ok(count as usize)
+    }
+
+    /// Insert or replace a run with its jobs, steps and errors
+    pub fn upsert(&mut self, analysis: &WorkflowAnalysis) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        let run = &analysis.run;
+
+        // Cascades to jobs, steps and errors
+        tx.execute("DELETE FROM runs WHERE id = ?1", params![run.id])?;
+        tx.execute(
+            "INSERT INTO runs (id, name, workflow_id, path, status, conclusion, html_url, created_at, updated_at,
+                               run_attempt, head_branch, head_sha, duration_seconds, cache_hits, cache_misses,
+                               errors, warnings)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)",
+            params![
+                run.id,
+                run.name,
+                run.workflow_id,
+                run.path,
+                run.status,
+                run.conclusion,
+                run.html_url,
+                run.created_at,
+                run.updated_at,
+                run.run_attempt,
+                run.head_branch,
+                run.head_sha,
+                analysis.duration_seconds,
+                analysis.cache.hits,
+                analysis.cache.misses,
+                serde_json::to_string(&analysis.errors)?,
+                serde_json::to_string(&analysis.warnings)?,
+            ],
+        )?;
+
+        for job in &analysis.jobs {
+            tx.execute(
+                "INSERT OR REPLACE INTO jobs (id, run_id, name, status, conclusion, created_at, started_at, completed_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![job.id, run.id, job.name, job.status, job.conclusion, job.created_at, job.started_at, job.completed_at],
+            )?;
+
+            for step in job.steps.iter().flatten() {
+                tx.execute(
+                    "INSERT OR REPLACE INTO steps (job_id, number, name, status, conclusion, started_at, completed_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                    params![job.id, step.number, step.name, step.status, step.conclusion, step.started_at, step.completed_at],
+                )?;
+            }
+        }
+
+        for f in &analysis.findings {
+            tx.execute(
+                "INSERT INTO errors (run_id, job, step, line, line_number, location, signature, category, suggestion)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![run.id, f.job, f.step, f.line, f.line_number, f.location, f.signature, f.category, f.suggestion],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Store the jobs of an earlier attempt of a stored run
+    ///
+    /// Must follow [`RunStore::upsert`] of the run, which clears its attempts.
+    pub fn upsert_attempt(&mut self, run_id: u64, attempt: u32, jobs: &[Job]) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO attempts (run_id, attempt, jobs) VALUES (?1, ?2, ?3)",
+            params![run_id, attempt, serde_json::to_string(jobs)?],
+        )?;
+        Ok(())
+    }
+
+    /// Jobs of the earlier attempts of a run, oldest attempt first
+    pub fn attempts(&self, run_id: u64) -> Result<Vec<(u32, Vec<Job>)>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT attempt, jobs FROM attempts WHERE run_id = ?1 ORDER BY attempt")?;
+        let rows = stmt
+            .query_map(params![run_id], |r| Ok((r.get::<_, u32>(0)?, r.get::<_, String>(1)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        rows.into_iter()
+            .map(|(attempt, jobs)| Ok((attempt, serde_json::from_str(&jobs)?)))
+            .collect()
+    }
+
+    /// Whether a run is stored as completed with this `updated_at`
+    pub fn is_current(&self, run: &WorkflowRun) -> Result<bool> {
+        let stored: Option<(String, String)> = self
+            .conn
+            .query_row(
+                "SELECT status, updated_at FROM runs WHERE id = ?1",
+                params![run.id],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .optional()?;
+
+        Ok(matches!(stored, Some((status, updated_at)) if status == "completed" && updated_at == run.updated_at))
+    }
+
+    /// Where the next sync starts (see module docs)
+    pub fn sync_cursor(&self, backfill_days: i64) -> Result<DateTime<Utc>> {
+        let in_progress: Option<String> = self.conn.query_row(
+            "SELECT MIN(created_at) FROM runs WHERE status != 'completed'",
+            [],
+            |r| r.get(0),
+        )?;
+        let newest: Option<String> = self.conn.query_row("SELECT MAX(created_at) FROM runs", [], |r| r.get(0))?;
+
+        let parse = |t: Option<String>| t.and_then(|t| t.parse::<DateTime<Utc>>().ok());
+        let rerun_window = Utc::now() - Duration::days(RERUN_WINDOW_DAYS);
+
+        Ok(match (parse(in_progress), parse(newest)) {
+            (Some(oldest_open), Some(newest)) => oldest_open.min(newest - Duration::days(1)).min(rerun_window),
+            (None, Some(newest)) => (newest - Duration::days(1)).min(rerun_window),
+            (Some(oldest_open), None) => oldest_open,
+            (None, None) => Utc::now() - Duration::days(backfill_days),
+        })
+    }
+
+    /// Runs created at or after `since`, newest first
+    pub fn runs_since(&self, since: DateTime<Utc>) -> Result<Vec<WorkflowRun>> {
+        self.query_runs("WHERE created_at >= ?1 ORDER BY created_at DESC", params![format_time(since)])
+    }
+
+    /// Analyses of runs created at or after `since`, newest first
+    pub fn analyses_since(&self, since: DateTime<Utc>) -> Result<Vec<WorkflowAnalysis>> {
+        self.query_analyses("WHERE created_at >= ?1 ORDER BY created_at DESC", params![format_time(since)])
+    }
+
+    /// Analyses of runs created in `[from, to)`, newest first
+    pub fn analyses_between(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<WorkflowAnalysis>> {
+        self.query_analyses(
+            "WHERE created_at >= ?1 AND created_at < ?2 ORDER BY created_at DESC",
+            params![format_time(from), format_time(to)],
+        )
+    }
+
+    /// Analyses of runs on a branch / tag, or on a commit whose SHA starts with `git_ref`
+    pub fn analyses_for_ref(&self, git_ref: &str) -> Result<Vec<WorkflowAnalysis>> {
+        // `%` and `_` in the ref are literal, not wildcards
+        let prefix = git_ref.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+        self.query_analyses(
+            "WHERE head_branch = ?1 OR (length(?1) >= 7 AND head_sha LIKE ?2 || '%' ESCAPE '\\') ORDER BY created_at DESC",
+            params![git_ref, prefix],
+        )
+    }
+
+    /// The `limit` most recent analyses
+    pub fn recent_analyses(&self, limit: usize) -> Result<Vec<WorkflowAnalysis>> {
+        self.query_analyses("ORDER BY created_at DESC LIMIT ?1", params![limit as i64])
+    }
+
+    /// One stored analysis
+    pub fn analysis(&self, run_id: u64) -> Result<Option<WorkflowAnalysis>> {
+        Ok(self.query_analyses("WHERE id = ?1", params![run_id])?.into_iter().next())
+    }
+
+    fn query_runs(&self, clause: &str, params: impl rusqlite::Params) -> Result<Vec<WorkflowRun>> {
+        let sql = format!(
+            "SELECT id, name, status, conclusion, html_url, created_at, updated_at, run_attempt, workflow_id,
+                    head_branch, head_sha, path
+             FROM runs {}",
+            clause
+        );
+        let mut stmt = self.conn.prepare(&sql)?;
+        let runs = stmt.query_map(params, run_from_row)?.collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(runs)
+    }
+
+    fn query_analyses(&self, clause: &str, params: impl rusqlite::Params) -> Result<Vec<WorkflowAnalysis>> {
+        let sql = format!(
+            "SELECT id, name, status, conclusion, html_url, created_at, updated_at, run_attempt, workflow_id,
+                    head_branch, head_sha, path, duration_seconds, cache_hits, cache_misses, errors, warnings
+             FROM runs {}",
+            clause
+        );
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt
+            .query_map(params, |row| {
+                Ok((
+                    run_from_row(row)?,
+                    row.get::<_, Option<i64>>(12)?,
+                    CacheStats {
+                        hits: row.get(13)?,
+                        misses: row.get(14)?,
+                    },
+                    row.get::<_, String>(15)?,
+                    row.get::<_, String>(16)?,
+                ))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut analyses = Vec::with_capacity(rows.len());
+        for (run, duration_seconds, cache, errors, warnings) in rows {
+            let jobs = self.jobs(run.id)?;
+            let findings = self.findings(run.id)?;
+            let timeline = RunTimeline::from_jobs(&jobs).metrics();
+
+            analyses.push(WorkflowAnalysis {
+                run,
+                jobs,
+                logs: None,
+                errors: serde_json::from_str(&errors)?,
+                warnings: serde_json::from_str(&warnings)?,
+                findings,
+                cache,
+                timeline,
+                duration_seconds,
+            });
+        }
+        Ok(analyses)
+    }
+
+    fn jobs(&self, run_id: u64) -> Result<Vec<Job>> {
+        let mut steps: HashMap<u64, Vec<Step>> = HashMap::new();
+        let mut stmt = self.conn.prepare(
+            "SELECT s.job_id, s.number, s.name, s.status, s.conclusion, s.started_at, s.completed_at
+             FROM steps s JOIN jobs j ON j.id = s.job_id
+             WHERE j.run_id = ?1 ORDER BY s.job_id, s.number",
+        )?;
+        let rows = stmt.query_map(params![run_id], |r| {
+            Ok((
+                r.get::<_, u64>(0)?,
+                Step {
+                    number: r.get(1)?,
+                    name: r.get(2)?,
+                    status: r.get(3)?,
+                    conclusion: r.get(4)?,
+                    started_at: r.get(5)?,
+                    completed_at: r.get(6)?,
+                },
+            ))
+        })?;
+        for row in rows {
+            let (job_id, step) = row?;
+            steps.entry(job_id).or_default().push(step);
+        }
+
+        let mut stmt = self.conn.prepare(
+            "SELECT id, name, status, conclusion, created_at, started_at, completed_at
+             FROM jobs WHERE run_id = ?1 ORDER BY id",
+        )?;
+        let jobs = stmt
+            .query_map(params![run_id], |r| {
+                let id: u64 = r.get(0)?;
+                Ok(Job {
+                    id,
+                    name: r.get(1)?,
+                    status: r.get(2)?,
+                    conclusion: r.get(3)?,
+                    created_at: r.get(4)?,
+                    started_at: r.get(5)?,
+                    completed_at: r.get(6)?,
+                    steps: steps.remove(&id),
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(jobs)
+    }
+
+    fn findings(&self, run_id: u64) -> Result<Vec<LogFinding>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT job, step, line, line_number, location, signature, category, suggestion
+             FROM errors WHERE run_id = ?1 ORDER BY rowid",
+        )?;
+        let findings = stmt
+            .query_map(params![run_id], |r| {
+                Ok(LogFinding {
+                    job: r.get(0)?,
+                    step: r.get(1)?,
+                    line: r.get(2)?,
+                    line_number: r.get(3)?,
+                    location: r.get(4)?,
+                    signature: r.get(5)?,
+                    category: r.get(6)?,
+                    suggestion: r.get(7)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(findings)
+    }
+}
+
+/// Fetch runs created since the store's cursor and analyze the new or changed ones
+///
+/// Returns the number of runs written.
+pub async fn sync(client: &GitHubClient, store: &mut RunStore, backfill_days: i64) -> Result<usize> {
+    let since = store.sync_cursor(backfill_days)?;
+    info!("🗄️  Syncing workflow runs since {}...", since.format("%Y-%m-%d %H:%M"));
+
+    let runs = client.get_workflow_runs_since(since, MAX_SYNC_RUNS).await?;
+
+    let mut changed = Vec::new();
+    for run in runs {
+        if !store.is_current(&run)? {
+            changed.push(run);
+        }
+    }
+
+    if changed.is_empty() {
+        info!("🗄️  Run store is up to date ({} runs)", store.run_count()?);
+        return Ok(0);
+    }
+
+    let analyses = client.analyze_runs_parallel(changed).await?;
+    for analysis in &analyses {
+        store.upsert(analysis)?;
+    }
+
+    // Earlier attempts of re-run runs, fetched in parallel (the client limits concurrency)
+    let fetches: Vec<_> = analyses
+        .iter()
+        .flat_map(|a| (1..a.run.run_attempt).map(move |attempt| (a.run.id, attempt)))
+        .map(|(run_id, attempt)| async move { (run_id, attempt, client.get_attempt_jobs(run_id, attempt).await) })
+        .collect();
+
+    for (run_id, attempt, jobs) in join_all(fetches).await {
+        match jobs {
+            Ok(jobs) => store.upsert_attempt(run_id, attempt, &jobs)?,
+            Err(e) => warn!("Failed to fetch jobs for run {} attempt {}: {}", run_id, attempt, e),
+        }
+    }
+
+    info!("🗄️  Stored {} new/updated runs ({} total)", analyses.len(), store.run_count()?);
+    Ok(analyses.len())
+}
+
+fn run_from_row(r: &Row) -> rusqlite::Result<WorkflowRun> {
+    Ok(WorkflowRun {
+        id: r.get(0)?,
+        name: r.get(1)?,
+        status: r.get(2)?,
+        conclusion: r.get(3)?,
+        html_url: r.get(4)?,
+        created_at: r.get(5)?,
+        updated_at: r.get(6)?,
+        run_attempt: r.get(7)?,
+        workflow_id: r.get(8)?,
+        head_branch: r.get(9)?,
+        head_sha: r.get(10)?,
+        path: r.get(11)?,
+    })
+}
+
+/// Same format GitHub uses, so stored timestamps compare as text
+fn format_time(t: DateTime<Utc>) -> String {
+    t.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analysis(id: u64, created_at: &str, status: &str) -> WorkflowAnalysis {
+        let job = Job {
+            id: id * 10,
+            name: "build".to_string(),
+            status: "completed".to_string(),
+            conclusion: Some("failure".to_string()),
+            created_at: Some(created_at.to_string()),
+            started_at: Some(created_at.to_string()),
+            completed_at: Some(created_at.replace("00:00Z", "05:00Z")),
+            steps: Some(vec![Step {
+                name: "Build".to_string(),
+                status: "completed".to_string(),
+                conclusion: Some("failure".to_string()),
+                number: 1,
+                started_at: Some(created_at.to_string()),
+                completed_at: Some(created_at.replace("00:00Z", "04:00Z")),
+            }]),
+        };
+
+        WorkflowAnalysis {
+            run: WorkflowRun {
+                id,
+                name: "CI".to_string(),
+                status: status.to_string(),
+                conclusion: Some("failure".to_string()),
+                html_url: format!("https://github.com/o/r/actions/runs/{}", id),
+                created_at: created_at.to_string(),
+                updated_at: created_at.to_string(),
+                run_attempt: 1,
+                workflow_id: 7,
+                head_branch: "main".to_string(),
+                head_sha: "abc".to_string(),
+                path: Some(".github/workflows/ci.yml".to_string()),
+            },
+            timeline: RunTimeline::from_jobs(std::slice::from_ref(&job)).metrics(),
+            jobs: vec![job],
+            logs: None,
+            errors: vec!["Job 'build' failed".to_string()],
+            warnings: vec![],
+            findings: vec![LogFinding {
+                job: "build".to_string(),
+                step: Some("Build".to_string()),
+                line: "error[E0308]: mismatched types".to_string(),
+                line_number: 42,
+                location: Some("src/lib.rs:10:5".to_string()),
+                signature: Some("rust-compile".to_string()),
+                category: Some("build".to_string()),
+                suggestion: None,
+            }],
+            cache: CacheStats { hits: 1, misses: 2 },
+            duration_seconds: Some(300),
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut store = RunStore::open_in_memory().unwrap();
+        let original = analysis(1, "2026-01-05T10:00:00Z", "completed");
+        store.upsert(&original).unwrap();
+        // Upserting again replaces instead of duplicating
+        store.upsert(&original).unwrap();
+
+        let loaded = store.analysis(1).unwrap().unwrap();
+
+        assert_eq!(store.run_count().unwrap(), 1);
+        assert_eq!(loaded.run.path, original.run.path);
+        assert_eq!(loaded.jobs.len(), 1);
+        assert_eq!(loaded.jobs[0].steps.as_ref().unwrap()[0].name, "Build");
+        assert_eq!(loaded.findings, original.findings);
+        assert_eq!(loaded.errors, original.errors);
+        assert_eq!(loaded.cache, original.cache);
+        assert_eq!(loaded.timeline, original.timeline);
+        assert!(store.is_current(&original.run).unwrap());
+    }
+
+    #[test]
+    fn test_attempts() {
+        let mut store = RunStore::open_in_memory().unwrap();
+        let mut rerun = analysis(1, "2026-01-05T10:00:00Z", "completed");
+        rerun.run.run_attempt = 2;
+        store.upsert(&rerun).unwrap();
+        store.upsert_attempt(1, 1, &rerun.jobs).unwrap();
+
+        let attempts = store.attempts(1).unwrap();
+        assert_eq!(attempts.len(), 1);
+        assert_eq!(attempts[0].0, 1);
+        assert_eq!(attempts[0].1[0].steps.as_ref().unwrap()[0].name, "Build");
+
+        // Re-analyzing the run drops attempts until they are fetched again
+        store.upsert(&rerun).unwrap();
+        assert!(store.attempts(1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_time_queries() {
+        let mut store = RunStore::open_in_memory().unwrap();
+        for (id, day) in [(1, 1), (2, 5), (3, 9)] {
+            store.upsert(&analysis(id, &format!("2026-01-{:02}T10:00:00Z", day), "completed")).unwrap();
+        }
+
+        let at = |day: u32| format!("2026-01-{:02}T00:00:00Z", day).parse::<DateTime<Utc>>().unwrap();
+        let ids = |analyses: Vec<WorkflowAnalysis>| analyses.iter().map(|a| a.run.id).collect::<Vec<_>>();
+
+        assert_eq!(ids(store.analyses_since(at(5)).unwrap()), [3, 2]);
+        assert_eq!(ids(store.analyses_between(at(1), at(9)).unwrap()), [2, 1]);
+        assert_eq!(ids(store.recent_analyses(1).unwrap()), [3]);
+        assert_eq!(store.runs_since(at(2)).unwrap().len(), 2);
+
+        let mut tagged = analysis(4, "2026-01-10T10:00:00Z", "completed");
+        tagged.run.head_branch = "v1.2.0".to_string();
+        tagged.run.head_sha = "4f2a9c1d0e".to_string();
+        store.upsert(&tagged).unwrap();
+
+        assert_eq!(ids(store.analyses_for_ref("v1.2.0").unwrap()), [4]);
+        assert_eq!(ids(store.analyses_for_ref("4f2a9c1").unwrap()), [4]);
+        assert_eq!(ids(store.analyses_for_ref("main").unwrap()), [3, 2, 1]);
+        // Short prefixes never match a SHA
+        assert!(store.analyses_for_ref("4f2").unwrap().is_empty());
+        // Wildcards in the ref match literally
+        assert!(store.analyses_for_ref("4f2a9c_").unwrap().is_empty());
+        assert!(store.analyses_for_ref("4f2a%%%").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_sync_cursor() {
+        let mut store = RunStore::open_in_memory().unwrap();
+        let before = Utc::now() - Duration::days(30);
+        assert!(store.sync_cursor(30).unwrap() >= before);
+
+        store.upsert(&analysis(1, "2026-01-09T10:00:00Z", "completed")).unwrap();
+        assert_eq!(store.sync_cursor(30).unwrap().to_rfc3339(), "2026-01-08T10:00:00+00:00");
+
+        // Recent runs can still be re-run, so the cursor stays within reach of them
+        let mut recent = RunStore::open_in_memory().unwrap();
+        recent.upsert(&analysis(9, &Utc::now().format("%Y-%m-%dT%H:00:00Z").to_string(), "completed")).unwrap();
+        assert!(recent.sync_cursor(30).unwrap() <= Utc::now() - Duration::days(RERUN_WINDOW_DAYS));
+
+        // An older run still in progress holds the cursor back
+        let mut running = analysis(2, "2026-01-03T10:00:00Z", "in_progress");
+        store.upsert(&running).unwrap();
+        assert_eq!(store.sync_cursor(30).unwrap().to_rfc3339(), "2026-01-03T10:00:00+00:00");
+        assert!(!store.is_current(&running.run).unwrap());
+
+        running.run.status = "completed".to_string();
+        store.upsert(&running).unwrap();
+        assert_eq!(store.sync_cursor(30).unwrap().to_rfc3339(), "2026-01-08T10:00:00+00:00");
+    }
+}
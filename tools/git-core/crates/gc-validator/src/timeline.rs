@@ -0,0 +1,379 @@
+//! # Run Timeline - Parallelism Metrics from Job Timestamps
+//!
+//! Every job of a run is an interval on a shared time axis:
+//!
+//! ```text
+//!            queued   started              completed
+//! build      ·········[=====================]
+//! lint       ··[=======]
+//! test                                      ··[==============]
+//!            └─ queue wait ─┘          idle ─┘
+//! ```
+//!
+//! From these intervals:
+//!
+//! - **duration**: earliest start → latest completion
+//! - **concurrency**: jobs running at the same time (peak and average)
+//! - **queue wait**: job created → job started (runner availability)
+//! - **idle gaps**: moments inside the run where no job was running
+//! - **critical path**: chain of jobs ending with the last one to finish,
+//!   each preceded by the latest job that completed before it started
+//!
+//! ```text
+//! parallel efficiency = critical path / wall clock (first queued → last completed)
+//! ```
+//!
+//! A run whose wall clock is all critical-path work scores 1.0; queue
+//! waits and idle gaps pull the score down.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::github::Job;
+
+/// One job as an interval
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobSpan {
+    pub name: String,
+    pub queued_at: Option<DateTime<Utc>>,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: DateTime<Utc>,
+}
+
+impl JobSpan {
+    pub fn seconds(&self) -> i64 {
+        (self.completed_at - self.started_at).num_seconds().max(0)
+    }
+
+    pub fn queue_wait_seconds(&self) -> Option<i64> {
+        self.queued_at
+            .map(|q| (self.started_at - q).num_seconds().max(0))
+    }
+
+    fn overlaps(&self, other: &JobSpan) -> bool {
+        self.started_at < other.completed_at && other.started_at < self.completed_at
+    }
+}
+
+/// Stretch of a run with no job running
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IdleGap {
+    /// Job whose completion opened the gap
+    pub after: String,
+    /// Job whose start closed the gap
+    pub before: String,
+    pub seconds: i64,
+}
+
+/// Wall time of one step
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StepTiming {
+    pub job: String,
+    pub step: String,
+    pub seconds: i64,
+}
+
+/// Parallelism metrics of one run
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct TimelineMetrics {
+    /// Earliest job start → latest job completion
+    pub duration_seconds: i64,
+    /// Earliest job queued → latest job completion
+    pub wall_clock_seconds: i64,
+    /// Sum of job durations
+    pub busy_seconds: i64,
+    pub critical_path: Vec<String>,
+    pub critical_path_seconds: i64,
+    pub max_concurrency: usize,
+    /// Average jobs running while at least one was running
+    pub avg_concurrency: f64,
+    /// Jobs that overlapped at least one other job
+    pub parallel_jobs: usize,
+    pub sequential_jobs: usize,
+    pub total_queue_wait_seconds: i64,
+    pub max_queue_wait_seconds: i64,
+    /// Jobs with both a queued and a start time
+    pub queued_jobs: usize,
+    pub idle_gaps: Vec<IdleGap>,
+    /// Slowest first
+    pub steps: Vec<StepTiming>,
+    pub parallel_efficiency: f64,
+}
+
+/// Jobs of one run laid out on a time axis
+#[derive(Debug, Clone, Default)]
+pub struct RunTimeline {
+    jobs: Vec<JobSpan>,
+    steps: Vec<StepTiming>,
+}
+
+impl RunTimeline {
+    /// Build from API jobs, skipping jobs that never ran
+    pub fn from_jobs(jobs: &[Job]) -> Self {
+        let mut timeline = Self::default();
+
+        for job in jobs {
+            if let (Some(started_at), Some(completed_at)) = (parse(&job.started_at), parse(&job.completed_at)) {
+                timeline.jobs.push(JobSpan {
+                    name: job.name.clone(),
+                    queued_at: parse(&job.created_at),
+                    started_at,
+                    completed_at,
+                });
+            }
+
+            for step in job.steps.iter().flatten() {
+                if let (Some(s), Some(e)) = (parse(&step.started_at), parse(&step.completed_at)) {
+                    timeline.steps.push(StepTiming {
+                        job: job.name.clone(),
+                        step: step.name.clone(),
+                        seconds: (e - s).num_seconds().max(0),
+                    });
+                }
+            }
+        }
+
+        timeline.jobs.sort_by_key(|j| (j.started_at, j.completed_at));
+        timeline.steps.sort_by_key(|s| std::cmp::Reverse(s.seconds));
+        timeline
+    }
+
+    /// Earliest start → latest completion
+    pub fn duration_seconds(&self) -> Option<i64> {
+        let start = self.jobs.iter().map(|j| j.started_at).min()?;
+        let end = self.jobs.iter().map(|j| j.completed_at).max()?;
+        Some((end - start).num_seconds().max(0))
+    }
+
+    /// Chain of jobs ending with the last one to finish (first job first)
+    pub fn critical_path(&self) -> Vec<&JobSpan> {
+        let Some(mut current) = self.jobs.iter().max_by_key(|j| (j.completed_at, j.started_at)) else {
+            return Vec::new();
+        };
+
+        let mut path = vec![current];
+        while let Some(previous) = self
+            .jobs
+            .iter()
+            .filter(|j| j.completed_at <= current.started_at && !path.contains(j))
+            .max_by_key(|j| j.completed_at)
+        {
+            path.push(previous);
+            current = previous;
+        }
+
+        path.reverse();
+        path
+    }
+
+    pub fn metrics(&self) -> TimelineMetrics {
+        let Some(duration_seconds) = self.duration_seconds() else {
+            return TimelineMetrics {
+                parallel_efficiency: 1.0,
+                steps: self.steps.clone(),
+                ..Default::default()
+            };
+        };
+
+        let first_start = self.jobs.iter().map(|j| j.started_at).min().expect("non-empty timeline");
+        let last_end = self.jobs.iter().map(|j| j.completed_at).max().expect("non-empty timeline");
+        let first_queued = self
+            .jobs
+            .iter()
+            .filter_map(|j| j.queued_at)
+            .min()
+            .map_or(first_start, |q| q.min(first_start));
+        let wall_clock_seconds = (last_end - first_queued).num_seconds().max(0);
+
+        let critical_path = self.critical_path();
+        let critical_path_seconds: i64 = critical_path.iter().map(|j| j.seconds()).sum();
+
+        let (max_concurrency, covered_seconds, idle_gaps) = self.sweep();
+        let busy_seconds: i64 = self.jobs.iter().map(JobSpan::seconds).sum();
+
+        let parallel_jobs = self
+            .jobs
+            .iter()
+            .enumerate()
+            .filter(|(i, job)| {
+                self.jobs
+                    .iter()
+                    .enumerate()
+                    .any(|(k, other)| k != *i && job.overlaps(other))
+            })
+            .count();
+
+        let queue_waits: Vec<i64> = self.jobs.iter().filter_map(JobSpan::queue_wait_seconds).collect();
+
+        TimelineMetrics {
+            duration_seconds,
+            wall_clock_seconds,
+            busy_seconds,
+            critical_path: critical_path.iter().map(|j| j.name.clone()).collect(),
+            critical_path_seconds,
+            max_concurrency,
+            avg_concurrency: if covered_seconds > 0 {
+                busy_seconds as f64 / covered_seconds as f64
+            } else {
+                max_concurrency as f64
+            },
+            parallel_jobs,
+            sequential_jobs: self.jobs.len() - parallel_jobs,
+            total_queue_wait_seconds: queue_waits.iter().sum(),
+            max_queue_wait_seconds: queue_waits.iter().copied().max().unwrap_or(0),
+            queued_jobs: queue_waits.len(),
+            idle_gaps,
+            steps: self.steps.clone(),
+            parallel_efficiency: if wall_clock_seconds > 0 {
+                (critical_path_seconds as f64 / wall_clock_seconds as f64).min(1.0)
+            } else {
+                1.0
+            },
+        }
+    }
+
+    /// Peak concurrency, seconds with at least one job running, and idle gaps
+    fn sweep(&self) -> (usize, i64, Vec<IdleGap>) {
+        // Completions sort before starts at the same instant: back-to-back is not overlap
+        let mut events: Vec<(DateTime<Utc>, bool, &str)> = self
+            .jobs
+            .iter()
+            .flat_map(|j| [(j.started_at, true, j.name.as_str()), (j.completed_at, false, j.name.as_str())])
+            .collect();
+        events.sort_by_key(|&(at, is_start, _)| (at, is_start));
+
+        let mut running = 0usize;
+        let mut peak = 0;
+        let mut covered = 0;
+        let mut busy_since = None;
+        let mut idle_since: Option<(DateTime<Utc>, &str)> = None;
+        let mut gaps = Vec::new();
+
+        for (at, is_start, name) in events {
+            if is_start {
+                if running == 0 {
+                    if let Some((since, after)) = idle_since.take() {
+                        let seconds = (at - since).num_seconds();
+                        if seconds > 0 {
+                            gaps.push(IdleGap {
+                                after: after.to_string(),
+                                before: name.to_string(),
+                                seconds,
+                            });
+                        }
+                    }
+                    busy_since = Some(at);
+                }
+                running += 1;
+                peak = peak.max(running);
+            } else {
+                running -= 1;
+                if running == 0 {
+                    if let Some(since) = busy_since.take() {
+                        covered += (at - since).num_seconds();
+                    }
+                    idle_since = Some((at, name));
+                }
+            }
+        }
+
+        (peak, covered, gaps)
+    }
+}
+
+fn parse(timestamp: &Option<String>) -> Option<DateTime<Utc>> {
+    timestamp.as_deref().and_then(|t| t.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::Step;
+
+    fn t(seconds: u32) -> Option<String> {
+        Some(format!("2026-01-05T10:{:02}:{:02}Z", seconds / 60, seconds % 60))
+    }
+
+    fn job(name: &str, queued: u32, start: u32, end: u32) -> Job {
+        Job {
+            id: 1,
+            name: name.to_string(),
+            status: "completed".to_string(),
+            conclusion: Some("success".to_string()),
+            created_at: t(queued),
+            started_at: t(start),
+            completed_at: t(end),
+            steps: None,
+        }
+    }
+
+    #[test]
+    fn test_critical_path_and_concurrency() {
+        // lint and build in parallel, test needs build, deploy needs test
+        let jobs = vec![
+            job("deploy", 300, 330, 400),
+            job("lint", 0, 10, 60),
+            job("build", 0, 10, 200),
+            job("test", 200, 210, 300),
+        ];
+
+        let timeline = RunTimeline::from_jobs(&jobs);
+        let m = timeline.metrics();
+
+        assert_eq!(m.duration_seconds, 390);
+        assert_eq!(m.wall_clock_seconds, 400);
+        assert_eq!(m.critical_path, ["build", "test", "deploy"]);
+        assert_eq!(m.critical_path_seconds, 190 + 90 + 70);
+        assert_eq!(m.max_concurrency, 2);
+        assert_eq!(m.parallel_jobs, 2);
+        assert_eq!(m.sequential_jobs, 2);
+        assert_eq!(m.total_queue_wait_seconds, 10 + 10 + 10 + 30);
+        assert_eq!(m.max_queue_wait_seconds, 30);
+        assert_eq!(m.queued_jobs, 4);
+        assert!((m.parallel_efficiency - 350.0 / 400.0).abs() < 1e-9);
+
+        // Waiting for runners shows up as idle time between the jobs
+        assert_eq!(m.idle_gaps.len(), 2);
+        assert_eq!(m.idle_gaps[1], IdleGap { after: "test".to_string(), before: "deploy".to_string(), seconds: 30 });
+    }
+
+    #[test]
+    fn test_unordered_jobs_use_min_and_max() {
+        // API order is not chronological
+        let jobs = vec![job("late", 0, 100, 150), job("early", 0, 0, 120)];
+
+        assert_eq!(RunTimeline::from_jobs(&jobs).duration_seconds(), Some(150));
+    }
+
+    #[test]
+    fn test_avg_concurrency_and_steps() {
+        let mut a = job("a", 0, 0, 100);
+        a.steps = Some(vec![Step {
+            name: "Run tests".to_string(),
+            status: "completed".to_string(),
+            conclusion: Some("success".to_string()),
+            number: 1,
+            started_at: t(5),
+            completed_at: t(95),
+        }]);
+        let jobs = vec![a, job("b", 0, 0, 100), job("skipped", 0, 0, 0)];
+        let mut jobs = jobs;
+        jobs[2].started_at = None;
+
+        let m = RunTimeline::from_jobs(&jobs).metrics();
+
+        assert_eq!(m.max_concurrency, 2);
+        assert_eq!(m.avg_concurrency, 2.0);
+        assert!(m.idle_gaps.is_empty());
+        assert_eq!(m.steps[0], StepTiming { job: "a".to_string(), step: "Run tests".to_string(), seconds: 90 });
+    }
+
+    #[test]
+    fn test_empty_timeline() {
+        let m = RunTimeline::from_jobs(&[]).metrics();
+
+        assert_eq!(m.duration_seconds, 0);
+        assert_eq!(m.parallel_efficiency, 1.0);
+        assert!(m.critical_path.is_empty());
+    }
+}
@@ -0,0 +1,637 @@
+//! # Trends - Comparing Two Slices of Run History
+//!
+//! `report --compare CURRENT..BASELINE` compares two selections of stored runs:
+//!
+//! ```text
+//! last-7d..prev-7d     this week vs the week before
+//! last-30d..prev-30d   this month vs the month before
+//! v1.3.0..v1.2.0       runs on a tag / branch (head_branch)
+//! 4f2a9c1..9b8e7d6     runs on a commit (SHA prefix, 7+ hex chars)
+//! ```
+//!
+//! For every workflow the report shows success rate and p50/p95 duration
+//! on both sides, and lists jobs that started failing, jobs that were fixed
+//! and error signatures that did not occur in the baseline. The markdown
+//! form adds daily sparklines (`▁▂▃▄▅▆▇█`) for a weekly evolution issue.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
+
+use crate::github::WorkflowAnalysis;
+
+const SPARK_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Maximum sparkline width (days are merged beyond this)
+const MAX_SPARK_WIDTH: usize = 28;
+
+/// One side of a comparison
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Selector {
+    /// The last N days
+    Last(i64),
+    /// The N days before the last N days
+    Previous(i64),
+    /// Runs on a branch / tag, or on a commit when it looks like a SHA
+    Ref(String),
+}
+
+impl FromStr for Selector {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let days = |rest: &str| -> Result<i64> {
+            let n: i64 = rest
+                .strip_suffix('d')
+                .and_then(|n| n.parse().ok())
+                .with_context(|| format!("Invalid window '{}' (expected e.g. last-7d)", s))?;
+            if n <= 0 {
+                bail!("Window must be at least one day: {}", s);
+            }
+            Ok(n)
+        };
+
+        if let Some(rest) = s.strip_prefix("last-") {
+            Ok(Selector::Last(days(rest)?))
+        } else if let Some(rest) = s.strip_prefix("prev-") {
+            Ok(Selector::Previous(days(rest)?))
+        } else if s.is_empty() {
+            bail!("Empty selector")
+        } else {
+            Ok(Selector::Ref(s.to_string()))
+        }
+    }
+}
+
+impl Selector {
+    /// Time window `[from, to)` for window selectors
+    pub fn window(&self, now: DateTime<Utc>) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        match self {
+            Selector::Last(n) => Some((now - Duration::days(*n), now)),
+            Selector::Previous(n) => Some((now - Duration::days(2 * n), now - Duration::days(*n))),
+            Selector::Ref(_) => None,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Selector::Last(n) => format!("last {}d", n),
+            Selector::Previous(n) => format!("previous {}d", n),
+            Selector::Ref(r) => r.clone(),
+        }
+    }
+}
+
+/// Parse `CURRENT..BASELINE`
+pub fn parse_compare(spec: &str) -> Result<(Selector, Selector)> {
+    let (current, baseline) = spec
+        .split_once("..")
+        .with_context(|| format!("Invalid comparison '{}' (expected CURRENT..BASELINE)", spec))?;
+    Ok((current.parse()?, baseline.parse()?))
+}
+
+/// Success rate and duration percentiles of a set of runs
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct WindowStats {
+    pub runs: usize,
+    /// Share of successful runs among runs that succeeded or failed
+    pub success_rate: Option<f64>,
+    pub p50_seconds: Option<i64>,
+    pub p95_seconds: Option<i64>,
+}
+
+impl WindowStats {
+    pub fn from_runs<'a>(analyses: impl IntoIterator<Item = &'a WorkflowAnalysis>) -> Self {
+        let analyses: Vec<&WorkflowAnalysis> = analyses.into_iter().collect();
+
+        let decided: Vec<bool> = analyses
+            .iter()
+            .filter_map(|a| match a.run.conclusion.as_deref() {
+                Some("success") => Some(true),
+                Some("failure") | Some("timed_out") => Some(false),
+                _ => None,
+            })
+            .collect();
+
+        let mut durations: Vec<i64> = analyses.iter().filter_map(|a| a.duration_seconds).collect();
+        durations.sort_unstable();
+
+        Self {
+            runs: analyses.len(),
+            success_rate: (!decided.is_empty())
+                .then(|| decided.iter().filter(|&&ok| ok).count() as f64 / decided.len() as f64),
+            p50_seconds: percentile(&durations, 50.0),
+            p95_seconds: percentile(&durations, 95.0),
+        }
+    }
+}
+
+/// Per-workflow comparison
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WorkflowDelta {
+    pub workflow: String,
+    pub current: WindowStats,
+    pub baseline: WindowStats,
+    /// Daily success rate over both selections
+    pub success_sparkline: String,
+    /// Daily p50 duration over both selections
+    pub duration_sparkline: String,
+}
+
+/// A job that changed state between the selections
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JobChange {
+    pub workflow: String,
+    pub job: String,
+    /// Failures in the selection where the job is failing
+    pub failures: usize,
+}
+
+/// An error signature only seen in the current selection
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NewSignature {
+    pub signature: String,
+    pub occurrences: usize,
+    pub example: String,
+}
+
+/// Result of comparing two selections
+#[derive(Debug, Clone, Serialize)]
+pub struct TrendReport {
+    pub current_label: String,
+    pub baseline_label: String,
+    pub current: WindowStats,
+    pub baseline: WindowStats,
+    pub workflows: Vec<WorkflowDelta>,
+    /// Failing in the current selection, never failed in the baseline
+    pub newly_failing: Vec<JobChange>,
+    /// Failed in the baseline, only passing in the current selection
+    pub fixed: Vec<JobChange>,
+    pub new_signatures: Vec<NewSignature>,
+}
+
+/// Compare two selections of analyzed runs
+pub fn compare(
+    current: &[WorkflowAnalysis],
+    baseline: &[WorkflowAnalysis],
+    current_label: &str,
+    baseline_label: &str,
+) -> TrendReport {
+    let workflows: BTreeSet<&str> = current
+        .iter()
+        .chain(baseline)
+        .map(|a| a.run.name.as_str())
+        .collect();
+
+    let days = day_buckets(current.iter().chain(baseline));
+
+    let workflows = workflows
+        .into_iter()
+        .map(|name| {
+            let both: Vec<&WorkflowAnalysis> = of(baseline, name).chain(of(current, name)).collect();
+
+            let daily: Vec<WindowStats> = days
+                .iter()
+                .map(|bucket| WindowStats::from_runs(both.iter().copied().filter(|a| in_bucket(a, bucket))))
+                .collect();
+
+            WorkflowDelta {
+                workflow: name.to_string(),
+                current: WindowStats::from_runs(of(current, name)),
+                baseline: WindowStats::from_runs(of(baseline, name)),
+                success_sparkline: sparkline(&daily.iter().map(|s| s.success_rate).collect::<Vec<_>>()),
+                duration_sparkline: sparkline(
+                    &daily.iter().map(|s| s.p50_seconds.map(|d| d as f64)).collect::<Vec<_>>(),
+                ),
+            }
+        })
+        .collect();
+
+    let current_jobs = job_outcomes(current);
+    let baseline_jobs = job_outcomes(baseline);
+
+    let newly_failing = current_jobs
+        .iter()
+        .filter(|(key, (failures, _))| *failures > 0 && baseline_jobs.get(*key).is_none_or(|(f, _)| *f == 0))
+        .map(|((workflow, job), (failures, _))| JobChange {
+            workflow: workflow.clone(),
+            job: job.clone(),
+            failures: *failures,
+        })
+        .collect();
+
+    let fixed = baseline_jobs
+        .iter()
+        .filter(|(key, (failures, _))| {
+            *failures > 0 && current_jobs.get(*key).is_some_and(|(f, passes)| *f == 0 && *passes > 0)
+        })
+        .map(|((workflow, job), (failures, _))| JobChange {
+            workflow: workflow.clone(),
+            job: job.clone(),
+            failures: *failures,
+        })
+        .collect();
+
+    let baseline_signatures: BTreeSet<String> = baseline
+        .iter()
+        .flat_map(|a| a.findings.iter())
+        .map(|f| f.signature.clone().unwrap_or_else(|| f.line.clone()))
+        .collect();
+
+    let mut new_signatures: BTreeMap<String, NewSignature> = BTreeMap::new();
+    for finding in current.iter().flat_map(|a| a.findings.iter()) {
+        let signature = finding.signature.clone().unwrap_or_else(|| finding.line.clone());
+        if baseline_signatures.contains(&signature) {
+            continue;
+        }
+        new_signatures
+            .entry(signature.clone())
+            .or_insert_with(|| NewSignature {
+                signature,
+                occurrences: 0,
+                example: finding.line.clone(),
+            })
+            .occurrences += 1;
+    }
+    let mut new_signatures: Vec<NewSignature> = new_signatures.into_values().collect();
+    new_signatures.sort_by(|a, b| b.occurrences.cmp(&a.occurrences).then_with(|| a.signature.cmp(&b.signature)));
+
+    TrendReport {
+        current_label: current_label.to_string(),
+        baseline_label: baseline_label.to_string(),
+        current: WindowStats::from_runs(current),
+        baseline: WindowStats::from_runs(baseline),
+        workflows,
+        newly_failing,
+        fixed,
+        new_signatures,
+    }
+}
+
+impl TrendReport {
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::new();
+
+        md.push_str(&format!("# 📈 Workflow Trends: {} vs {}\n\n", self.current_label, self.baseline_label));
+
+        md.push_str("| Metric | Baseline | Current | Δ |\n");
+        md.push_str("|--------|----------|---------|---|\n");
+        md.push_str(&format!("| Runs | {} | {} | {:+} |\n",
+            self.baseline.runs, self.current.runs, self.current.runs as i64 - self.baseline.runs as i64));
+        md.push_str(&stats_rows(&self.baseline, &self.current));
+        md.push('\n');
+
+        if !self.workflows.is_empty() {
+            md.push_str("## Workflows\n\n");
+            md.push_str("| Workflow | Success | p50 | p95 | Daily success | Daily p50 |\n");
+            md.push_str("|----------|---------|-----|-----|---------------|-----------|\n");
+            for w in &self.workflows {
+                md.push_str(&format!(
+                    "| {} | {} | {} | {} | `{}` | `{}` |\n",
+                    w.workflow,
+                    rate_change(w.baseline.success_rate, w.current.success_rate),
+                    duration_change(w.baseline.p50_seconds, w.current.p50_seconds),
+                    duration_change(w.baseline.p95_seconds, w.current.p95_seconds),
+                    w.success_sparkline,
+                    w.duration_sparkline
+                ));
+            }
+            md.push('\n');
+        }
+
+        if !self.newly_failing.is_empty() {
+            md.push_str("## 🔴 Newly Failing Jobs\n\n");
+            for j in &self.newly_failing {
+                md.push_str(&format!("- **{}** / {} ({} failure(s))\n", j.workflow, j.job, j.failures));
+            }
+            md.push('\n');
+        }
+
+        if !self.fixed.is_empty() {
+            md.push_str("## 🟢 Fixed Jobs\n\n");
+            for j in &self.fixed {
+                md.push_str(&format!("- **{}** / {} (failed {} time(s) before)\n", j.workflow, j.job, j.failures));
+            }
+            md.push('\n');
+        }
+
+        if !self.new_signatures.is_empty() {
+            md.push_str("## 🆕 New Error Signatures\n\n");
+            for s in &self.new_signatures {
+                md.push_str(&format!("- **{}** × {}: `{}`\n", s.signature, s.occurrences, s.example.replace('`', "'")));
+            }
+            md.push('\n');
+        }
+
+        md
+    }
+
+    pub fn to_terminal(&self) -> String {
+        let mut out = String::new();
+        out.push_str(&format!("\n📈 Workflow Trends: {} vs {}\n", self.current_label, self.baseline_label));
+        out.push_str(&format!("   Runs: {} → {}\n", self.baseline.runs, self.current.runs));
+        out.push_str(&format!("   Success: {}\n", rate_change(self.baseline.success_rate, self.current.success_rate)));
+        out.push_str(&format!("   p50: {} | p95: {}\n",
+            duration_change(self.baseline.p50_seconds, self.current.p50_seconds),
+            duration_change(self.baseline.p95_seconds, self.current.p95_seconds)));
+
+        for w in &self.workflows {
+            out.push_str(&format!(
+                "   {:<32} {:<24} {}\n",
+                w.workflow.chars().take(32).collect::<String>(),
+                rate_change(w.baseline.success_rate, w.current.success_rate),
+                w.success_sparkline
+            ));
+        }
+
+        for j in &self.newly_failing {
+            out.push_str(&format!("   🔴 newly failing: {} / {}\n", j.workflow, j.job));
+        }
+        for j in &self.fixed {
+            out.push_str(&format!("   🟢 fixed: {} / {}\n", j.workflow, j.job));
+        }
+        for s in &self.new_signatures {
+            out.push_str(&format!("   🆕 {} × {}\n", s.signature, s.occurrences));
+        }
+        out
+    }
+}
+
+fn stats_rows(baseline: &WindowStats, current: &WindowStats) -> String {
+    format!(
+        "| Success Rate | {} | {} | {} |\n| p50 Duration | {} | {} | {} |\n| p95 Duration | {} | {} | {} |\n",
+        fmt_rate(baseline.success_rate),
+        fmt_rate(current.success_rate),
+        delta_rate(baseline.success_rate, current.success_rate),
+        fmt_duration(baseline.p50_seconds),
+        fmt_duration(current.p50_seconds),
+        delta_duration(baseline.p50_seconds, current.p50_seconds),
+        fmt_duration(baseline.p95_seconds),
+        fmt_duration(current.p95_seconds),
+        delta_duration(baseline.p95_seconds, current.p95_seconds),
+    )
+}
+
+fn fmt_rate(rate: Option<f64>) -> String {
+    rate.map_or_else(|| "–".to_string(), |r| format!("{:.1}%", r * 100.0))
+}
+
+fn fmt_duration(seconds: Option<i64>) -> String {
+    seconds.map_or_else(|| "–".to_string(), |s| format!("{}s", s))
+}
+
+fn delta_rate(before: Option<f64>, after: Option<f64>) -> String {
+    match (before, after) {
+        (Some(b), Some(a)) => {
+            let icon = if a > b { "📈" } else if a < b { "📉" } else { "➡️" };
+            format!("{:+.1}pp {}", (a - b) * 100.0, icon)
+        }
+        _ => "–".to_string(),
+    }
+}
+
+fn delta_duration(before: Option<i64>, after: Option<i64>) -> String {
+    match (before, after) {
+        (Some(b), Some(a)) => {
+            let icon = if a < b { "📈" } else if a > b { "📉" } else { "➡️" };
+            format!("{:+}s {}", a - b, icon)
+        }
+        _ => "–".to_string(),
+    }
+}
+
+fn rate_change(before: Option<f64>, after: Option<f64>) -> String {
+    format!("{} → {} ({})", fmt_rate(before), fmt_rate(after), delta_rate(before, after))
+}
+
+fn duration_change(before: Option<i64>, after: Option<i64>) -> String {
+    format!("{} → {}", fmt_duration(before), fmt_duration(after))
+}
+
+/// Runs of one workflow
+fn of<'a>(analyses: &'a [WorkflowAnalysis], workflow: &'a str) -> impl Iterator<Item = &'a WorkflowAnalysis> {
+    analyses.iter().filter(move |a| a.run.name == workflow)
+}
+
+/// (failures, passes) per (workflow, job)
+fn job_outcomes(analyses: &[WorkflowAnalysis]) -> BTreeMap<(String, String), (usize, usize)> {
+    let mut outcomes: BTreeMap<(String, String), (usize, usize)> = BTreeMap::new();
+    for analysis in analyses {
+        for job in &analysis.jobs {
+            let entry = outcomes
+                .entry((analysis.run.name.clone(), job.name.clone()))
+                .or_default();
+            match job.conclusion.as_deref() {
+                Some("failure") | Some("timed_out") => entry.0 += 1,
+                Some("success") => entry.1 += 1,
+                _ => {}
+            }
+        }
+    }
+    outcomes
+}
+
+/// Nearest-rank percentile of sorted values
+pub fn percentile(sorted: &[i64], p: f64) -> Option<i64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+/// Sparkline scaled to the min/max of the values; gaps render as spaces
+pub fn sparkline(values: &[Option<f64>]) -> String {
+    let present: Vec<f64> = values.iter().flatten().copied().collect();
+    let Some(min) = present.iter().copied().reduce(f64::min) else {
+        return " ".repeat(values.len());
+    };
+    let max = present.iter().copied().fold(min, f64::max);
+
+    values
+        .iter()
+        .map(|v| match v {
+            None => ' ',
+            Some(_) if max == min => SPARK_CHARS[SPARK_CHARS.len() / 2],
+            Some(v) => {
+                let level = ((v - min) / (max - min) * (SPARK_CHARS.len() - 1) as f64).round() as usize;
+                SPARK_CHARS[level.min(SPARK_CHARS.len() - 1)]
+            }
+        })
+        .collect()
+}
+
+/// Consecutive day ranges `[start, end]` covering the runs (at most MAX_SPARK_WIDTH)
+fn day_buckets<'a>(analyses: impl Iterator<Item = &'a WorkflowAnalysis>) -> Vec<(NaiveDate, NaiveDate)> {
+    let days: Vec<NaiveDate> = analyses.filter_map(run_day).collect();
+    let (Some(&first), Some(&last)) = (days.iter().min(), days.iter().max()) else {
+        return Vec::new();
+    };
+
+    let span = (last - first).num_days() as usize + 1;
+    let width = span.div_ceil(MAX_SPARK_WIDTH);
+
+    (0..span.div_ceil(width))
+        .map(|i| {
+            let start = first + Duration::days((i * width) as i64);
+            let end = (start + Duration::days(width as i64 - 1)).min(last);
+            (start, end)
+        })
+        .collect()
+}
+
+fn in_bucket(analysis: &WorkflowAnalysis, (start, end): &(NaiveDate, NaiveDate)) -> bool {
+    run_day(analysis).is_some_and(|day| *start <= day && day <= *end)
+}
+
+fn run_day(analysis: &WorkflowAnalysis) -> Option<NaiveDate> {
+    analysis
+        .run
+        .created_at
+        .parse::<DateTime<Utc>>()
+        .ok()
+        .map(|t| t.date_naive())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::{Job, WorkflowRun};
+    use crate::log_analysis::{CacheStats, LogFinding};
+
+    fn run(workflow: &str, day: u32, conclusion: &str, seconds: i64, failing_job: Option<&str>) -> WorkflowAnalysis {
+        let job = |name: &str, conclusion: &str| Job {
+            id: 1,
+            name: name.to_string(),
+            status: "completed".to_string(),
+            conclusion: Some(conclusion.to_string()),
+            created_at: None,
+            started_at: None,
+            completed_at: None,
+            steps: None,
+        };
+
+        let mut jobs = vec![job("build", "success"), job("test", "success")];
+        let mut findings = Vec::new();
+        if let Some(name) = failing_job {
+            jobs.retain(|j| j.name != name);
+            jobs.push(job(name, "failure"));
+            findings.push(LogFinding {
+                job: name.to_string(),
+                step: None,
+                line: format!("{} broke", name),
+                line_number: 1,
+                location: None,
+                signature: Some(format!("{}-error", name)),
+                category: None,
+                suggestion: None,
+            });
+        }
+
+        WorkflowAnalysis {
+            run: WorkflowRun {
+                id: day as u64,
+                name: workflow.to_string(),
+                status: "completed".to_string(),
+                conclusion: Some(conclusion.to_string()),
+                html_url: String::new(),
+                created_at: format!("2026-01-{:02}T10:00:00Z", day),
+                updated_at: String::new(),
+                run_attempt: 1,
+                workflow_id: 1,
+                head_branch: "main".to_string(),
+                head_sha: "abc".to_string(),
+                path: None,
+            },
+            jobs,
+            logs: None,
+            errors: vec![],
+            warnings: vec![],
+            findings,
+            cache: CacheStats::default(),
+            timeline: Default::default(),
+            duration_seconds: Some(seconds),
+        }
+    }
+
+    #[test]
+    fn test_parse_compare() {
+        assert_eq!(parse_compare("last-7d..prev-7d").unwrap(), (Selector::Last(7), Selector::Previous(7)));
+        assert_eq!(
+            parse_compare("v1.3.0..4f2a9c1").unwrap(),
+            (Selector::Ref("v1.3.0".to_string()), Selector::Ref("4f2a9c1".to_string()))
+        );
+        assert!(parse_compare("last-7d").is_err());
+        assert!(parse_compare("last-xd..prev-7d").is_err());
+        assert!(parse_compare("last-0d..prev-7d").is_err());
+
+        let now: DateTime<Utc> = "2026-01-15T00:00:00Z".parse().unwrap();
+        let (from, to) = Selector::Previous(7).window(now).unwrap();
+        assert_eq!(from.to_rfc3339(), "2026-01-01T00:00:00+00:00");
+        assert_eq!(to.to_rfc3339(), "2026-01-08T00:00:00+00:00");
+    }
+
+    #[test]
+    fn test_percentile_and_sparkline() {
+        let values = [10, 20, 30, 40, 50, 60, 70, 80, 90, 100];
+        assert_eq!(percentile(&values, 50.0), Some(50));
+        assert_eq!(percentile(&values, 95.0), Some(100));
+        assert_eq!(percentile(&[], 50.0), None);
+
+        assert_eq!(sparkline(&[Some(0.0), Some(0.5), None, Some(1.0)]), "▁▅ █");
+        assert_eq!(sparkline(&[Some(3.0), Some(3.0)]), "▅▅");
+        assert_eq!(sparkline(&[None, None]), "  ");
+    }
+
+    #[test]
+    fn test_compare_windows() {
+        let baseline = vec![
+            run("CI", 1, "failure", 100, Some("test")),
+            run("CI", 2, "success", 120, None),
+            run("Docs", 3, "success", 30, None),
+        ];
+        let current = vec![
+            run("CI", 8, "success", 90, None),
+            run("CI", 9, "failure", 200, Some("build")),
+            run("CI", 10, "success", 110, None),
+        ];
+
+        let report = compare(&current, &baseline, "last 7d", "previous 7d");
+
+        let ci = &report.workflows[0];
+        assert_eq!(ci.workflow, "CI");
+        assert_eq!(ci.baseline.success_rate, Some(0.5));
+        assert!((ci.current.success_rate.unwrap() - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(ci.current.p50_seconds, Some(110));
+        assert_eq!(ci.current.p95_seconds, Some(200));
+        // Days 1..=10, no CI runs on days 3-7
+        assert_eq!(ci.success_sparkline.chars().count(), 10);
+        assert_eq!(ci.success_sparkline, "▁█     █▁█");
+
+        assert_eq!(report.workflows[1].current.runs, 0);
+
+        assert_eq!(report.newly_failing, [JobChange { workflow: "CI".into(), job: "build".into(), failures: 1 }]);
+        assert_eq!(report.fixed, [JobChange { workflow: "CI".into(), job: "test".into(), failures: 1 }]);
+        assert_eq!(report.new_signatures.len(), 1);
+        assert_eq!(report.new_signatures[0].signature, "build-error");
+
+        let md = report.to_markdown();
+        assert!(md.contains("| CI | 50.0% → 66.7% (+16.7pp 📈)"));
+        assert!(md.contains("## 🔴 Newly Failing Jobs"));
+    }
+
+    #[test]
+    fn test_terminal_truncates_by_character() {
+        let name = "🚀 Release — build, sign and publish every artifact";
+        let current = vec![run(name, 1, "success", 60, None)];
+        let report = compare(&current, &[], "last 7d", "previous 7d");
+
+        let out = report.to_terminal();
+        let truncated: String = name.chars().take(32).collect();
+        assert!(out.contains(&truncated));
+        assert!(!out.contains(name));
+    }
+}
@@ -1,50 +1,91 @@
 //! Post-run validation and continuous improvement system
 
 use crate::github::{GitHubClient, WorkflowAnalysis};
+use crate::review_bots::{self, Presence, ReviewerRegistry};
+use crate::run_store::RunStore;
+use crate::workflow_security::{self, SecurityAudit, Severity};
 use anyhow::Result;
 use chrono::Utc;
+use std::collections::{HashMap, HashSet};
 use tracing::{info, warn};
 
+/// Directory validation reports are committed to in PR mode
+pub const REPORTS_DIR: &str = "docs/agent-docs/reports";
+
+/// Label on validation tracking issues
+pub const VALIDATION_LABEL: &str = "workflow-validation";
+
+/// Where validation results are published
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Publish {
+    /// Print only
+    None,
+    /// Commit the report under `REPORTS_DIR` on a new branch and open a PR
+    Pr,
+    /// Open or update one tracking issue per workflow
+    Issue,
+}
+
+impl std::str::FromStr for Publish {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "none" | "off" => Ok(Publish::None),
+            "pr" => Ok(Publish::Pr),
+            "issue" => Ok(Publish::Issue),
+            _ => Err(anyhow::anyhow!("Invalid publish target: {} (expected none, pr or issue)", s)),
+        }
+    }
+}
+
+/// Where and how validation results are published
+#[derive(Debug, Clone, Copy)]
+pub struct PublishOptions<'a> {
+    pub target: Publish,
+    /// PR base branch (default: the repository's default branch)
+    pub base: Option<&'a str>,
+    /// Reviewer bots asked to review validation PRs
+    pub reviewers: Option<&'a ReviewerRegistry>,
+}
+
 /// Run validation on completed workflows
 pub async fn run_validation(
     client: &GitHubClient,
+    store: &RunStore,
     run_id: &str,
     last_hours: Option<u64>,
-    create_pr: bool,
+    publish: PublishOptions<'_>,
     output_format: &str,
 ) -> Result<()> {
     info!("🔍 Starting workflow validation...");
 
-    let runs = client.get_workflow_runs(50).await?;
-
-    let runs_to_validate: Vec<_> = if run_id == "latest" {
+    // Runs come pre-analyzed from the local store
+    let analyses = if run_id == "latest" {
         if let Some(hours) = last_hours {
-            let cutoff = Utc::now() - chrono::Duration::hours(hours as i64);
-            runs.into_iter()
-                .filter(|r| {
-                    chrono::DateTime::parse_from_rfc3339(&r.created_at)
-                        .map(|dt| dt > cutoff)
-                        .unwrap_or(false)
-                })
-                .collect()
+            store.analyses_since(Utc::now() - chrono::Duration::hours(hours as i64))?
         } else {
-            runs.into_iter().take(10).collect()
+            store.recent_analyses(10)?
         }
     } else {
         let target_id: u64 = run_id.parse()?;
-        runs.into_iter().filter(|r| r.id == target_id).collect()
+        store.analysis(target_id)?.into_iter().collect()
     };
 
-    info!("📊 Validating {} workflow runs...", runs_to_validate.len());
-
-    // Parallel analysis
-    let analyses = client.analyze_runs_parallel(runs_to_validate).await?;
+    info!("📊 Validating {} workflow runs...", analyses.len());
 
     // Generate validation report
     let report = generate_validation_report(&analyses);
 
-    if create_pr {
-        create_validation_pr(client, &report, &analyses).await?;
+    if publish.target != Publish::None {
+        // Newest run of each workflow (analyses are newest first)
+        let mut seen = HashSet::new();
+        let latest: Vec<&WorkflowAnalysis> = analyses
+            .iter()
+            .filter(|a| seen.insert(a.run.name.clone()))
+            .collect();
+
+        publish_reports(client, &latest, publish).await?;
     }
 
     match output_format {
@@ -56,11 +97,11 @@ pub async fn run_validation(
     Ok(())
 }
 
-/// Post-run validation with AI review requests
+/// Post-run validation with review requests to the configured bots
 pub async fn post_run_validation(
     client: &GitHubClient,
     run_id: &str,
-    ai_review: bool,
+    publish: PublishOptions<'_>,
 ) -> Result<()> {
     info!("🔬 Running post-execution validation for run {}...", run_id);
 
@@ -75,33 +116,38 @@ pub async fn post_run_validation(
     let analysis = analyses.into_iter().next()
         .ok_or_else(|| anyhow::anyhow!("Failed to analyze run"))?;
 
-    // Generate comprehensive report
-    let report = ValidationReport::from_analysis(&analysis);
-
-    // Create branch for validation PR
-    let branch_name = format!("validation/run-{}-{}", run_id, Utc::now().format("%Y%m%d%H%M%S"));
-    let sha = client.get_default_branch_sha().await?;
-
-    // Try to create branch (may fail if already exists)
-    let _ = client.create_branch(&branch_name, &sha).await;
+    match publish.target {
+        Publish::None => {
+            let report = ValidationReport::from_analysis(&analysis, &workflow_audit(&analysis));
+            println!("{}", report.to_terminal());
+        }
+        _ => publish_reports(client, &[&analysis], publish).await?,
+    }
 
-    // Create PR with validation results
-    let pr_body = generate_pr_body(&report, &analysis, ai_review);
+    Ok(())
+}
 
-    let pr = client.create_pr(
-        &format!("🔬 Validation: {} - Run #{}", analysis.run.name, run_id),
-        &pr_body,
-        &branch_name,
-    ).await?;
+/// Publish one report per analysis as a PR or as tracking issues
+async fn publish_reports(
+    client: &GitHubClient,
+    analyses: &[&WorkflowAnalysis],
+    publish: PublishOptions<'_>,
+) -> Result<()> {
+    if analyses.is_empty() {
+        warn!("No analyses to publish");
+        return Ok(());
+    }
 
-    info!("✅ Created validation PR: {}", pr.html_url);
+    let reports: Vec<(ValidationReport, &WorkflowAnalysis)> = analyses
+        .iter()
+        .map(|&a| (ValidationReport::from_analysis(a, &workflow_audit(a)), a))
+        .collect();
 
-    if ai_review {
-        // Request AI reviews
-        request_ai_reviews(client, pr.number).await?;
+    match publish.target {
+        Publish::Pr => create_validation_pr(client, &reports, publish.base, publish.reviewers).await,
+        Publish::Issue => upsert_validation_issues(client, &reports).await,
+        Publish::None => Ok(()),
     }
-
-    Ok(())
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -127,6 +173,10 @@ pub struct ErrorDetail {
     pub message: String,
     pub severity: String,
     pub suggested_fix: Option<String>,
+    /// Failure signature the message matched
+    pub signature: Option<String>,
+    /// Source location reported with the error
+    pub location: Option<String>,
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -148,10 +198,17 @@ pub struct ValidationMetrics {
     pub sequential_jobs: usize,
     pub cache_hits: usize,
     pub cache_misses: usize,
+    pub max_concurrency: usize,
+    pub critical_path: Vec<String>,
+    pub critical_path_seconds: i64,
+    pub queue_wait_seconds: i64,
+    pub idle_seconds: i64,
+    pub parallel_efficiency: f64,
 }
 
 impl ValidationReport {
-    pub fn from_analysis(analysis: &WorkflowAnalysis) -> Self {
+    /// Report of one run; `audit` is the security audit of its workflow (see [`workflow_audit`])
+    pub fn from_analysis(analysis: &WorkflowAnalysis, audit: &SecurityAudit) -> Self {
         let mut errors = Vec::new();
         let mut warnings = analysis.warnings.clone();
         let mut failed_jobs = 0;
@@ -167,13 +224,36 @@ impl ValidationReport {
                         step_count += 1;
                         if step.conclusion.as_deref() == Some("failure") {
                             failed_steps += 1;
-                            errors.push(ErrorDetail {
-                                job: job.name.clone(),
-                                step: Some(step.name.clone()),
-                                message: format!("Step '{}' failed in job '{}'", step.name, job.name),
-                                severity: "error".to_string(),
-                                suggested_fix: suggest_fix(&step.name),
-                            });
+
+                            let step_findings: Vec<_> = analysis
+                                .findings
+                                .iter()
+                                .filter(|f| f.job == job.name && f.step.as_deref() == Some(step.name.as_str()))
+                                .collect();
+
+                            if step_findings.is_empty() {
+                                errors.push(ErrorDetail {
+                                    job: job.name.clone(),
+                                    step: Some(step.name.clone()),
+                                    message: format!("Step '{}' failed in job '{}'", step.name, job.name),
+                                    severity: "error".to_string(),
+                                    suggested_fix: suggest_fix(&step.name),
+                                    signature: None,
+                                    location: None,
+                                });
+                            }
+
+                            for finding in step_findings {
+                                errors.push(ErrorDetail {
+                                    job: job.name.clone(),
+                                    step: Some(step.name.clone()),
+                                    message: finding.line.clone(),
+                                    severity: "error".to_string(),
+                                    suggested_fix: finding.suggestion.clone().or_else(|| suggest_fix(&step.name)),
+                                    signature: finding.signature.clone(),
+                                    location: finding.location.clone(),
+                                });
+                            }
                         }
                     }
                 }
@@ -182,6 +262,27 @@ impl ValidationReport {
             }
         }
 
+        // Findings that could not be tied to a failed API step
+        for finding in &analysis.findings {
+            let attributed = errors.iter().any(|e| {
+                e.job == finding.job
+                    && e.step == finding.step
+                    && e.message == finding.line
+                    && e.location == finding.location
+            });
+            if !attributed {
+                errors.push(ErrorDetail {
+                    job: finding.job.clone(),
+                    step: finding.step.clone(),
+                    message: finding.line.clone(),
+                    severity: "error".to_string(),
+                    suggested_fix: finding.suggestion.clone(),
+                    signature: finding.signature.clone(),
+                    location: finding.location.clone(),
+                });
+            }
+        }
+
         // Generate recommendations based on analysis
         let mut recommendations = Vec::new();
 
@@ -207,6 +308,59 @@ impl ValidationReport {
             }
         }
 
+        let timeline = &analysis.timeline;
+
+        if timeline.max_queue_wait_seconds > 120 {
+            recommendations.push(Recommendation {
+                category: "performance".to_string(),
+                priority: "medium".to_string(),
+                title: "Reduce runner queue time".to_string(),
+                description: format!(
+                    "Jobs waited {}s in total for a runner (longest: {}s)",
+                    timeline.total_queue_wait_seconds, timeline.max_queue_wait_seconds
+                ),
+                action: "Check runner availability or concurrency limits; consider larger or additional runners".to_string(),
+            });
+        }
+
+        if analysis.cache.misses > 0 {
+            recommendations.push(Recommendation {
+                category: "performance".to_string(),
+                priority: if analysis.cache.hits == 0 { "medium" } else { "low" }.to_string(),
+                title: "Improve cache hit rate".to_string(),
+                description: format!(
+                    "{} cache miss(es), {} hit(s) in this run",
+                    analysis.cache.misses, analysis.cache.hits
+                ),
+                action: "Make cache keys stable (hash lockfiles only) and add restore-keys fallbacks".to_string(),
+            });
+        }
+
+        if let Some(slowest) = timeline.steps.first() {
+            if timeline.critical_path_seconds > 0 && slowest.seconds * 2 > timeline.critical_path_seconds {
+                recommendations.push(Recommendation {
+                    category: "performance".to_string(),
+                    priority: "low".to_string(),
+                    title: "Split the slowest step".to_string(),
+                    description: format!(
+                        "'{}' in job '{}' takes {}s of a {}s critical path",
+                        slowest.step, slowest.job, slowest.seconds, timeline.critical_path_seconds
+                    ),
+                    action: "Split it into parallel jobs (e.g. a test matrix) or cache its inputs".to_string(),
+                });
+            }
+        }
+
+        for finding in audit.findings.iter().filter(|f| f.severity >= Severity::High) {
+            recommendations.push(Recommendation {
+                category: "security".to_string(),
+                priority: if finding.severity == Severity::Critical { "critical" } else { "high" }.to_string(),
+                title: format!("Fix {}", finding.rule.id()),
+                description: finding.to_string(),
+                action: finding.message.clone(),
+            });
+        }
+
         // Calculate scores
         let total_jobs = analysis.jobs.len().max(1);
         let performance_score = if let Some(d) = analysis.duration_seconds {
@@ -227,17 +381,23 @@ impl ValidationReport {
             errors,
             warnings,
             performance_score,
-            security_score: 100.0, // Placeholder - would need deeper analysis
+            security_score: audit.score(),
             recommendations,
             metrics: ValidationMetrics {
                 job_count: analysis.jobs.len(),
                 step_count,
                 failed_jobs,
                 failed_steps,
-                parallel_jobs: estimate_parallel_jobs(&analysis.jobs),
-                sequential_jobs: analysis.jobs.len() - estimate_parallel_jobs(&analysis.jobs),
-                cache_hits: 0,  // Would need log analysis
-                cache_misses: 0,
+                parallel_jobs: timeline.parallel_jobs,
+                sequential_jobs: timeline.sequential_jobs,
+                cache_hits: analysis.cache.hits,
+                cache_misses: analysis.cache.misses,
+                max_concurrency: timeline.max_concurrency,
+                critical_path: timeline.critical_path.clone(),
+                critical_path_seconds: timeline.critical_path_seconds,
+                queue_wait_seconds: timeline.total_queue_wait_seconds,
+                idle_seconds: timeline.idle_gaps.iter().map(|g| g.seconds).sum(),
+                parallel_efficiency: timeline.parallel_efficiency,
             },
         }
     }
@@ -255,6 +415,13 @@ impl ValidationReport {
         md.push_str(&format!("| Duration | {}s |\n", self.duration_seconds.unwrap_or(0)));
         md.push_str(&format!("| Jobs | {} ({} failed) |\n", self.metrics.job_count, self.metrics.failed_jobs));
         md.push_str(&format!("| Steps | {} ({} failed) |\n", self.metrics.step_count, self.metrics.failed_steps));
+        md.push_str(&format!("| Critical Path | {}s ({}) |\n",
+            self.metrics.critical_path_seconds, self.metrics.critical_path.join(" → ")));
+        md.push_str(&format!("| Max Concurrency | {} |\n", self.metrics.max_concurrency));
+        md.push_str(&format!("| Queue Wait | {}s |\n", self.metrics.queue_wait_seconds));
+        md.push_str(&format!("| Idle Time | {}s |\n", self.metrics.idle_seconds));
+        md.push_str(&format!("| Parallel Efficiency | {:.1}% |\n", self.metrics.parallel_efficiency * 100.0));
+        md.push_str(&format!("| Cache | {} hit / {} miss |\n", self.metrics.cache_hits, self.metrics.cache_misses));
         md.push_str(&format!("| Performance Score | {:.1}% |\n", self.performance_score));
         md.push_str(&format!("| Security Score | {:.1}% |\n", self.security_score));
         md.push('\n');
@@ -264,6 +431,12 @@ impl ValidationReport {
             for error in &self.errors {
                 md.push_str(&format!("### {} / {}\n", error.job, error.step.as_deref().unwrap_or("N/A")));
                 md.push_str(&format!("- **Message:** {}\n", error.message));
+                if let Some(location) = &error.location {
+                    md.push_str(&format!("- **Location:** `{}`\n", location));
+                }
+                if let Some(signature) = &error.signature {
+                    md.push_str(&format!("- **Signature:** {}\n", signature));
+                }
                 md.push_str(&format!("- **Severity:** {}\n", error.severity));
                 if let Some(fix) = &error.suggested_fix {
                     md.push_str(&format!("- **Suggested Fix:** {}\n", fix));
@@ -316,20 +489,20 @@ fn suggest_fix(step_name: &str) -> Option<String> {
     }
 }
 
-fn estimate_parallel_jobs(jobs: &[crate::github::Job]) -> usize {
-    // Estimate based on start times - jobs starting at similar times are parallel
-    // This is a simplified heuristic
-    if jobs.len() <= 1 {
-        return 0;
+/// Static audit of a run's workflow file (the whole directory when it is not checked out here)
+pub fn workflow_audit(analysis: &WorkflowAnalysis) -> SecurityAudit {
+    match analysis.run.path.as_deref().filter(|p| std::path::Path::new(p).exists()) {
+        Some(path) => workflow_security::audit_file(path),
+        None => workflow_security::audit_dir(workflow_security::DEFAULT_WORKFLOWS_DIR).unwrap_or_else(|e| {
+            warn!("Skipping workflow security audit: {}", e);
+            SecurityAudit::default()
+        }),
     }
-
-    // For now, assume ~50% parallelism if multiple jobs
-    jobs.len() / 2
 }
 
 fn generate_validation_report(analyses: &[WorkflowAnalysis]) -> ValidationReport {
     if let Some(analysis) = analyses.first() {
-        ValidationReport::from_analysis(analysis)
+        ValidationReport::from_analysis(analysis, &workflow_audit(analysis))
     } else {
         ValidationReport {
             timestamp: Utc::now().to_rfc3339(),
@@ -352,12 +525,19 @@ fn generate_validation_report(analyses: &[WorkflowAnalysis]) -> ValidationReport
                 sequential_jobs: 0,
                 cache_hits: 0,
                 cache_misses: 0,
+                max_concurrency: 0,
+                critical_path: vec![],
+                critical_path_seconds: 0,
+                queue_wait_seconds: 0,
+                idle_seconds: 0,
+                parallel_efficiency: 0.0,
             },
         }
     }
 }
 
-fn generate_pr_body(report: &ValidationReport, analysis: &WorkflowAnalysis, ai_review: bool) -> String {
+/// `reviewers` are the bots whose review is requested on the PR
+fn generate_report_body(report: &ValidationReport, analysis: &WorkflowAnalysis, reviewers: &[String]) -> String {
     let mut body = String::new();
 
     body.push_str("## 🔬 Workflow Validation Report\n\n");
@@ -401,16 +581,10 @@ fn generate_pr_body(report: &ValidationReport, analysis: &WorkflowAnalysis, ai_r
         body.push('\n');
     }
 
-    if ai_review {
+    if !reviewers.is_empty() {
         body.push_str("---\n\n");
         body.push_str("### 🤖 AI Review Requested\n\n");
-        body.push_str("The following AI agents will analyze this validation:\n\n");
-        body.push_str("| Agent | Status | Focus |\n");
-        body.push_str("|-------|--------|-------|\n");
-        body.push_str("| CodeRabbit | 🔄 Pending | Code quality, patterns |\n");
-        body.push_str("| Gemini Code Assist | 🔄 Pending | Performance, security |\n");
-        body.push_str("| Copilot (Sonnet 4.5) | ⏳ After bots | Final validation |\n\n");
-        body.push_str("> After AI reviews complete, Copilot will be assigned for final validation and implementation.\n");
+        body.push_str(&format!("Review requests are posted below to: {}\n", reviewers.join(", ")));
     }
 
     body.push_str("\n---\n*Generated by Git-Core Protocol Workflow Orchestrator*\n");
@@ -418,76 +592,243 @@ fn generate_pr_body(report: &ValidationReport, analysis: &WorkflowAnalysis, ai_r
     body
 }
 
+/// Commit the reports to a new branch off `base` and open a PR
 async fn create_validation_pr(
     client: &GitHubClient,
-    report: &ValidationReport,
-    analyses: &[WorkflowAnalysis],
+    reports: &[(ValidationReport, &WorkflowAnalysis)],
+    base: Option<&str>,
+    reviewers: Option<&ReviewerRegistry>,
 ) -> Result<()> {
-    if analyses.is_empty() {
-        warn!("No analyses to create PR from");
-        return Ok(());
-    }
+    // Decided before the PR exists, so its body names only the bots actually asked
+    let presence = match reviewers {
+        Some(registry) => review_bots::detect_presence(client, registry).await,
+        None => Presence::default(),
+    };
+    let requested: Vec<String> = reviewers
+        .map(|registry| registry.pending(&presence, &[]).into_iter().map(|r| r.name.clone()).collect())
+        .unwrap_or_default();
 
-    let analysis = &analyses[0];
-    let branch_name = format!("validation/batch-{}", Utc::now().format("%Y%m%d%H%M%S"));
+    let base = match base {
+        Some(base) => base.to_string(),
+        None => client.get_default_branch().await?,
+    };
+    let sha = client.get_branch_sha(&base).await?;
+
+    let (title, branch_name, pr_body) = match reports {
+        [(report, analysis)] => (
+            format!("🔬 Validation: {} - Run #{}", report.workflow_name, report.run_id),
+            format!("validation/run-{}-{}", report.run_id, Utc::now().format("%Y%m%d%H%M%S")),
+            generate_report_body(report, analysis, &requested),
+        ),
+        _ => (
+            format!("🔬 Validation Report: {} workflows", reports.len()),
+            format!("validation/batch-{}", Utc::now().format("%Y%m%d%H%M%S")),
+            generate_batch_body(reports, &requested),
+        ),
+    };
 
-    let sha = client.get_default_branch_sha().await?;
-    let _ = client.create_branch(&branch_name, &sha).await;
+    client.create_branch(&branch_name, &sha).await?;
 
-    let pr_body = generate_pr_body(report, analysis, true);
+    for (report, analysis) in reports {
+        client.put_file(
+            &branch_name,
+            &report_path(report),
+            &generate_report_body(report, analysis, &[]),
+            &format!("docs(reports): validation of {} run #{}", report.workflow_name, report.run_id),
+        ).await?;
+    }
 
-    let pr = client.create_pr(
-        &format!("🔬 Validation Report: {} runs analyzed", analyses.len()),
-        &pr_body,
-        &branch_name,
-    ).await?;
+    let pr = client.create_pr(&title, &pr_body, &branch_name, &base).await?;
 
     info!("✅ Created validation PR #{}: {}", pr.number, pr.html_url);
 
-    // Request AI reviews
-    request_ai_reviews(client, pr.number).await?;
+    if let Some(registry) = reviewers {
+        let requested = review_bots::request_reviews(client, registry, &presence, pr.number).await?;
+        info!("🤖 Requested reviews from: {}", if requested.is_empty() { "none".to_string() } else { requested.join(", ") });
+    }
+
+    Ok(())
+}
+
+/// Open or update one tracking issue per workflow
+async fn upsert_validation_issues(
+    client: &GitHubClient,
+    reports: &[(ValidationReport, &WorkflowAnalysis)],
+) -> Result<()> {
+    let existing: HashMap<String, u64> = client
+        .list_open_issues(VALIDATION_LABEL)
+        .await?
+        .into_iter()
+        .map(|issue| (issue.title, issue.number))
+        .collect();
+
+    for (report, analysis) in reports {
+        let title = issue_title(report);
+        let body = generate_report_body(report, analysis, &[]);
+
+        match existing.get(&title) {
+            Some(&number) => {
+                client.update_issue_body(number, &body).await?;
+                info!("🔄 Updated validation issue #{}: {}", number, title);
+            }
+            None => {
+                let issue = client.create_issue(&title, &body, &[VALIDATION_LABEL]).await?;
+                info!("🆕 Opened validation issue #{}: {}", issue.number, issue.html_url);
+            }
+        }
+    }
 
     Ok(())
 }
 
-async fn request_ai_reviews(client: &GitHubClient, pr_number: u64) -> Result<()> {
-    info!("🤖 Requesting AI reviews for PR #{}...", pr_number);
+fn generate_batch_body(reports: &[(ValidationReport, &WorkflowAnalysis)], reviewers: &[String]) -> String {
+    let mut body = String::new();
 
-    // Request Gemini review
-    client.add_pr_comment(pr_number, "/gemini review
+    body.push_str("## 🔬 Workflow Validation Reports\n\n");
+    body.push_str("| Workflow | Run | Conclusion | Performance | Security | Report |\n");
+    body.push_str("|----------|-----|------------|-------------|----------|--------|\n");
+    for (report, analysis) in reports {
+        body.push_str(&format!(
+            "| {} | [#{}]({}) | {} | {:.1}% | {:.1}% | `{}` |\n",
+            report.workflow_name,
+            report.run_id,
+            analysis.run.html_url,
+            report.conclusion,
+            report.performance_score,
+            report.security_score,
+            report_path(report)
+        ));
+    }
 
-Please analyze this workflow validation report and provide:
+    if !reviewers.is_empty() {
+        body.push_str(&format!("\n> 🤖 AI reviews requested from {} in the comments below.\n", reviewers.join(", ")));
+    }
 
-1. **Error Analysis**: Deep dive into any failures
-2. **Performance Assessment**: Are there optimization opportunities?
-3. **Security Review**: Any security concerns in the workflow?
-4. **Recommendations Validation**: Are the suggested fixes appropriate?
+    body.push_str("\n---\n*Generated by Git-Core Protocol Workflow Orchestrator*\n");
 
-Focus on actionable improvements for the CI/CD pipeline.").await?;
+    body
+}
 
-    // Request CodeRabbit review
-    client.add_pr_comment(pr_number, "@coderabbitai review
+/// `docs/agent-docs/reports/<workflow-slug>-run-<id>.md`
+///
+/// Names without ASCII letters or digits get `workflow-<name hash>` as slug.
+fn report_path(report: &ValidationReport) -> String {
+    let mut slug = report
+        .workflow_name
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+
+    if slug.is_empty() {
+        // FNV-1a, stable across runs and toolchains
+        let hash = report
+            .workflow_name
+            .bytes()
+            .fold(0x811c9dc5u32, |hash, b| (hash ^ b as u32).wrapping_mul(0x01000193));
+        slug = format!("workflow-{:08x}", hash);
+    }
 
-Please analyze this validation report for:
-- Pattern improvements
-- Best practices violations
-- Potential reliability issues").await?;
+    format!("{}/{}-run-{}.md", REPORTS_DIR, slug, report.run_id)
+}
 
-    // Add Copilot assignment note
-    client.add_pr_comment(pr_number, "## 🤖 Copilot Assignment Queue
+fn issue_title(report: &ValidationReport) -> String {
+    format!("🔬 Workflow validation: {}", report.workflow_name)
+}
 
-Once AI reviews are complete, assign this PR to Copilot for:
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::{Job, WorkflowRun};
+    use crate::log_analysis::LogFinding;
+
+    fn analysis(jobs: Vec<Job>, findings: Vec<LogFinding>) -> WorkflowAnalysis {
+        WorkflowAnalysis {
+            run: WorkflowRun {
+                id: 1,
+                name: "CI".to_string(),
+                status: "completed".to_string(),
+                conclusion: Some("failure".to_string()),
+                html_url: "https://github.com/o/r/actions/runs/1".to_string(),
+                created_at: "2025-01-01T00:00:00Z".to_string(),
+                updated_at: "2025-01-01T00:05:00Z".to_string(),
+                run_attempt: 1,
+                workflow_id: 7,
+                head_branch: "main".to_string(),
+                head_sha: "abc".to_string(),
+                path: None,
+            },
+            jobs,
+            logs: None,
+            errors: vec![],
+            warnings: vec![],
+            findings,
+            cache: Default::default(),
+            timeline: Default::default(),
+            duration_seconds: Some(300),
+        }
+    }
 
-1. **Meta-Analysis**: Review the AI reviews themselves
-2. **Action Plan**: Generate specific fixes based on all feedback
-3. **Implementation**: Create follow-up PRs with corrections
+    fn finding(job: &str, step: &str, line: &str) -> LogFinding {
+        LogFinding {
+            job: job.to_string(),
+            step: Some(step.to_string()),
+            line: line.to_string(),
+            line_number: 1,
+            location: None,
+            signature: None,
+            category: None,
+            suggestion: None,
+        }
+    }
 
-```
-/copilot analyze --model sonnet-4.5 --scope full
-```
+    #[test]
+    fn test_publish_from_str() {
+        assert_eq!("pr".parse::<Publish>().unwrap(), Publish::Pr);
+        assert_eq!("Issue".parse::<Publish>().unwrap(), Publish::Issue);
+        assert_eq!("none".parse::<Publish>().unwrap(), Publish::None);
+        assert!("comment".parse::<Publish>().is_err());
+    }
 
-> ⏳ Copilot will be auto-assigned after CodeRabbit and Gemini complete their reviews.").await?;
+    #[test]
+    fn test_unattributed_findings_in_one_step() {
+        // The job has no API step data, so no finding is attributed to a failed step
+        let job = Job {
+            id: 10,
+            name: "test".to_string(),
+            status: "completed".to_string(),
+            conclusion: Some("failure".to_string()),
+            created_at: None,
+            started_at: None,
+            completed_at: None,
+            steps: None,
+        };
+        let findings = vec![
+            finding("test", "Run tests", "error[E0308]: mismatched types"),
+            finding("test", "Run tests", "error: could not compile `app`"),
+            finding("test", "Run tests", "error: could not compile `app`"),
+        ];
+
+        let report = ValidationReport::from_analysis(&analysis(vec![job], findings), &SecurityAudit::default());
+        let messages: Vec<&str> = report.errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, ["error[E0308]: mismatched types", "error: could not compile `app`"]);
+    }
 
-    info!("✅ AI review requests sent");
-    Ok(())
+    #[test]
+    fn test_report_path() {
+        let mut report = generate_validation_report(&[]);
+        report.workflow_name = "CI / Build & Test".to_string();
+        report.run_id = 42;
+
+        assert_eq!(report_path(&report), "docs/agent-docs/reports/ci-build-test-run-42.md");
+        assert_eq!(issue_title(&report), "🔬 Workflow validation: CI / Build & Test");
+
+        report.workflow_name = "🚀 デプロイ".to_string();
+        let path = report_path(&report);
+        assert!(path.starts_with("docs/agent-docs/reports/workflow-"), "{}", path);
+        assert!(path.ends_with("-run-42.md"));
+        report.workflow_name = "🧪 テスト".to_string();
+        assert_ne!(report_path(&report), path);
+    }
 }
@@ -2,7 +2,7 @@
 //! 
 //! These tests verify the dispatch logic and strategy selection.
 
-use workflow_orchestrator::dispatcher_core::{DispatcherCore, Strategy, Agent};
+use workflow_orchestrator::dispatcher_core::{AgentRegistry, DispatcherCore, Strategy};
 use octocrab::Octocrab;
 
 async fn create_dispatcher() -> DispatcherCore {
@@ -35,7 +35,7 @@ async fn test_copilot_only_strategy() {
     let _dispatcher = create_dispatcher().await;
     
     let strategy: Strategy = "copilot-only".parse().unwrap();
-    assert_eq!(strategy, Strategy::CopilotOnly);
+    assert_eq!(strategy, Strategy::Only("copilot".to_string()));
 }
 
 #[tokio::test]
@@ -43,7 +43,7 @@ async fn test_jules_only_strategy() {
     let _dispatcher = create_dispatcher().await;
     
     let strategy: Strategy = "jules-only".parse().unwrap();
-    assert_eq!(strategy, Strategy::JulesOnly);
+    assert_eq!(strategy, Strategy::Only("jules".to_string()));
 }
 
 #[tokio::test]
@@ -54,14 +54,26 @@ async fn test_invalid_strategy() {
 
 #[tokio::test]
 async fn test_agent_labels() {
-    assert_eq!(Agent::Copilot.label(), "copilot");
-    assert_eq!(Agent::Jules.label(), "jules");
+    let registry = AgentRegistry::defaults();
+    assert_eq!(registry.get("copilot").unwrap().label(), "copilot");
+    assert_eq!(registry.get("jules").unwrap().label(), "jules");
 }
 
 #[tokio::test]
 async fn test_agent_assignees() {
-    assert_eq!(Agent::Copilot.assignee(), Some("Copilot"));
-    assert_eq!(Agent::Jules.assignee(), None);
+    let registry = AgentRegistry::defaults();
+    assert_eq!(registry.get("copilot").unwrap().assignee(), Some("Copilot"));
+    assert_eq!(registry.get("jules").unwrap().assignee(), None);
+}
+
+#[tokio::test]
+async fn test_repo_agent_registry_loads() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../../../../.github/dispatcher-agents.yml");
+    let registry = AgentRegistry::from_file(path).unwrap();
+    assert!(registry.get("copilot").is_some());
+
+    let dispatcher = create_dispatcher().await.with_registry(registry);
+    assert_eq!(dispatcher.registry().escalation().label(), "needs-human");
 }
 
 #[tokio::test]
@@ -90,10 +102,10 @@ async fn test_strategy_case_insensitivity() {
 async fn test_strategy_aliases() {
     // Test short aliases
     let copilot: Strategy = "copilot".parse().unwrap();
-    assert_eq!(copilot, Strategy::CopilotOnly);
+    assert_eq!(copilot, Strategy::Only("copilot".to_string()));
     
     let jules: Strategy = "jules".parse().unwrap();
-    assert_eq!(jules, Strategy::JulesOnly);
+    assert_eq!(jules, Strategy::Only("jules".to_string()));
     
     let roundrobin: Strategy = "roundrobin".parse().unwrap();
     assert_eq!(roundrobin, Strategy::RoundRobin);
@@ -148,7 +148,7 @@ fn bench_file_scanning(c: &mut Criterion) {
 
     // Setup: Create temp directory with multiple issue files
     let temp_dir = TempDir::new().unwrap();
-    let syncer = create_test_syncer(&temp_dir);
+    let _syncer = create_test_syncer(&temp_dir);
 
     let issues_dir = temp_dir.path().join("issues");
 
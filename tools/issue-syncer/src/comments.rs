@@ -0,0 +1,270 @@
+//! Comment Mirroring
+//!
+//! Mirrors issue comments into the local tree, either as a sidecar file
+//! next to the issue (`FEAT_x.md` → `FEAT_x.comments.md`) or as a
+//! collapsible section at the end of the issue file.
+//!
+//! Each comment is a block headed by a marker line:
+//!
+//! ```markdown
+//! <!-- comment id=1234 author=octocat created=2025-01-05T10:00:00Z -->
+//! Comment body, e.g. an <agent-state> block
+//!
+//! <!-- comment -->
+//! A new local comment, posted on the next push
+//! ```
+//!
+//! Remote comments are merged in by id (new ones appended, edited ones
+//! replaced). Blocks without an id are posted, then get their id.
+
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+use crate::parser::{COMMENTS_END, COMMENTS_START};
+
+const MARKER_PREFIX: &str = "<!-- comment";
+const MARKER_SUFFIX: &str = "-->";
+const SIDECAR_SUFFIX: &str = ".comments.md";
+
+/// Where comments are mirrored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CommentMode {
+    /// Comments are not synced
+    #[default]
+    Off,
+    /// `FEAT_x.comments.md` next to the issue file
+    Sidecar,
+    /// A collapsible section at the end of the issue file
+    Section,
+}
+
+impl std::str::FromStr for CommentMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "off" | "none" => Ok(CommentMode::Off),
+            "sidecar" => Ok(CommentMode::Sidecar),
+            "section" => Ok(CommentMode::Section),
+            _ => Err(anyhow::anyhow!("Invalid comment mode: {} (expected off, sidecar or section)", s)),
+        }
+    }
+}
+
+/// One comment; `id` is `None` until a local comment is posted
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CommentBlock {
+    pub id: Option<u64>,
+    pub author: Option<String>,
+    /// RFC 3339 creation time
+    pub created_at: Option<String>,
+    pub body: String,
+}
+
+impl CommentBlock {
+    fn marker(&self) -> String {
+        let mut marker = MARKER_PREFIX.to_string();
+        if let Some(id) = self.id {
+            marker.push_str(&format!(" id={}", id));
+        }
+        if let Some(author) = &self.author {
+            marker.push_str(&format!(" author={}", author));
+        }
+        if let Some(created_at) = &self.created_at {
+            marker.push_str(&format!(" created={}", created_at));
+        }
+        marker.push(' ');
+        marker.push_str(MARKER_SUFFIX);
+        marker
+    }
+
+    /// Parse a marker line
+    fn from_marker(line: &str) -> Option<Self> {
+        let inner = line.trim().strip_prefix(MARKER_PREFIX)?.strip_suffix(MARKER_SUFFIX)?;
+        // `<!-- comments ... -->` and the like are not comment markers
+        if !inner.is_empty() && !inner.starts_with(' ') {
+            return None;
+        }
+
+        let mut block = Self::default();
+        for pair in inner.split_whitespace() {
+            match pair.split_once('=') {
+                Some(("id", id)) => block.id = id.parse().ok(),
+                Some(("author", author)) => block.author = Some(author.to_string()),
+                Some(("created", created)) => block.created_at = Some(created.to_string()),
+                _ => {}
+            }
+        }
+        Some(block)
+    }
+}
+
+/// The comments of one issue
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CommentThread {
+    pub blocks: Vec<CommentBlock>,
+}
+
+impl CommentThread {
+    /// Parse comment blocks; text before the first marker is ignored
+    pub fn parse(text: &str) -> Self {
+        let mut blocks: Vec<CommentBlock> = Vec::new();
+
+        for line in text.lines() {
+            if let Some(block) = CommentBlock::from_marker(line) {
+                blocks.push(block);
+            } else if let Some(block) = blocks.last_mut() {
+                block.body.push_str(line);
+                block.body.push('\n');
+            }
+        }
+
+        for block in &mut blocks {
+            block.body = block.body.trim().to_string();
+        }
+        blocks.retain(|b| b.id.is_some() || !b.body.is_empty());
+
+        Self { blocks }
+    }
+
+    /// Parse the comments section of an issue file
+    pub fn from_section(section: &str) -> Self {
+        let inner = section
+            .rsplit_once("</details>")
+            .map(|(inner, _)| inner)
+            .unwrap_or(section);
+        Self::parse(inner)
+    }
+
+    /// Local comments not posted yet
+    pub fn pending(&self) -> impl Iterator<Item = &CommentBlock> {
+        self.blocks.iter().filter(|b| b.id.is_none())
+    }
+
+    /// Merge a remote comment by id; returns whether the thread changed
+    pub fn merge(&mut self, comment: CommentBlock) -> bool {
+        match self.blocks.iter_mut().find(|b| b.id.is_some() && b.id == comment.id) {
+            Some(existing) if *existing == comment => false,
+            Some(existing) => {
+                *existing = comment;
+                true
+            }
+            None => {
+                // Keep local pending blocks at the end
+                let at = self.blocks.iter().position(|b| b.id.is_none()).unwrap_or(self.blocks.len());
+                self.blocks.insert(at, comment);
+                true
+            }
+        }
+    }
+
+    fn render_blocks(&self) -> String {
+        self.blocks
+            .iter()
+            .map(|b| format!("{}\n{}\n", b.marker(), b.body))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Sidecar file content
+    pub fn render_sidecar(&self, issue_number: u64) -> String {
+        let mut out = format!(
+            "# Comments on #{}\n\n\
+             > Mirrored by issue-syncer. To post a comment, append a block that starts\n\
+             > with a `<!-- comment -->` line; it is posted on the next push.\n",
+            issue_number
+        );
+        if !self.blocks.is_empty() {
+            out.push('\n');
+            out.push_str(&self.render_blocks());
+        }
+        out
+    }
+
+    /// Collapsible section for the end of an issue file
+    pub fn render_section(&self) -> String {
+        let posted = self.blocks.iter().filter(|b| b.id.is_some()).count();
+        let mut out = format!(
+            "{}\n<details>\n<summary>Comments ({})</summary>\n\n",
+            COMMENTS_START, posted
+        );
+        if !self.blocks.is_empty() {
+            out.push_str(&self.render_blocks());
+            out.push('\n');
+        }
+        out.push_str("</details>\n");
+        out.push_str(COMMENTS_END);
+        out
+    }
+}
+
+/// `FEAT_x.comments.md` for `FEAT_x.md`
+pub fn sidecar_path(issue_file: &Path) -> PathBuf {
+    let stem = issue_file.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+    issue_file.with_file_name(format!("{}{}", stem, SIDECAR_SUFFIX))
+}
+
+/// Whether a file name is a comments sidecar
+pub fn is_sidecar(filename: &str) -> bool {
+    filename.ends_with(SIDECAR_SUFFIX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn remote(id: u64, body: &str) -> CommentBlock {
+        CommentBlock {
+            id: Some(id),
+            author: Some("octocat".to_string()),
+            created_at: Some("2025-01-05T10:00:00+00:00".to_string()),
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_sidecar_round_trip() {
+        let mut thread = CommentThread::default();
+        thread.merge(remote(1, "First"));
+        thread.merge(remote(2, "<agent-state>\nstep: 2\n</agent-state>"));
+
+        let text = thread.render_sidecar(42);
+        assert!(text.starts_with("# Comments on #42"));
+        assert_eq!(CommentThread::parse(&text), thread);
+    }
+
+    #[test]
+    fn test_pending_and_merge() {
+        let text = "<!-- comment id=1 author=octocat -->\nFirst\n\n<!-- comment -->\nPlease rerun CI\n\n<!-- comment -->\n";
+        let mut thread = CommentThread::parse(text);
+
+        assert_eq!(thread.blocks.len(), 2);
+        assert_eq!(thread.pending().map(|b| b.body.as_str()).collect::<Vec<_>>(), ["Please rerun CI"]);
+
+        assert!(thread.merge(remote(1, "First (edited)")));
+        assert!(thread.merge(remote(3, "Third")));
+        assert!(!thread.merge(remote(3, "Third")));
+
+        let ids: Vec<Option<u64>> = thread.blocks.iter().map(|b| b.id).collect();
+        assert_eq!(ids, [Some(1), Some(3), None]);
+        assert_eq!(thread.blocks[0].body, "First (edited)");
+    }
+
+    #[test]
+    fn test_section_round_trip() {
+        let mut thread = CommentThread::default();
+        thread.merge(remote(7, "Looks good"));
+
+        let section = thread.render_section();
+        assert!(section.contains("<summary>Comments (1)</summary>"));
+        assert_eq!(CommentThread::from_section(&section), thread);
+    }
+
+    #[test]
+    fn test_sidecar_names() {
+        assert_eq!(sidecar_path(Path::new("issues/FEAT_x.md")), Path::new("issues/FEAT_x.comments.md"));
+        assert!(is_sidecar("FEAT_x.comments.md"));
+        assert!(!is_sidecar("FEAT_x.md"));
+        assert!("inline".parse::<CommentMode>().is_err());
+    }
+}
@@ -0,0 +1,320 @@
+//! Mapping Doctor
+//!
+//! Checks the issues directory against the mapping file and GitHub. The
+//! `number` in a file's frontmatter is the source of truth; the mapping
+//! only caches it along with the sync state.
+//!
+//! | Finding | Fix |
+//! |---------|-----|
+//! | Renamed file (same `number`, or similar content) | move the mapping entry |
+//! | Mapping entry without a file | remove the entry |
+//! | Mapped file without `number` | write the number into the file |
+//! | `number` differs from the mapping | remap the file to its `number` |
+//! | Several files with the same `number` | manual |
+//! | Open issues with the same title | manual (close the duplicates) |
+//! | Issue file in a subdirectory | move it to the issues directory |
+//! | File whose frontmatter does not parse | manual (fix the frontmatter) |
+//!
+//! Files that do not parse still count as present, so their mapping
+//! entries are never taken for orphans.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::mapping::IssueMapping;
+use crate::parser::IssueData;
+
+/// Similarity above which an unmapped file is taken for a renamed one
+pub const RENAME_SIMILARITY: f64 = 0.8;
+
+/// A problem found by the doctor
+#[derive(Debug, Clone, PartialEq)]
+pub enum Finding {
+    /// A mapped file was renamed
+    Renamed { from: String, to: String, issue: u64 },
+    /// A mapping entry whose file is gone
+    Orphaned { file: String, issue: u64 },
+    /// A mapped file without `number` in its frontmatter
+    MissingNumber { file: String, issue: u64 },
+    /// A file whose `number` is not the issue it is mapped to
+    NumberMismatch { file: String, mapped: u64, number: u64 },
+    /// Several files bound to one issue
+    SharedNumber { issue: u64, files: Vec<String> },
+    /// Open remote issues with the same title
+    DuplicateTitle { title: String, issues: Vec<u64> },
+    /// An issue file below a subdirectory, which is not synced
+    Nested { path: PathBuf },
+    /// A file whose frontmatter does not parse
+    Unparseable { file: String, error: String },
+}
+
+impl Finding {
+    /// Whether `doctor --fix` can repair it
+    pub fn is_fixable(&self) -> bool {
+        !matches!(
+            self,
+            Finding::SharedNumber { .. } | Finding::DuplicateTitle { .. } | Finding::Unparseable { .. }
+        )
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Finding::Renamed { from, to, issue } => {
+                write!(f, "{} was renamed to {} (issue #{}); the mapping still has the old name", from, to, issue)
+            }
+            Finding::Orphaned { file, issue } => {
+                write!(f, "{} is mapped to issue #{} but does not exist; the entry can be removed", file, issue)
+            }
+            Finding::MissingNumber { file, issue } => {
+                write!(f, "{} has no `number`; `number: {}` can be added", file, issue)
+            }
+            Finding::NumberMismatch { file, mapped, number } => write!(
+                f,
+                "{} says `number: {}` but is mapped to issue #{}; it can be remapped to #{}",
+                file, number, mapped, number
+            ),
+            Finding::SharedNumber { issue, files } => write!(
+                f,
+                "{} all have `number: {}`; keep one and remove `number` from the others",
+                files.join(", "),
+                issue
+            ),
+            Finding::DuplicateTitle { title, issues } => {
+                let issues: Vec<String> = issues.iter().map(|n| format!("#{}", n)).collect();
+                write!(f, "Open issues {} are all titled \"{}\"; close the duplicates on GitHub", issues.join(", "), title)
+            }
+            Finding::Nested { path } => {
+                write!(f, "{} is in a subdirectory and is not synced; it can be moved up", path.display())
+            }
+            Finding::Unparseable { file, error } => {
+                write!(f, "{} does not parse and is not synced until its frontmatter is fixed: {}", file, error)
+            }
+        }
+    }
+}
+
+/// A finding and whether `--fix` repaired it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnosis {
+    pub finding: Finding,
+    pub fixed: bool,
+}
+
+/// An issue file in the issues directory
+#[derive(Debug, Clone)]
+pub struct LocalFile {
+    pub name: String,
+    pub data: IssueData,
+}
+
+/// Everything wrong with the files, the mapping and the remote issues
+///
+/// `unparseable` holds the name and parse error of files whose frontmatter
+/// does not parse; `remote` the number and title of open issues; `nested`
+/// the issue files found in subdirectories.
+pub fn diagnose(
+    files: &[LocalFile],
+    unparseable: &[(String, String)],
+    mapping: &IssueMapping,
+    remote: &[(u64, String)],
+    nested: &[PathBuf],
+) -> Vec<Finding> {
+    let mut findings: Vec<Finding> = unparseable
+        .iter()
+        .map(|(file, error)| Finding::Unparseable { file: file.clone(), error: error.clone() })
+        .collect();
+
+    // Several files claiming one issue
+    let mut claims: BTreeMap<u64, Vec<String>> = BTreeMap::new();
+    for file in files {
+        if let Some(number) = file.data.number {
+            claims.entry(number).or_default().push(file.name.clone());
+        }
+    }
+    for (issue, mut names) in claims.into_iter().filter(|(_, names)| names.len() > 1) {
+        names.sort();
+        findings.push(Finding::SharedNumber { issue, files: names });
+    }
+
+    for file in files {
+        match (mapping.get_issue(&file.name), file.data.number) {
+            (Some(mapped), Some(number)) if mapped != number => findings.push(Finding::NumberMismatch {
+                file: file.name.clone(),
+                mapped,
+                number,
+            }),
+            (Some(issue), None) => findings.push(Finding::MissingNumber { file: file.name.clone(), issue }),
+            _ => {}
+        }
+    }
+
+    // Entries whose file is gone: renamed, or orphaned
+    let names: HashSet<&str> = files
+        .iter()
+        .map(|f| f.name.as_str())
+        .chain(unparseable.iter().map(|(name, _)| name.as_str()))
+        .collect();
+    let mut missing: Vec<(&String, _)> = mapping.entries().filter(|(file, _)| !names.contains(file.as_str())).collect();
+    missing.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut adopted: HashSet<&str> = HashSet::new();
+    for (old, entry) in missing {
+        let by_number = files.iter().find(|f| {
+            f.data.number == Some(entry.issue)
+                && mapping.get_issue(&f.name) != Some(entry.issue)
+                && !adopted.contains(f.name.as_str())
+        });
+        let by_content = || {
+            let base = entry.base.as_ref()?;
+            files
+                .iter()
+                .filter(|f| f.data.number.is_none() && !mapping.contains_file(&f.name) && !adopted.contains(f.name.as_str()))
+                .map(|f| (f, similarity(base, &f.data)))
+                .filter(|(_, score)| *score >= RENAME_SIMILARITY)
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(f, _)| f)
+        };
+
+        match by_number.or_else(by_content) {
+            Some(file) => {
+                adopted.insert(file.name.as_str());
+                findings.push(Finding::Renamed { from: old.clone(), to: file.name.clone(), issue: entry.issue });
+            }
+            None => findings.push(Finding::Orphaned { file: old.clone(), issue: entry.issue }),
+        }
+    }
+
+    let mut titles: BTreeMap<String, (String, Vec<u64>)> = BTreeMap::new();
+    for (number, title) in remote {
+        let group = titles
+            .entry(title.trim().to_lowercase())
+            .or_insert_with(|| (title.trim().to_string(), Vec::new()));
+        group.1.push(*number);
+    }
+    for (_, (title, mut issues)) in titles.into_iter().filter(|(_, (_, issues))| issues.len() > 1) {
+        issues.sort_unstable();
+        findings.push(Finding::DuplicateTitle { title, issues });
+    }
+
+    findings.extend(nested.iter().map(|path| Finding::Nested { path: path.clone() }));
+    findings
+}
+
+/// Word overlap of title and body (Sørensen–Dice, 0.0 to 1.0)
+pub fn similarity(a: &IssueData, b: &IssueData) -> f64 {
+    fn words(data: &IssueData) -> HashSet<String> {
+        format!("{} {}", data.title, data.body)
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(str::to_lowercase)
+            .collect()
+    }
+
+    let (a, b) = (words(a), words(b));
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    2.0 * a.intersection(&b).count() as f64 / (a.len() + b.len()) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(name: &str, title: &str, number: Option<u64>) -> LocalFile {
+        LocalFile {
+            name: name.to_string(),
+            data: IssueData {
+                title: title.to_string(),
+                number,
+                body: format!("Details about {}", title.to_lowercase()),
+                ..Default::default()
+            },
+        }
+    }
+
+    fn synced(mapping: &mut IssueMapping, local: &LocalFile, issue: u64) {
+        mapping.record_sync(local.name.clone(), issue, String::new(), local.data.clone(), None);
+    }
+
+    #[test]
+    fn test_renames_and_orphans() {
+        let mut mapping = IssueMapping::default();
+        synced(&mut mapping, &file("FEAT_login.md", "Add login", None), 1);
+        synced(&mut mapping, &file("FEAT_oauth.md", "Support OAuth providers", None), 2);
+        synced(&mut mapping, &file("BUG_gone.md", "Crash on start", None), 3);
+        synced(&mut mapping, &file("FEAT_kept.md", "Kept", Some(4)), 4);
+
+        let files = [
+            file("FEAT_sign-in.md", "Add login", Some(1)),
+            file("FEAT_oauth2.md", "Support OAuth providers", None),
+            file("FEAT_kept.md", "Kept", None),
+            file("FEAT_new.md", "Something else entirely", None),
+        ];
+
+        let findings = diagnose(&files, &[], &mapping, &[], &[]);
+        assert_eq!(
+            findings,
+            vec![
+                Finding::MissingNumber { file: "FEAT_kept.md".to_string(), issue: 4 },
+                Finding::Orphaned { file: "BUG_gone.md".to_string(), issue: 3 },
+                Finding::Renamed { from: "FEAT_login.md".to_string(), to: "FEAT_sign-in.md".to_string(), issue: 1 },
+                Finding::Renamed { from: "FEAT_oauth.md".to_string(), to: "FEAT_oauth2.md".to_string(), issue: 2 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_conflicting_numbers_and_duplicates() {
+        let mut mapping = IssueMapping::default();
+        mapping.add("FEAT_a.md".to_string(), 5);
+
+        let files = [file("FEAT_a.md", "A", Some(6)), file("FEAT_b.md", "B", Some(6))];
+        let remote = [(6, "Add login".to_string()), (9, "add login ".to_string()), (7, "Other".to_string())];
+        let nested = [PathBuf::from("issues/old/FEAT_c.md")];
+
+        let findings = diagnose(&files, &[], &mapping, &remote, &nested);
+        assert_eq!(
+            findings,
+            vec![
+                Finding::SharedNumber { issue: 6, files: vec!["FEAT_a.md".to_string(), "FEAT_b.md".to_string()] },
+                Finding::NumberMismatch { file: "FEAT_a.md".to_string(), mapped: 5, number: 6 },
+                Finding::DuplicateTitle { title: "Add login".to_string(), issues: vec![6, 9] },
+                Finding::Nested { path: PathBuf::from("issues/old/FEAT_c.md") },
+            ]
+        );
+        assert!(!findings[0].is_fixable());
+        assert!(findings[1].is_fixable());
+    }
+
+    #[test]
+    fn test_unparseable_files_are_not_orphans() {
+        let mut mapping = IssueMapping::default();
+        synced(&mut mapping, &file("FEAT_broken.md", "Broken", Some(1)), 1);
+
+        let unparseable = [("FEAT_broken.md".to_string(), "Missing frontmatter closing marker".to_string())];
+        let findings = diagnose(&[], &unparseable, &mapping, &[], &[]);
+        assert_eq!(
+            findings,
+            vec![Finding::Unparseable {
+                file: "FEAT_broken.md".to_string(),
+                error: "Missing frontmatter closing marker".to_string(),
+            }]
+        );
+        assert!(!findings[0].is_fixable());
+    }
+
+    #[test]
+    fn test_similarity() {
+        let a = file("a.md", "Add OAuth login", None).data;
+        let mut b = a.clone();
+        assert_eq!(similarity(&a, &b), 1.0);
+
+        b.body.push_str(" and tokens");
+        assert!(similarity(&a, &b) >= RENAME_SIMILARITY);
+        assert!(similarity(&a, &file("c.md", "Fix crash on start", None).data) < 0.5);
+    }
+}
@@ -0,0 +1,507 @@
+//! Frontmatter Syntax
+//!
+//! Splits a markdown file into frontmatter and body, and edits frontmatter
+//! keys in place so a rewritten file keeps its formatting and comments.
+//!
+//! Accepted forms:
+//! - YAML between `---` and `---` (or `...`)
+//! - TOML between `+++` and `+++`, read and edited with `toml_edit`;
+//!   dates and times are read as strings
+//!
+//! A UTF-8 BOM and CRLF line endings are accepted and kept on rewrite.
+//! Errors carry the line (and column where known) in the file.
+
+use anyhow::{anyhow, Context, Result};
+use serde::de::DeserializeOwned;
+use serde_yaml::Value;
+use toml_edit::{DocumentMut, Item};
+
+const BOM: char = '\u{feff}';
+
+/// Frontmatter syntax
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Yaml,
+    Toml,
+}
+
+/// A markdown file split into frontmatter and body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Document {
+    pub format: Format,
+    bom: bool,
+    line_ending: &'static str,
+    open: String,
+    close: String,
+    /// Frontmatter lines, without line endings
+    lines: Vec<String>,
+    /// Everything after the closing delimiter, with `\n` line endings
+    pub body: String,
+}
+
+impl Document {
+    /// Split a file into frontmatter and body
+    pub fn parse(content: &str) -> Result<Self> {
+        let (bom, content) = match content.strip_prefix(BOM) {
+            Some(rest) => (true, rest),
+            None => (false, content),
+        };
+        let line_ending = if content.contains("\r\n") { "\r\n" } else { "\n" };
+
+        let mut lines = content.split('\n').map(|l| l.strip_suffix('\r').unwrap_or(l));
+
+        let open = lines.next().unwrap_or_default().trim_end();
+        let format = match open {
+            "---" => Format::Yaml,
+            "+++" => Format::Toml,
+            _ => anyhow::bail!("Missing frontmatter marker at line 1 (expected `---` or `+++`)"),
+        };
+
+        let mut frontmatter = Vec::new();
+        let mut close = None;
+        for line in lines.by_ref() {
+            let trimmed = line.trim_end();
+            let closes = match format {
+                Format::Yaml => trimmed == "---" || trimmed == "...",
+                Format::Toml => trimmed == "+++",
+            };
+            if closes {
+                close = Some(trimmed.to_string());
+                break;
+            }
+            frontmatter.push(line.to_string());
+        }
+        let close = close.with_context(|| {
+            format!("Missing frontmatter closing marker for the `{}` at line 1", open)
+        })?;
+
+        Ok(Self {
+            format,
+            bom,
+            line_ending,
+            open: open.to_string(),
+            close,
+            lines: frontmatter,
+            body: lines.collect::<Vec<_>>().join("\n"),
+        })
+    }
+
+    /// Deserialize the frontmatter
+    pub fn deserialize<T: DeserializeOwned>(&self) -> Result<T> {
+        match self.format {
+            Format::Yaml => serde_yaml::from_str(&self.lines.join("\n")).map_err(|e| yaml_error(&e)),
+            Format::Toml => {
+                let document = self.toml()?;
+                serde_yaml::from_value(yaml_from_toml(document.as_item()))
+                    .map_err(|e| anyhow!("Invalid TOML frontmatter: {}", e))
+            }
+        }
+    }
+
+    /// Set a top-level key in place, append it if missing, or remove it with `None`
+    ///
+    /// Fails only for TOML frontmatter that does not parse.
+    pub fn set(&mut self, key: &str, value: Option<&Value>) -> Result<()> {
+        if self.format == Format::Toml {
+            return self.set_toml(key, value);
+        }
+
+        let block = self.find_key(key);
+        let replacement = match value {
+            None => vec![],
+            Some(value) => {
+                let old = block.clone().map(|range| &self.lines[range]).unwrap_or_default();
+                yaml_block(key, value, old)
+            }
+        };
+
+        match block {
+            Some(range) => {
+                self.lines.splice(range, replacement);
+            }
+            None => self.lines.extend(replacement),
+        }
+        Ok(())
+    }
+
+    fn set_toml(&mut self, key: &str, value: Option<&Value>) -> Result<()> {
+        let mut document = self.toml()?;
+
+        let old_table = document
+            .get(key)
+            .and_then(Item::as_table)
+            .map(|table| (table.position(), table.is_dotted()));
+
+        match (value.and_then(toml_value), old_table) {
+            (None, _) => {
+                document.remove(key);
+            }
+            // Keep a `[key]` table (or dotted keys) where it was
+            (Some(toml_edit::Value::InlineTable(inline)), Some((position, dotted))) => {
+                let mut table = inline.into_table();
+                if let Some(position) = position {
+                    table.set_position(position);
+                }
+                table.set_dotted(dotted);
+                document[key] = Item::Table(table);
+            }
+            (Some(value), _) => match document.get_mut(key).and_then(Item::as_value_mut) {
+                // Keep the comment after the old value
+                Some(old) => {
+                    let suffix = old.decor().suffix().cloned();
+                    *old = value;
+                    if let Some(suffix) = suffix {
+                        old.decor_mut().set_suffix(suffix);
+                    }
+                }
+                None => document[key] = toml_edit::value(value),
+            },
+        }
+
+        let text = document.to_string();
+        let text = text.strip_suffix('\n').unwrap_or(&text);
+        self.lines = if text.is_empty() { vec![] } else { text.split('\n').map(str::to_string).collect() };
+        Ok(())
+    }
+
+    /// The TOML frontmatter, with errors located in the file
+    fn toml(&self) -> Result<DocumentMut> {
+        let text = self.lines.join("\n");
+        text.parse::<DocumentMut>().map_err(|e| {
+            let message = e.message().trim().replace('\n', ", ");
+            match e.span() {
+                Some(span) => {
+                    let before = &text[..span.start];
+                    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+                    // The frontmatter starts on line 2
+                    anyhow!(
+                        "Invalid TOML frontmatter at line {}, column {}: {}",
+                        before.matches('\n').count() + 2,
+                        before[line_start..].chars().count() + 1,
+                        message
+                    )
+                }
+                None => anyhow!("Invalid TOML frontmatter: {}", message),
+            }
+        })
+    }
+
+    /// Lines of a top-level YAML key and its continuation lines
+    ///
+    /// Blank lines belong to the key when more continuation lines follow,
+    /// as in block scalars with several paragraphs.
+    fn find_key(&self, key: &str) -> Option<std::ops::Range<usize>> {
+        let start = self
+            .lines
+            .iter()
+            .position(|line| line.strip_prefix(key).is_some_and(|rest| rest.starts_with(':')))?;
+
+        let mut end = start + 1;
+        let mut next = end;
+        while next < self.lines.len() {
+            let line = &self.lines[next];
+            if line.trim().is_empty() {
+                next += 1;
+            } else if is_yaml_continuation(line) {
+                next += 1;
+                end = next;
+            } else {
+                break;
+            }
+        }
+        Some(start..end)
+    }
+
+    /// The file as text, with the original BOM and line endings
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        if self.bom {
+            out.push(BOM);
+        }
+
+        let lines = std::iter::once(self.open.as_str())
+            .chain(self.lines.iter().map(String::as_str))
+            .chain(std::iter::once(self.close.as_str()))
+            .chain(self.body.split('\n'));
+        out.push_str(&lines.collect::<Vec<_>>().join(self.line_ending));
+        out
+    }
+}
+
+/// A serde_yaml error with the line and column in the file
+fn yaml_error(error: &serde_yaml::Error) -> anyhow::Error {
+    let message = error.to_string();
+    let message = match message.find(" at line ") {
+        Some(i) => &message[..i],
+        None => &message,
+    };
+
+    match error.location() {
+        // The frontmatter starts on line 2
+        Some(location) => anyhow!(
+            "Invalid YAML frontmatter at line {}, column {}: {}",
+            location.line() + 1,
+            location.column(),
+            message
+        ),
+        None => anyhow!("Invalid YAML frontmatter: {}", message),
+    }
+}
+
+fn is_yaml_continuation(line: &str) -> bool {
+    line.starts_with(' ') || line.starts_with('\t') || line.starts_with('-')
+}
+
+/// YAML lines for a key, keeping the flow or block style and indent of `old`
+fn yaml_block(key: &str, value: &Value, old: &[String]) -> Vec<String> {
+    match value {
+        Value::Sequence(items) if !items.is_empty() => {
+            let flow = old
+                .first()
+                .and_then(|line| line.split_once(':'))
+                .is_some_and(|(_, rest)| rest.trim_start().starts_with('['));
+
+            if flow {
+                let items: Vec<String> = items.iter().map(yaml_scalar).collect();
+                vec![format!("{}: [{}]", key, items.join(", "))]
+            } else {
+                let indent = old
+                    .get(1)
+                    .map(|line| &line[..line.len() - line.trim_start().len()])
+                    .unwrap_or("  ");
+                std::iter::once(format!("{}:", key))
+                    .chain(items.iter().map(|item| format!("{}- {}", indent, yaml_scalar(item))))
+                    .collect()
+            }
+        }
+        Value::Mapping(entries) if !entries.is_empty() => {
+            let flow = old
+                .first()
+                .and_then(|line| line.split_once(':'))
+                .is_some_and(|(_, rest)| rest.trim_start().starts_with('{'));
+
+            let pairs = entries.iter().map(|(k, v)| format!("{}: {}", yaml_scalar(k), yaml_scalar(v)));
+            if flow {
+                vec![format!("{}: {{{}}}", key, pairs.collect::<Vec<_>>().join(", "))]
+            } else {
+                let indent = old
+                    .get(1)
+                    .map(|line| &line[..line.len() - line.trim_start().len()])
+                    .unwrap_or("  ");
+                std::iter::once(format!("{}:", key))
+                    .chain(pairs.map(|pair| format!("{}{}", indent, pair)))
+                    .collect()
+            }
+        }
+        _ => vec![format!("{}: {}", key, yaml_scalar(value))],
+    }
+}
+
+fn yaml_scalar(value: &Value) -> String {
+    match value {
+        Value::Sequence(items) if items.is_empty() => "[]".to_string(),
+        Value::Mapping(entries) if entries.is_empty() => "{}".to_string(),
+        _ => serde_yaml::to_string(value).unwrap_or_default().trim_end().to_string(),
+    }
+}
+
+/// A YAML value as a TOML value; `None` for null
+fn toml_value(value: &Value) -> Option<toml_edit::Value> {
+    Some(match value {
+        Value::Null => return None,
+        Value::String(s) => s.as_str().into(),
+        Value::Bool(b) => (*b).into(),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => i.into(),
+            None => n.as_f64().unwrap_or_default().into(),
+        },
+        Value::Sequence(items) => items.iter().filter_map(toml_value).collect::<toml_edit::Array>().into(),
+        Value::Mapping(entries) => entries
+            .iter()
+            .filter_map(|(k, v)| Some((yaml_key(k), toml_value(v)?)))
+            .collect::<toml_edit::InlineTable>()
+            .into(),
+        Value::Tagged(tagged) => return toml_value(&tagged.value),
+    })
+}
+
+fn yaml_key(key: &Value) -> String {
+    match key.as_str() {
+        Some(k) => k.to_string(),
+        None => yaml_scalar(key),
+    }
+}
+
+/// A TOML item as a YAML value, with dates and times as strings
+fn yaml_from_toml(item: &toml_edit::Item) -> Value {
+    match item {
+        Item::None => Value::Null,
+        Item::Value(value) => yaml_from_toml_value(value),
+        Item::Table(table) => Value::Mapping(
+            table.iter().map(|(k, v)| (Value::from(k), yaml_from_toml(v))).collect(),
+        ),
+        Item::ArrayOfTables(tables) => Value::Sequence(
+            tables.iter().map(|table| yaml_from_toml(&Item::Table(table.clone()))).collect(),
+        ),
+    }
+}
+
+fn yaml_from_toml_value(value: &toml_edit::Value) -> Value {
+    match value {
+        toml_edit::Value::String(s) => Value::from(s.value().as_str()),
+        toml_edit::Value::Integer(i) => Value::from(*i.value()),
+        toml_edit::Value::Float(f) => Value::from(*f.value()),
+        toml_edit::Value::Boolean(b) => Value::from(*b.value()),
+        toml_edit::Value::Datetime(d) => Value::from(d.value().to_string()),
+        toml_edit::Value::Array(items) => Value::Sequence(items.iter().map(yaml_from_toml_value).collect()),
+        toml_edit::Value::InlineTable(table) => Value::Mapping(
+            table.iter().map(|(k, v)| (Value::from(k), yaml_from_toml_value(v))).collect(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_yaml::Mapping;
+
+    #[test]
+    fn test_crlf_bom_and_dot_terminator() {
+        let content = "\u{feff}---\r\ntitle: Test\r\n...\r\n\r\nBody\r\n";
+        let doc = Document::parse(content).unwrap();
+
+        assert_eq!(doc.format, Format::Yaml);
+        assert_eq!(doc.body, "\nBody\n");
+        assert_eq!(doc.render(), content);
+
+        // Closing marker at EOF without a newline
+        assert_eq!(Document::parse("---\ntitle: T\n---").unwrap().body, "");
+    }
+
+    #[test]
+    fn test_missing_markers() {
+        let error = Document::parse("title: T\n").unwrap_err().to_string();
+        assert!(error.contains("line 1"), "{}", error);
+        assert!(Document::parse("---\ntitle: T\n").is_err());
+        assert!(Document::parse("+++\ntitle = \"T\"\n---\n").is_err());
+    }
+
+    #[test]
+    fn test_yaml_error_location() {
+        let doc = Document::parse("---\ntitle: T\nlabels: [a\n---\nBody").unwrap();
+        let error = doc.deserialize::<Value>().unwrap_err().to_string();
+        assert!(error.starts_with("Invalid YAML frontmatter at line 4, column"), "{}", error);
+        assert!(!error.contains(" at line 3"), "{}", error);
+    }
+
+    #[test]
+    fn test_toml() {
+        let doc = Document::parse(
+            "+++\n# comment\ntitle = \"Say \\\"hi\\\"\"\nparent = 40\nlabels = [\n  'bug', # first\n  \"p1\",\n]\n+++\nBody",
+        )
+        .unwrap();
+        let value: Value = doc.deserialize().unwrap();
+
+        assert_eq!(value["title"], Value::from("Say \"hi\""));
+        assert_eq!(value["parent"], Value::from(40));
+        assert_eq!(value["labels"], Value::Sequence(vec!["bug".into(), "p1".into()]));
+
+        let error = Document::parse("+++\ntitle = \"T\"\nparent = forty\n+++\n")
+            .unwrap()
+            .deserialize::<Value>()
+            .unwrap_err()
+            .to_string();
+        assert!(error.starts_with("Invalid TOML frontmatter at line 3, column 10: "), "{}", error);
+    }
+
+    #[test]
+    fn test_toml_tables_dates_and_multiline_strings() {
+        let doc = Document::parse(
+            "+++\ntitle = \"T\"\nclosed_at = 2025-01-05\nproject.status = \"Todo\"\nbody = \"\"\"\nline 1\nline 2\"\"\"\n\n[extra]\nsize = 1.5\n+++\n",
+        )
+        .unwrap();
+        let value: Value = doc.deserialize().unwrap();
+
+        assert_eq!(value["closed_at"], Value::from("2025-01-05"));
+        assert_eq!(value["project"]["status"], Value::from("Todo"));
+        assert_eq!(value["body"], Value::from("line 1\nline 2"));
+        assert_eq!(value["extra"]["size"], Value::from(1.5));
+    }
+
+    #[test]
+    fn test_set_toml_keeps_tables_and_comments() {
+        let mut doc = Document::parse(
+            "+++\ntitle = \"Old\" # inline\nproject.status = \"Todo\"\n\n[extra]\nsize = 1\n+++\nBody",
+        )
+        .unwrap();
+
+        let mut table = Mapping::new();
+        table.insert("status".into(), "Done".into());
+        doc.set("title", Some(&Value::from("New"))).unwrap();
+        doc.set("project", Some(&Value::Mapping(table))).unwrap();
+        doc.set("milestone", Some(&Value::from("v1"))).unwrap();
+        doc.set("extra", None).unwrap();
+
+        assert_eq!(
+            doc.render(),
+            "+++\ntitle = \"New\" # inline\nproject.status = \"Done\"\nmilestone = \"v1\"\n+++\nBody"
+        );
+        assert!(Document::parse("+++\ntitle = \n+++\n").unwrap().set("title", None).is_err());
+    }
+
+    #[test]
+    fn test_set_keeps_formatting() {
+        let content = "---\r\n# Keep me\r\ntitle: Old # inline\r\nlabels:\r\n    - a\r\n    - b\r\ncustom: x\r\n---\r\nBody\r\n";
+        let mut doc = Document::parse(content).unwrap();
+
+        doc.set("title", Some(&Value::from("New: title"))).unwrap();
+        doc.set("labels", Some(&Value::Sequence(vec!["c".into()]))).unwrap();
+        doc.set("milestone", Some(&Value::from("v1"))).unwrap();
+        doc.set("custom", None).unwrap();
+
+        assert_eq!(
+            doc.render(),
+            "---\r\n# Keep me\r\ntitle: 'New: title'\r\nlabels:\r\n    - c\r\nmilestone: v1\r\n---\r\nBody\r\n"
+        );
+    }
+
+    #[test]
+    fn test_set_yaml_block_scalar_with_blank_lines() {
+        let mut doc = Document::parse("---\nbody: |\n  First\n\n  Second\n\nlabels: [a]\n---\n").unwrap();
+        doc.set("body", Some(&Value::from("New"))).unwrap();
+        assert_eq!(doc.render(), "---\nbody: New\n\nlabels: [a]\n---\n");
+    }
+
+    #[test]
+    fn test_set_toml() {
+        let mut doc = Document::parse("+++\ntitle = \"Old\"\nlabels = [\n  \"a\",\n]\nx = 1\n+++\n").unwrap();
+        doc.set("labels", Some(&Value::Sequence(vec!["b".into()]))).unwrap();
+        doc.set("title", Some(&Value::from("New"))).unwrap();
+
+        assert_eq!(doc.render(), "+++\ntitle = \"New\"\nlabels = [\"b\"]\nx = 1\n+++\n");
+    }
+
+    #[test]
+    fn test_tables() {
+        let doc = Document::parse("+++\nproject = { status = \"In Progress\", \"story points\" = 3 }\n+++\n").unwrap();
+        let value: Value = doc.deserialize().unwrap();
+        assert_eq!(value["project"]["status"], Value::from("In Progress"));
+        assert_eq!(value["project"]["story points"], Value::from(3));
+
+        let mut table = Mapping::new();
+        table.insert("status".into(), "Done".into());
+        table.insert("story points".into(), 5.into());
+        let mut doc = doc;
+        doc.set("project", Some(&Value::Mapping(table.clone()))).unwrap();
+        assert_eq!(doc.render(), "+++\nproject = { status = \"Done\", \"story points\" = 5 }\n+++\n");
+
+        // YAML keeps block or flow style
+        let mut doc = Document::parse("---\nproject:\n    status: Todo\n---\n").unwrap();
+        doc.set("project", Some(&Value::Mapping(table.clone()))).unwrap();
+        assert_eq!(doc.render(), "---\nproject:\n    status: Done\n    story points: 5\n---\n");
+
+        let mut doc = Document::parse("---\nproject: {status: Todo}\n---\n").unwrap();
+        doc.set("project", Some(&Value::Mapping(table))).unwrap();
+        assert_eq!(doc.render(), "---\nproject: {status: Done, story points: 5}\n---\n");
+    }
+}
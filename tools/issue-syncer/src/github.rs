@@ -1,10 +1,41 @@
 //! GitHub API Wrapper
 //!
 //! Simplified interface for GitHub Issues operations using octocrab.
+//!
+//! Every request, including each page of a list, goes through a shared
+//! `RateLimiter`, so concurrent sync jobs slow down instead of tripping
+//! GitHub's limits.
 
 use anyhow::{Context, Result};
-use octocrab::{models::issues::Issue, Octocrab};
-use crate::parser::IssueData;
+use chrono::{DateTime, SecondsFormat, Utc};
+use octocrab::models::issues::{Comment, Issue};
+use octocrab::models::Milestone;
+use octocrab::models::IssueState as RemoteState;
+use octocrab::models::issues::IssueStateReason;
+use octocrab::Octocrab;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::sync::Arc;
+use tracing::warn;
+use crate::comments::CommentBlock;
+use crate::import::{ImportFilter, MilestoneFilter, StateFilter};
+use crate::mapping::FieldChanges;
+use crate::parser::{IssueData, IssueState, StateReason};
+use crate::projects::{Project, ProjectValues};
+use crate::ratelimit::{is_rate_limited, RateLimitHeaders, RateLimiter, MAX_RETRIES};
+
+/// Items per page of list requests
+const PER_PAGE: usize = 100;
+
+/// HTTP method of a rate-limited request
+#[derive(Debug, Clone, Copy)]
+enum Method {
+    Get,
+    Post,
+    Patch,
+    Delete,
+}
 
 /// GitHub client wrapper
 #[derive(Clone)]
@@ -12,6 +43,7 @@ pub struct GitHubClient {
     client: Octocrab,
     owner: String,
     repo: String,
+    limiter: Arc<RateLimiter>,
 }
 
 impl GitHubClient {
@@ -21,109 +53,609 @@ impl GitHubClient {
             client,
             owner,
             repo,
+            limiter: Arc::default(),
+        }
+    }
+
+    /// Create a new issue with every field of `data`
+    pub async fn create_issue(&self, data: &IssueData) -> Result<Issue> {
+        let route = format!("/repos/{}/{}/issues", self.owner, self.repo);
+
+        // Issues are always created open; the state is set afterwards
+        let mut fields = FieldChanges::all();
+        fields.state = false;
+        let payload = self.issue_payload(data, &fields).await?;
+
+        let mut issue: Issue = self
+            .request(Method::Post, &route, Some(&payload))
+            .await
+            .context("Failed to create GitHub issue")?;
+
+        if !data.state.is_open() {
+            let state = FieldChanges { state: true, ..Default::default() };
+            issue = self.patch_issue(issue.number, &self.issue_payload(data, &state).await?).await?;
         }
+
+        self.update_relations(&issue, data, &IssueData::default(), &FieldChanges::all())
+            .await?;
+
+        Ok(issue)
     }
 
-    /// Create a new issue
-    pub async fn create_issue(&self, data: &IssueData) -> Result<u64> {
-        let issues = self.client.issues(&self.owner, &self.repo);
+    /// Update the changed fields of an existing issue
+    ///
+    /// `current` is the issue as it is on GitHub, needed to remove the old
+    /// parent and projects. Cleared fields are sent as empty or `null`.
+    pub async fn update_issue(
+        &self,
+        number: u64,
+        data: &IssueData,
+        changes: &FieldChanges,
+        current: &IssueData,
+    ) -> Result<Issue> {
+        let payload = self.issue_payload(data, changes).await?;
+
+        let issue = if payload.is_empty() {
+            self.get_issue(number).await?
+        } else {
+            self.patch_issue(number, &payload).await?
+        };
+
+        self.update_relations(&issue, data, current, changes).await?;
+
+        Ok(issue)
+    }
+
+    async fn patch_issue(&self, number: u64, payload: &Map<String, Value>) -> Result<Issue> {
+        let route = format!("/repos/{}/{}/issues/{}", self.owner, self.repo, number);
+        self.request(Method::Patch, &route, Some(payload))
+            .await
+            .context("Failed to update GitHub issue")
+    }
+
+    /// REST fields for the changed values of `data`
+    async fn issue_payload(&self, data: &IssueData, changes: &FieldChanges) -> Result<Map<String, Value>> {
+        let mut payload = Map::new();
+
+        if changes.title {
+            payload.insert("title".into(), json!(data.title));
+        }
+        if changes.body {
+            payload.insert("body".into(), json!(data.body));
+        }
+        // An empty list clears labels / assignees
+        if changes.labels {
+            payload.insert("labels".into(), json!(data.labels));
+        }
+        if changes.assignees {
+            payload.insert("assignees".into(), json!(data.assignees));
+        }
+        if changes.milestone {
+            let milestone = match &data.milestone {
+                Some(title) => json!(self.milestone_number(title).await?),
+                None => Value::Null,
+            };
+            payload.insert("milestone".into(), milestone);
+        }
+        if changes.state {
+            payload.insert("state".into(), json!(data.state));
+            if !data.state.is_open() {
+                payload.insert("state_reason".into(), json!(data.state_reason));
+            }
+        }
+        if changes.issue_type {
+            payload.insert("type".into(), json!(data.issue_type));
+        }
 
-        let mut builder = issues
-            .create(&data.title)
-            .body(&data.body);
+        Ok(payload)
+    }
 
-        // Add labels if present
-        if !data.labels.is_empty() {
-            builder = builder.labels(data.labels.clone());
+    /// Move the issue to its new parent and projects
+    async fn update_relations(
+        &self,
+        issue: &Issue,
+        data: &IssueData,
+        current: &IssueData,
+        changes: &FieldChanges,
+    ) -> Result<()> {
+        if changes.parent && data.parent != current.parent {
+            let body = json!({ "sub_issue_id": issue.id, "replace_parent": true });
+            match (data.parent, current.parent) {
+                (Some(parent), _) => {
+                    let route = format!("/repos/{}/{}/issues/{}/sub_issues", self.owner, self.repo, parent);
+                    let _: Value = self
+                        .request(Method::Post, &route, Some(&body))
+                        .await
+                        .with_context(|| format!("Failed to add #{} as a sub-issue of #{}", issue.number, parent))?;
+                }
+                (None, Some(old)) => {
+                    let route = format!("/repos/{}/{}/issues/{}/sub_issue", self.owner, self.repo, old);
+                    let _: Value = self
+                        .request(Method::Delete, &route, Some(&json!({ "sub_issue_id": issue.id })))
+                        .await
+                        .with_context(|| format!("Failed to remove #{} from parent #{}", issue.number, old))?;
+                }
+                (None, None) => {}
+            }
         }
 
-        // Add assignees if present
-        if !data.assignees.is_empty() {
-            builder = builder.assignees(data.assignees.clone());
+        if changes.projects {
+            for project in data.projects.iter().filter(|p| !current.projects.contains(p)) {
+                self.add_to_project(*project, &issue.node_id).await?;
+            }
+            for project in current.projects.iter().filter(|p| !data.projects.contains(p)) {
+                self.remove_from_project(*project, &issue.node_id).await?;
+            }
         }
 
-        let issue = builder
-            .send()
+        Ok(())
+    }
+
+    /// Node id of a Projects (v2) board of the repository owner
+    async fn project_id(&self, number: u64) -> Result<String> {
+        let data = self
+            .graphql(
+                "query($owner: String!, $number: Int!) {
+                    repositoryOwner(login: $owner) {
+                        ... on Organization { projectV2(number: $number) { id } }
+                        ... on User { projectV2(number: $number) { id } }
+                    }
+                }",
+                json!({ "owner": self.owner, "number": number }),
+            )
+            .await?;
+
+        data["repositoryOwner"]["projectV2"]["id"]
+            .as_str()
+            .map(str::to_string)
+            .with_context(|| format!("Project {} not found for {}", number, self.owner))
+    }
+
+    async fn add_to_project(&self, project: u64, issue_node_id: &str) -> Result<()> {
+        let project_id = self.project_id(project).await?;
+        self.add_project_item(&project_id, issue_node_id)
             .await
-            .context("Failed to create GitHub issue")?;
+            .with_context(|| format!("Failed to add issue to project {}", project))?;
+        Ok(())
+    }
+
+    /// Add an issue to a board (a no-op if it is on it) and return the item id
+    async fn add_project_item(&self, project_id: &str, issue_node_id: &str) -> Result<String> {
+        let data = self
+            .graphql(
+                "mutation($project: ID!, $content: ID!) {
+                    addProjectV2ItemById(input: {projectId: $project, contentId: $content}) { item { id } }
+                }",
+                json!({ "project": project_id, "content": issue_node_id }),
+            )
+            .await?;
 
-        Ok(issue.number)
+        data["addProjectV2ItemById"]["item"]["id"]
+            .as_str()
+            .map(str::to_string)
+            .context("GraphQL response has no project item id")
     }
 
-    /// Update an existing issue
-    pub async fn update_issue(&self, number: u64, data: &IssueData) -> Result<()> {
-        let issues = self.client.issues(&self.owner, &self.repo);
+    /// Fetch a Projects (v2) board with its fields and the items of this repository
+    pub async fn fetch_project(&self, number: u64) -> Result<Project> {
+        let data = self
+            .graphql(
+                "query($owner: String!, $number: Int!) {
+                    repositoryOwner(login: $owner) {
+                        ... on Organization { projectV2(number: $number) { ...board } }
+                        ... on User { projectV2(number: $number) { ...board } }
+                    }
+                }
+                fragment board on ProjectV2 {
+                    id
+                    fields(first: 100) {
+                        nodes {
+                            ... on ProjectV2FieldCommon { id name dataType }
+                            ... on ProjectV2SingleSelectField { options { id name } }
+                            ... on ProjectV2IterationField {
+                                configuration {
+                                    iterations { id title startDate duration }
+                                    completedIterations { id title startDate duration }
+                                }
+                            }
+                        }
+                    }
+                }",
+                json!({ "owner": self.owner, "number": number }),
+            )
+            .await
+            .with_context(|| format!("Failed to fetch project {}", number))?;
+
+        let node = &data["repositoryOwner"]["projectV2"];
+        if node.is_null() {
+            anyhow::bail!("Project {} not found for {}", number, self.owner);
+        }
+        let mut project = Project::from_graphql(number, node)?;
+
+        let repository = format!("{}/{}", self.owner, self.repo);
+        let mut cursor = Value::Null;
+        loop {
+            let data = self
+                .graphql(
+                    "query($id: ID!, $cursor: String) {
+                        node(id: $id) {
+                            ... on ProjectV2 {
+                                items(first: 100, after: $cursor) {
+                                    pageInfo { hasNextPage endCursor }
+                                    nodes {
+                                        id
+                                        content { ... on Issue { number repository { nameWithOwner } } }
+                                        fieldValues(first: 50) {
+                                            nodes {
+                                                ... on ProjectV2ItemFieldSingleSelectValue { name field { ... on ProjectV2FieldCommon { name } } }
+                                                ... on ProjectV2ItemFieldIterationValue { title field { ... on ProjectV2FieldCommon { name } } }
+                                                ... on ProjectV2ItemFieldNumberValue { number field { ... on ProjectV2FieldCommon { name } } }
+                                                ... on ProjectV2ItemFieldTextValue { text field { ... on ProjectV2FieldCommon { name } } }
+                                                ... on ProjectV2ItemFieldDateValue { date field { ... on ProjectV2FieldCommon { name } } }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }",
+                    json!({ "id": project.id, "cursor": cursor }),
+                )
+                .await
+                .with_context(|| format!("Failed to fetch items of project {}", number))?;
+
+            let items = &data["node"]["items"];
+            project.add_items(items["nodes"].as_array().map(Vec::as_slice).unwrap_or_default(), &repository);
+
+            if items["pageInfo"]["hasNextPage"].as_bool() != Some(true) {
+                break;
+            }
+            cursor = items["pageInfo"]["endCursor"].clone();
+        }
+
+        Ok(project)
+    }
 
-        let mut builder = issues
-            .update(number)
-            .title(&data.title)
-            .body(&data.body);
+    /// Set the changed project field values of an issue, adding it to the board if needed
+    ///
+    /// Keys in `current` but not in `values` are cleared.
+    pub async fn update_project_fields(
+        &self,
+        project: &Project,
+        issue: &Issue,
+        values: &ProjectValues,
+        current: &ProjectValues,
+    ) -> Result<()> {
+        let item_id = match project.items.get(&issue.number) {
+            Some(item) => item.id.clone(),
+            None => self
+                .add_project_item(&project.id, &issue.node_id)
+                .await
+                .with_context(|| format!("Failed to add issue #{} to project {}", issue.number, project.number))?,
+        };
+        let today = Utc::now().date_naive();
 
-        // Update labels
-        if !data.labels.is_empty() {
-            builder = builder.labels(&data.labels);
+        for (key, value) in values.iter().filter(|(k, v)| current.get(*k) != Some(*v)) {
+            let field = project.field(key)?;
+            self.graphql(
+                "mutation($project: ID!, $item: ID!, $field: ID!, $value: ProjectV2FieldValue!) {
+                    updateProjectV2ItemFieldValue(
+                        input: {projectId: $project, itemId: $item, fieldId: $field, value: $value}
+                    ) { projectV2Item { id } }
+                }",
+                json!({ "project": project.id, "item": item_id, "field": field.id, "value": field.input(value, today)? }),
+            )
+            .await
+            .with_context(|| format!("Failed to set `{}` of issue #{}", key, issue.number))?;
         }
 
-        builder
-            .send()
+        for key in current.keys().filter(|k| !values.contains_key(*k)) {
+            let field = project.field(key)?;
+            self.graphql(
+                "mutation($project: ID!, $item: ID!, $field: ID!) {
+                    clearProjectV2ItemFieldValue(
+                        input: {projectId: $project, itemId: $item, fieldId: $field}
+                    ) { projectV2Item { id } }
+                }",
+                json!({ "project": project.id, "item": item_id, "field": field.id }),
+            )
             .await
-            .context("Failed to update GitHub issue")?;
+            .with_context(|| format!("Failed to clear `{}` of issue #{}", key, issue.number))?;
+        }
+
+        Ok(())
+    }
+
+    async fn remove_from_project(&self, project: u64, issue_node_id: &str) -> Result<()> {
+        let project_id = self.project_id(project).await?;
+        let data = self
+            .graphql(
+                "query($id: ID!) {
+                    node(id: $id) {
+                        ... on Issue { projectItems(first: 100) { nodes { id project { id } } } }
+                    }
+                }",
+                json!({ "id": issue_node_id }),
+            )
+            .await?;
 
+        let items = data["node"]["projectItems"]["nodes"].as_array().cloned().unwrap_or_default();
+        let Some(item) = items.iter().find(|i| i["project"]["id"] == project_id.as_str()) else {
+            return Ok(());
+        };
+
+        self.graphql(
+            "mutation($project: ID!, $item: ID!) {
+                deleteProjectV2Item(input: {projectId: $project, itemId: $item}) { deletedItemId }
+            }",
+            json!({ "project": project_id, "item": item["id"] }),
+        )
+        .await
+        .with_context(|| format!("Failed to remove issue from project {}", project))?;
         Ok(())
     }
 
-    /// Fetch all closed issues
+    /// Run a GraphQL query and return its `data`
+    ///
+    /// Queries failing with `RATE_LIMITED` are retried after a backoff.
+    async fn graphql(&self, query: &str, variables: Value) -> Result<Value> {
+        let payload = json!({ "query": query, "variables": variables });
+        let mut attempt = 0;
+        loop {
+            let response: Value = self
+                .request(Method::Post, "/graphql", Some(&payload))
+                .await
+                .context("GraphQL request failed")?;
+
+            let Some(errors) = response.get("errors").filter(|e| !e.is_null()) else {
+                return Ok(response["data"].clone());
+            };
+            let limited = errors
+                .as_array()
+                .is_some_and(|errors| errors.iter().any(|e| e["type"] == "RATE_LIMITED"));
+            if limited && attempt < MAX_RETRIES {
+                if let Some(wait) = self.limiter.observe(&RateLimitHeaders::default(), true, attempt) {
+                    warn!("GraphQL rate limit hit, retrying in {:.1}s", wait.as_secs_f64());
+                }
+                attempt += 1;
+                continue;
+            }
+            anyhow::bail!("GraphQL error: {}", errors);
+        }
+    }
+
+    /// Send a request once the rate limiter allows it, retrying it while rate limited
+    async fn request<B, T>(&self, method: Method, route: &str, body: Option<&B>) -> Result<T>
+    where
+        B: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        let mut attempt = 0;
+        loop {
+            self.limiter.wait().await;
+            let response = match method {
+                Method::Get => self.client._get(route).await,
+                Method::Post => self.client._post(route, body).await,
+                Method::Patch => self.client._patch(route, body).await,
+                Method::Delete => self.client._delete(route, body).await,
+            }?;
+
+            let headers = RateLimitHeaders::parse(|name| response.headers().get(name)?.to_str().ok());
+            let status = response.status().as_u16();
+
+            if status == 403 || status == 429 {
+                let text = self.client.body_to_string(response).await?;
+                let limited = is_rate_limited(status, &headers, &text);
+                if let Some(wait) = self.limiter.observe(&headers, limited, attempt) {
+                    if attempt < MAX_RETRIES {
+                        warn!(
+                            "Rate limited on {:?} {} (HTTP {}), retrying in {:.1}s",
+                            method, route, status, wait.as_secs_f64()
+                        );
+                        attempt += 1;
+                        continue;
+                    }
+                }
+                let message = serde_json::from_str::<Value>(&text)
+                    .ok()
+                    .and_then(|v| v["message"].as_str().map(str::to_string))
+                    .unwrap_or(text);
+                anyhow::bail!("GitHub API error (HTTP {}): {}", status, message);
+            }
+
+            self.limiter.observe(&headers, false, attempt);
+            let response = octocrab::map_github_error(response).await?;
+            let text = self.client.body_to_string(response).await?;
+            let text = if text.trim().is_empty() { "null" } else { text.as_str() };
+            return serde_json::from_str(text).context("Invalid GitHub API response");
+        }
+    }
+
+    /// Fetch all closed issues (all pages, PRs excluded)
     pub async fn fetch_closed_issues(&self) -> Result<Vec<Issue>> {
-        let issues = self
-            .client
-            .issues(&self.owner, &self.repo)
-            .list()
-            .state(octocrab::params::State::Closed)
-            .per_page(100)
-            .send()
+        let filter = ImportFilter {
+            state: StateFilter::Closed,
+            ..Default::default()
+        };
+        self.fetch_issues(&filter).await
+    }
+
+    /// Fetch issues of any state updated at or after `since` (all pages, PRs excluded)
+    pub async fn fetch_issues_updated_since(&self, since: Option<DateTime<Utc>>) -> Result<Vec<Issue>> {
+        let mut query = vec![("state", "all".to_string())];
+        if let Some(since) = since {
+            query.push(("since", since.to_rfc3339_opts(SecondsFormat::Secs, true)));
+        }
+
+        let route = format!("/repos/{}/{}/issues", self.owner, self.repo);
+        let issues: Vec<Issue> = self
+            .get_all(&route, &query)
             .await
-            .context("Failed to fetch closed issues")?;
+            .context("Failed to fetch updated issues")?;
 
-        Ok(issues.items)
+        Ok(issues.into_iter().filter(|i| i.pull_request.is_none()).collect())
     }
 
-    /// Fetch all open issues
+    /// Fetch all open issues (all pages, PRs excluded)
     pub async fn fetch_open_issues(&self) -> Result<Vec<Issue>> {
-        let issues = self
-            .client
-            .issues(&self.owner, &self.repo)
-            .list()
-            .state(octocrab::params::State::Open)
-            .per_page(100)
-            .send()
+        self.fetch_issues(&ImportFilter::default()).await
+    }
+
+    /// Fetch the issues matching an import filter (all pages, PRs excluded)
+    pub async fn fetch_issues(&self, filter: &ImportFilter) -> Result<Vec<Issue>> {
+        let state = match filter.state {
+            StateFilter::Open => "open",
+            StateFilter::Closed => "closed",
+            StateFilter::All => "all",
+        };
+        let mut query = vec![("state", state.to_string())];
+        if !filter.labels.is_empty() {
+            query.push(("labels", filter.labels.join(",")));
+        }
+        match &filter.milestone {
+            None => {}
+            Some(MilestoneFilter::Number(number)) => query.push(("milestone", number.to_string())),
+            Some(MilestoneFilter::Title(title)) => query.push(("milestone", self.milestone_number(title).await?.to_string())),
+            Some(MilestoneFilter::Any) => query.push(("milestone", "*".to_string())),
+            Some(MilestoneFilter::None) => query.push(("milestone", "none".to_string())),
+        }
+
+        let route = format!("/repos/{}/{}/issues", self.owner, self.repo);
+        let issues: Vec<Issue> = self
+            .get_all(&route, &query)
+            .await
+            .context("Failed to fetch issues")?;
+
+        Ok(issues.into_iter().filter(|i| i.pull_request.is_none()).collect())
+    }
+
+    /// Number of the milestone with the given title (any state)
+    async fn milestone_number(&self, title: &str) -> Result<u64> {
+        let route = format!("/repos/{}/{}/milestones", self.owner, self.repo);
+        let milestones: Vec<Milestone> = self
+            .get_all(&route, &[("state", "all".to_string())])
+            .await
+            .context("Failed to fetch milestones")?;
+
+        milestones
+            .into_iter()
+            .find(|m| m.title.eq_ignore_ascii_case(title))
+            .map(|m| m.number as u64)
+            .with_context(|| format!("Milestone not found: {}", title))
+    }
+
+    /// Fetch the comments of an issue, oldest first
+    ///
+    /// With `since`, only comments created or edited at or after that time.
+    pub async fn fetch_comments(&self, number: u64, since: Option<DateTime<Utc>>) -> Result<Vec<Comment>> {
+        let query: Vec<(&str, String)> = since
+            .map(|since| ("since", since.to_rfc3339_opts(SecondsFormat::Secs, true)))
+            .into_iter()
+            .collect();
+
+        let route = format!("/repos/{}/{}/issues/{}/comments", self.owner, self.repo, number);
+        self.get_all(&route, &query)
             .await
-            .context("Failed to fetch open issues")?;
+            .context("Failed to fetch issue comments")
+    }
 
-        Ok(issues.items)
+    /// Every page of a list endpoint, each requested through the rate limiter
+    async fn get_all<T: DeserializeOwned>(&self, route: &str, query: &[(&str, String)]) -> Result<Vec<T>> {
+        let mut items = Vec::new();
+        for page in 1.. {
+            let mut url = format!("{}?per_page={}&page={}", route, PER_PAGE, page);
+            for (key, value) in query {
+                url.push_str(&format!("&{}={}", key, encode_query_value(value)));
+            }
+
+            let batch: Vec<T> = self.request(Method::Get, &url, None::<&()>).await?;
+            let last = batch.len() < PER_PAGE;
+            items.extend(batch);
+            if last {
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    /// Post a comment on an issue
+    pub async fn create_comment(&self, number: u64, body: &str) -> Result<Comment> {
+        let route = format!("/repos/{}/{}/issues/{}/comments", self.owner, self.repo, number);
+        self.request(Method::Post, &route, Some(&json!({ "body": body })))
+            .await
+            .with_context(|| format!("Failed to comment on issue #{}", number))
     }
 
     /// Check if an issue exists and is open
     pub async fn is_issue_open(&self, number: u64) -> Result<bool> {
-        let issue = self
-            .client
-            .issues(&self.owner, &self.repo)
-            .get(number)
-            .await
-            .context("Failed to fetch issue")?;
+        let issue = self.get_issue(number).await?;
 
         Ok(issue.state == octocrab::models::IssueState::Open)
     }
 
     /// Get issue by number
     pub async fn get_issue(&self, number: u64) -> Result<Issue> {
-        self.client
-            .issues(&self.owner, &self.repo)
-            .get(number)
+        let route = format!("/repos/{}/{}/issues/{}", self.owner, self.repo, number);
+        self.request(Method::Get, &route, None::<&()>)
             .await
             .context("Failed to fetch issue")
     }
 }
 
+/// Percent-encode a query string value (everything but unreserved characters)
+fn encode_query_value(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+impl From<&Issue> for IssueData {
+    /// The synced fields of a remote issue
+    ///
+    /// The push-only fields (`type`, `parent`, `projects`) and the board
+    /// fields (`project`) are left empty.
+    fn from(issue: &Issue) -> Self {
+        let state = match issue.state {
+            RemoteState::Closed => IssueState::Closed,
+            _ => IssueState::Open,
+        };
+        let state_reason = match issue.state_reason {
+            Some(IssueStateReason::Completed) => Some(StateReason::Completed),
+            Some(IssueStateReason::NotPlanned) => Some(StateReason::NotPlanned),
+            Some(IssueStateReason::Reopened) => Some(StateReason::Reopened),
+            _ => None,
+        };
+
+        Self {
+            title: issue.title.clone(),
+            labels: issue.labels.iter().map(|l| l.name.clone()).collect(),
+            assignees: issue.assignees.iter().map(|a| a.login.clone()).collect(),
+            milestone: issue.milestone.as_ref().map(|m| m.title.clone()),
+            state,
+            state_reason,
+            closed_at: issue.closed_at.map(|t| t.format("%Y-%m-%d").to_string()),
+            number: Some(issue.number),
+            body: issue.body.as_deref().unwrap_or_default().trim().to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<&Comment> for CommentBlock {
+    fn from(comment: &Comment) -> Self {
+        Self {
+            id: Some(comment.id.into_inner()),
+            author: Some(comment.user.login.clone()),
+            created_at: Some(comment.created_at.to_rfc3339()),
+            body: comment.body.as_deref().unwrap_or_default().trim().to_string(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -143,4 +675,34 @@ mod tests {
         assert_eq!(github.owner, "owner");
         assert_eq!(github.repo, "repo");
     }
+
+    #[tokio::test]
+    async fn test_get_all_pages() {
+        use mockito::Matcher;
+
+        let mut server = mockito::Server::new_async().await;
+        let mut page = |number: &str, items: usize| {
+            server
+                .mock("GET", "/repos/owner/repo/issues")
+                .match_query(Matcher::AllOf(vec![
+                    Matcher::UrlEncoded("page".into(), number.into()),
+                    Matcher::UrlEncoded("labels".into(), "good first issue,bug".into()),
+                ]))
+                .with_body(serde_json::to_string(&vec![0; items]).unwrap())
+        };
+        let first = page("1", PER_PAGE).create_async().await;
+        let second = page("2", 1).create_async().await;
+
+        let client = Octocrab::builder().base_uri(server.url()).unwrap().build().unwrap();
+        let github = GitHubClient::new(client, "owner".to_string(), "repo".to_string());
+        let items: Vec<u8> = github
+            .get_all("/repos/owner/repo/issues", &[("labels", "good first issue,bug".to_string())])
+            .await
+            .unwrap();
+
+        assert_eq!(items.len(), PER_PAGE + 1);
+        first.assert_async().await;
+        second.assert_async().await;
+        assert_eq!(encode_query_value("2025-01-05T10:00:00+01:00"), "2025-01-05T10%3A00%3A00%2B01%3A00");
+    }
 }
@@ -0,0 +1,172 @@
+//! Issue Import
+//!
+//! Bootstraps `.github/issues/` from issues that already exist on GitHub.
+//! Each issue becomes a `TYPE_slug.md` file, with the type taken from its
+//! labels:
+//!
+//! | Label | Prefix |
+//! |-------|--------|
+//! | `bug` | `BUG_` |
+//! | `documentation`, `docs` | `DOCS_` |
+//! | `refactor`, `refactoring` | `REFACTOR_` |
+//! | `test`, `testing`, `tests` | `TEST_` |
+//! | `chore`, `maintenance`, `dependencies` | `CHORE_` |
+//! | anything else | `FEAT_` |
+//!
+//! Recent comments can be added as a comments section (see
+//! [`crate::comments`]), which the parser strips so it is never pushed back
+//! as part of the body.
+
+use anyhow::Result;
+use std::collections::HashSet;
+
+/// Longest slug taken from an issue title
+const MAX_SLUG_LEN: usize = 50;
+
+/// Which issue states to import
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StateFilter {
+    #[default]
+    Open,
+    Closed,
+    All,
+}
+
+impl std::str::FromStr for StateFilter {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "open" => Ok(StateFilter::Open),
+            "closed" => Ok(StateFilter::Closed),
+            "all" => Ok(StateFilter::All),
+            _ => Err(anyhow::anyhow!("Invalid state: {} (expected open, closed or all)", s)),
+        }
+    }
+}
+
+/// Milestone to import issues from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MilestoneFilter {
+    /// Milestone number
+    Number(u64),
+    /// Milestone title, resolved to its number
+    Title(String),
+    /// Issues with any milestone (`*`)
+    Any,
+    /// Issues without a milestone (`none`)
+    None,
+}
+
+impl std::str::FromStr for MilestoneFilter {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if s.is_empty() {
+            anyhow::bail!("Milestone must not be empty");
+        }
+        Ok(match s {
+            "*" => MilestoneFilter::Any,
+            _ if s.eq_ignore_ascii_case("none") => MilestoneFilter::None,
+            _ => match s.parse::<u64>() {
+                Ok(number) => MilestoneFilter::Number(number),
+                Err(_) => MilestoneFilter::Title(s.to_string()),
+            },
+        })
+    }
+}
+
+/// Which remote issues to import
+#[derive(Debug, Clone, Default)]
+pub struct ImportFilter {
+    /// Issues must have all of these labels
+    pub labels: Vec<String>,
+    pub milestone: Option<MilestoneFilter>,
+    pub state: StateFilter,
+}
+
+/// File prefix for an issue's labels (`FEAT` when no label names a type)
+pub fn type_prefix(labels: &[String]) -> &'static str {
+    for label in labels {
+        let prefix = match label.to_lowercase().as_str() {
+            "bug" => "BUG",
+            "documentation" | "docs" => "DOCS",
+            "refactor" | "refactoring" => "REFACTOR",
+            "test" | "testing" | "tests" => "TEST",
+            "chore" | "maintenance" | "dependencies" => "CHORE",
+            "feature" | "enhancement" => "FEAT",
+            _ => continue,
+        };
+        return prefix;
+    }
+    "FEAT"
+}
+
+/// Lowercase, dash-separated slug of a title
+pub fn slugify(title: &str) -> String {
+    let mut slug = String::new();
+    for c in title.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+        if slug.chars().count() >= MAX_SLUG_LEN {
+            break;
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// `TYPE_slug.md` for an issue, suffixed with the number if the name is taken
+pub fn import_filename(number: u64, title: &str, labels: &[String], taken: &HashSet<String>) -> String {
+    let prefix = type_prefix(labels);
+    let slug = slugify(title);
+
+    let filename = if slug.is_empty() {
+        format!("{}_issue-{}.md", prefix, number)
+    } else {
+        format!("{}_{}.md", prefix, slug)
+    };
+
+    if taken.contains(&filename) {
+        format!("{}_{}-{}.md", prefix, if slug.is_empty() { "issue" } else { &slug }, number)
+    } else {
+        filename
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(names: &[&str]) -> Vec<String> {
+        names.iter().map(|l| l.to_string()).collect()
+    }
+
+    #[test]
+    fn test_filters_from_str() {
+        assert_eq!("Closed".parse::<StateFilter>().unwrap(), StateFilter::Closed);
+        assert!("merged".parse::<StateFilter>().is_err());
+
+        assert_eq!("3".parse::<MilestoneFilter>().unwrap(), MilestoneFilter::Number(3));
+        assert_eq!("*".parse::<MilestoneFilter>().unwrap(), MilestoneFilter::Any);
+        assert_eq!("none".parse::<MilestoneFilter>().unwrap(), MilestoneFilter::None);
+        assert_eq!("v1.0".parse::<MilestoneFilter>().unwrap(), MilestoneFilter::Title("v1.0".to_string()));
+    }
+
+    #[test]
+    fn test_import_filename() {
+        let taken = HashSet::from(["BUG_login-fails.md".to_string()]);
+
+        assert_eq!(import_filename(1, "Add OAuth2 login!", &labels(&["auth", "enhancement"]), &taken), "FEAT_add-oauth2-login.md");
+        assert_eq!(import_filename(2, "Update API docs", &labels(&["Documentation"]), &taken), "DOCS_update-api-docs.md");
+        assert_eq!(import_filename(3, "Login fails", &labels(&["bug"]), &taken), "BUG_login-fails-3.md");
+        assert_eq!(import_filename(4, "🚀", &[], &taken), "FEAT_issue-4.md");
+
+        let long = slugify(&"word ".repeat(30));
+        assert!(long.len() <= MAX_SLUG_LEN);
+        assert!(!long.ends_with('-'));
+    }
+}
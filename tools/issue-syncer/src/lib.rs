@@ -2,7 +2,14 @@
 //!
 //! Exposes modules for integration testing.
 
+pub mod comments;
+pub mod doctor;
+pub mod frontmatter;
 pub mod github;
+pub mod import;
 pub mod mapping;
+pub mod merge;
 pub mod parser;
+pub mod projects;
+pub mod ratelimit;
 pub mod syncer;
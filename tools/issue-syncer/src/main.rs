@@ -2,19 +2,17 @@
 //!
 //! High-performance Rust tool replacing sync-issues.ps1
 
-mod github;
-mod mapping;
-mod parser;
-mod syncer;
-
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
 use std::path::PathBuf;
-use tracing::{info, Level};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tracing::{info, warn, Level};
 use tracing_subscriber::FmtSubscriber;
 
-use crate::github::GitHubClient;
-use crate::syncer::IssueSyncer;
+use issue_syncer::github::GitHubClient;
+use issue_syncer::import::ImportFilter;
+use issue_syncer::syncer::{self, IssueSyncer};
 
 #[derive(Parser)]
 #[command(name = "issue-syncer")]
@@ -37,6 +35,26 @@ struct Cli {
     #[arg(long, default_value = "false")]
     dry_run: bool,
 
+    /// Resolve fields changed on both sides: ours, theirs or markers
+    #[arg(long, default_value = "markers")]
+    conflict: String,
+
+    /// What to do with local files of closed issues: delete, archive or mark
+    #[arg(long, default_value = "delete")]
+    on_close: String,
+
+    /// Mirror issue comments: off, sidecar (FEAT_x.comments.md) or section
+    #[arg(long, default_value = "off")]
+    comment_sync: String,
+
+    /// Projects (v2) board number of the repo owner, synced with the `project` key
+    #[arg(long)]
+    project: Option<u64>,
+
+    /// Issues created or updated at the same time during a push
+    #[arg(long, default_value_t = syncer::DEFAULT_CONCURRENCY)]
+    concurrency: usize,
+
     /// Enable verbose logging
     #[arg(short, long, default_value = "false")]
     verbose: bool,
@@ -47,15 +65,47 @@ struct Cli {
 
 #[derive(Subcommand)]
 enum Commands {
-    /// Bidirectional sync: push local files + pull closed issues
+    /// Bidirectional sync: three-way merge of local files and issues + pull closed issues
     Sync,
 
-    /// Push local .md files to GitHub Issues
+    /// Push local .md files to GitHub Issues (merging remote edits into mapped files)
     Push,
 
-    /// Pull and delete files for closed issues
+    /// Pull closed issues: delete, archive or mark their files (see --on-close)
     Pull,
 
+    /// Reopen the issue of an archived file and move the file back
+    Restore {
+        /// Archived file: a path, or its name under archive/
+        file: PathBuf,
+    },
+
+    /// Write local files for existing GitHub Issues that are not mapped yet
+    Import {
+        /// Only issues with this label (repeatable; all must match)
+        #[arg(long = "label")]
+        labels: Vec<String>,
+
+        /// Only issues in this milestone: number, title, `*` (any) or `none`
+        #[arg(long)]
+        milestone: Option<String>,
+
+        /// Issue state: open, closed or all (closed needs --on-close archive or mark)
+        #[arg(long, default_value = "open")]
+        state: String,
+
+        /// Mirror this many recent comments (see --comment-sync)
+        #[arg(long, default_value = "0")]
+        comments: usize,
+    },
+
+    /// Check files, mapping and open issues for renames, orphans and duplicates
+    Doctor {
+        /// Repair what can be repaired (mapping entries, `number` keys, nested files)
+        #[arg(long, default_value = "false")]
+        fix: bool,
+    },
+
     /// Show current mapping statistics
     Status,
 }
@@ -109,7 +159,17 @@ async fn main() -> Result<()> {
 
     // Create syncer
     let mut syncer = IssueSyncer::new(github, issues_dir, mapping_file)?
-        .with_dry_run(cli.dry_run);
+        .with_dry_run(cli.dry_run)
+        .with_conflict_strategy(cli.conflict.parse()?)
+        .with_close_policy(cli.on_close.parse()?)
+        .with_comment_mode(cli.comment_sync.parse()?)
+        .with_project(cli.project)
+        .with_concurrency(cli.concurrency);
+
+    // Only push stops early on the flag; other commands keep the default Ctrl-C exit
+    if matches!(cli.command, Commands::Sync | Commands::Push) {
+        syncer = syncer.with_cancel(cancel_on_ctrl_c());
+    }
 
     // Execute command
     match cli.command {
@@ -128,6 +188,35 @@ async fn main() -> Result<()> {
             let report = syncer.pull().await?;
             print_report(&report);
         }
+        Commands::Restore { file } => {
+            let restored = syncer.restore(&file).await?;
+            println!("\n✅ Restored {}", restored.display());
+        }
+        Commands::Import { labels, milestone, state, comments } => {
+            info!("Importing issues from GitHub");
+            let filter = ImportFilter {
+                labels,
+                milestone: milestone.map(|m| m.parse()).transpose()?,
+                state: state.parse()?,
+            };
+            let report = syncer.import(&filter, comments).await?;
+            print_report(&report);
+        }
+        Commands::Doctor { fix } => {
+            let diagnoses = syncer.doctor(fix).await?;
+            if diagnoses.is_empty() {
+                println!("\n✅ No problems found");
+            } else {
+                println!("\n🩺 {} problem(s) found", diagnoses.len());
+                for diagnosis in &diagnoses {
+                    let mark = if diagnosis.fixed { "fixed" } else if diagnosis.finding.is_fixable() { "fixable" } else { "manual" };
+                    println!("  [{}] {}", mark, diagnosis.finding);
+                }
+                if !fix && diagnoses.iter().any(|d| d.finding.is_fixable()) {
+                    println!("\nRun `issue-syncer doctor --fix` to repair the fixable ones.");
+                }
+            }
+        }
         Commands::Status => {
             let mapping = syncer.mapping();
             println!("📊 Mapping Statistics:");
@@ -140,12 +229,33 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// A flag set by the first Ctrl-C; the second one exits right away
+fn cancel_on_ctrl_c() -> Arc<AtomicBool> {
+    let cancel = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&cancel);
+    tokio::spawn(async move {
+        while tokio::signal::ctrl_c().await.is_ok() {
+            if flag.swap(true, Ordering::Relaxed) {
+                eprintln!("Interrupted again, exiting");
+                std::process::exit(130);
+            }
+            warn!("Interrupted, waiting for the requests in flight (press Ctrl-C again to quit)");
+        }
+    });
+    cancel
+}
+
 fn print_report(report: &syncer::SyncReport) {
     println!("\n✅ Sync Complete");
     println!("  Created:  {}", report.created);
     println!("  Updated:  {}", report.updated);
     println!("  Deleted:  {}", report.deleted);
+    println!("  Archived: {}", report.archived);
+    println!("  Pulled:   {}", report.pulled);
+    println!("  Imported: {}", report.imported);
     println!("  Skipped:  {}", report.skipped);
+    println!("  Conflicts: {}", report.conflicts);
+    println!("  Comments: {}", report.comments);
     println!("  Errors:   {}", report.errors);
     println!("  Total:    {}", report.total_operations());
 }
@@ -5,21 +5,192 @@
 //! # File Format
 //! ```json
 //! {
-//!   "FEAT_my-feature.md": 42,
+//!   "FEAT_my-feature.md": {
+//!     "issue": 42,
+//!     "hash": "9f86d081…",
+//!     "base": { "title": "…", "labels": ["…"], "assignees": [], "body": "…" },
+//!     "remote_updated_at": "2025-01-05T10:00:00Z",
+//!     "comments_synced_at": "2025-01-05T10:00:00Z"
+//!   },
 //!   "BUG_login-error.md": 43
 //! }
 //! ```
+//!
+//! Plain numbers (the original format) are still read; they are files that
+//! have not been synced with a content hash yet.
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
-use std::path::{Path, PathBuf};
+use std::path::Path;
+
+use crate::parser::{Field, IssueData};
+
+/// SHA-256 of a string, hex encoded
+pub fn content_hash(content: &str) -> String {
+    Sha256::digest(content.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Which issue fields an update should send
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FieldChanges {
+    pub title: bool,
+    pub body: bool,
+    pub labels: bool,
+    pub assignees: bool,
+    pub milestone: bool,
+    pub state: bool,
+    pub issue_type: bool,
+    pub parent: bool,
+    pub projects: bool,
+    pub project: bool,
+}
+
+impl FieldChanges {
+    pub fn all() -> Self {
+        let mut changes = Self::default();
+        for field in Field::ALL {
+            changes.insert(field);
+        }
+        changes
+    }
+
+    /// Fields of `current` that differ from `base` (all of them without a base)
+    ///
+    /// Label and assignee order is ignored.
+    pub fn between(base: Option<&IssueData>, current: &IssueData) -> Self {
+        let Some(base) = base else {
+            return Self::all();
+        };
+
+        let mut changes = Self::default();
+        for field in Field::ALL {
+            if base.differs(current, field) {
+                changes.insert(field);
+            }
+        }
+        changes
+    }
+
+    pub fn contains(&self, field: Field) -> bool {
+        match field {
+            Field::Title => self.title,
+            Field::Body => self.body,
+            Field::Labels => self.labels,
+            Field::Assignees => self.assignees,
+            Field::Milestone => self.milestone,
+            Field::State => self.state,
+            Field::Type => self.issue_type,
+            Field::Parent => self.parent,
+            Field::Projects => self.projects,
+            Field::Project => self.project,
+        }
+    }
+
+    pub fn insert(&mut self, field: Field) {
+        match field {
+            Field::Title => self.title = true,
+            Field::Body => self.body = true,
+            Field::Labels => self.labels = true,
+            Field::Assignees => self.assignees = true,
+            Field::Milestone => self.milestone = true,
+            Field::State => self.state = true,
+            Field::Type => self.issue_type = true,
+            Field::Parent => self.parent = true,
+            Field::Projects => self.projects = true,
+            Field::Project => self.project = true,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !Field::ALL.iter().any(|f| self.contains(*f))
+    }
+
+    /// `onto` with the changed fields taken from `from`
+    pub fn apply(&self, from: &IssueData, onto: &IssueData) -> IssueData {
+        let mut result = onto.clone();
+        for field in Field::ALL {
+            if self.contains(field) {
+                result.take(from, field);
+            }
+        }
+        result
+    }
+}
+
+/// Sync state of one mapped file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "RawEntry")]
+pub struct MappingEntry {
+    pub issue: u64,
+    /// Hash of the file content at the last sync
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+    /// The issue as last synced: the base of three-way merges
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<IssueData>,
+    /// Remote `updated_at` at the last sync
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_updated_at: Option<String>,
+    /// When comments were last mirrored; later comments are fetched next
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comments_synced_at: Option<String>,
+}
+
+impl MappingEntry {
+    pub(crate) fn new(issue: u64) -> Self {
+        Self {
+            issue,
+            hash: None,
+            base: None,
+            remote_updated_at: None,
+            comments_synced_at: None,
+        }
+    }
+}
+
+/// On-disk entry: a bare issue number or the full sync state
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawEntry {
+    Number(u64),
+    Entry {
+        issue: u64,
+        #[serde(default)]
+        hash: Option<String>,
+        #[serde(default)]
+        base: Option<Box<IssueData>>,
+        #[serde(default)]
+        remote_updated_at: Option<String>,
+        #[serde(default)]
+        comments_synced_at: Option<String>,
+    },
+}
+
+impl From<RawEntry> for MappingEntry {
+    fn from(raw: RawEntry) -> Self {
+        match raw {
+            RawEntry::Number(issue) => Self::new(issue),
+            RawEntry::Entry { issue, hash, base, remote_updated_at, comments_synced_at } => Self {
+                issue,
+                hash,
+                base: base.map(|b| *b),
+                remote_updated_at,
+                comments_synced_at,
+            },
+        }
+    }
+}
 
 /// Bidirectional mapping between files and issue numbers
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct IssueMapping {
     #[serde(flatten)]
-    file_to_issue: HashMap<String, u64>,
+    file_to_issue: HashMap<String, MappingEntry>,
 }
 
 impl IssueMapping {
@@ -35,37 +206,105 @@ impl IssueMapping {
     }
 
     /// Save mapping to file
+    ///
+    /// Written to a temporary file first, so an interrupted save keeps the
+    /// previous mapping.
     pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
         let json = serde_json::to_string_pretty(&self.file_to_issue)
             .context("Failed to serialize mapping")?;
 
-        std::fs::write(path.as_ref(), json)
-            .with_context(|| format!("Failed to write mapping to {}", path.as_ref().display()))?;
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, json)
+            .with_context(|| format!("Failed to write mapping to {}", tmp.display()))?;
+        std::fs::rename(&tmp, path)
+            .with_context(|| format!("Failed to write mapping to {}", path.display()))?;
 
         Ok(())
     }
 
     /// Get issue number for a file
     pub fn get_issue(&self, file: &str) -> Option<u64> {
-        self.file_to_issue.get(file).copied()
+        self.file_to_issue.get(file).map(|entry| entry.issue)
+    }
+
+    /// Get the sync state of a file
+    pub fn get_entry(&self, file: &str) -> Option<&MappingEntry> {
+        self.file_to_issue.get(file)
     }
 
     /// Get file path for an issue number
     pub fn get_file(&self, issue_number: u64) -> Option<String> {
         self.file_to_issue
             .iter()
-            .find(|(_, &num)| num == issue_number)
+            .find(|(_, entry)| entry.issue == issue_number)
             .map(|(file, _)| file.clone())
     }
 
-    /// Add a new mapping
+    /// Add a new mapping (without sync state)
     pub fn add(&mut self, file: String, issue_number: u64) {
-        self.file_to_issue.insert(file, issue_number);
+        self.file_to_issue.insert(file, MappingEntry::new(issue_number));
+    }
+
+    /// Record the state of a file after a successful sync
+    pub fn record_sync(
+        &mut self,
+        file: String,
+        issue_number: u64,
+        hash: String,
+        base: IssueData,
+        remote_updated_at: Option<String>,
+    ) {
+        // Comments stay synced as long as the file keeps its issue
+        let comments_synced_at = self
+            .file_to_issue
+            .get(&file)
+            .filter(|entry| entry.issue == issue_number)
+            .and_then(|entry| entry.comments_synced_at.clone());
+
+        self.file_to_issue.insert(
+            file,
+            MappingEntry {
+                issue: issue_number,
+                hash: Some(hash),
+                base: Some(base),
+                remote_updated_at,
+                comments_synced_at,
+            },
+        );
+    }
+
+    /// Record a comment sync, and the new content hash if the file was rewritten
+    pub fn record_comments(&mut self, file: &str, synced_at: String, hash: Option<String>) {
+        if let Some(entry) = self.file_to_issue.get_mut(file) {
+            entry.comments_synced_at = Some(synced_at);
+            if hash.is_some() {
+                entry.hash = hash;
+            }
+        }
+    }
+
+    /// Move the entry of a renamed file, keeping its sync state
+    pub fn rename(&mut self, old: &str, new: String) -> bool {
+        match self.file_to_issue.remove(old) {
+            Some(entry) => {
+                self.file_to_issue.insert(new, entry);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Record a new content hash for a file rewritten without remote changes
+    pub fn set_hash(&mut self, file: &str, hash: String) {
+        if let Some(entry) = self.file_to_issue.get_mut(file) {
+            entry.hash = Some(hash);
+        }
     }
 
     /// Remove a mapping by file name
     pub fn remove_by_file(&mut self, file: &str) -> Option<u64> {
-        self.file_to_issue.remove(file)
+        self.file_to_issue.remove(file).map(|entry| entry.issue)
     }
 
     /// Remove a mapping by issue number
@@ -82,7 +321,12 @@ impl IssueMapping {
 
     /// Check if an issue is mapped
     pub fn contains_issue(&self, issue_number: u64) -> bool {
-        self.file_to_issue.values().any(|&num| num == issue_number)
+        self.file_to_issue.values().any(|entry| entry.issue == issue_number)
+    }
+
+    /// Iterate over files and their sync state
+    pub fn entries(&self) -> impl Iterator<Item = (&String, &MappingEntry)> {
+        self.file_to_issue.iter()
     }
 
     /// Get all mapped files
@@ -92,7 +336,7 @@ impl IssueMapping {
 
     /// Get all mapped issue numbers
     pub fn issues(&self) -> Vec<u64> {
-        self.file_to_issue.values().copied().collect()
+        self.file_to_issue.values().map(|entry| entry.issue).collect()
     }
 
     /// Get the number of mappings
@@ -159,6 +403,74 @@ mod tests {
         assert_eq!(loaded.get_issue("BUG_error.md"), Some(43));
     }
 
+    #[test]
+    fn test_load_legacy_and_full_entries() {
+        let json = r#"{
+            "FEAT_old.md": 42,
+            "BUG_new.md": { "issue": 43, "hash": "abc", "remote_updated_at": "2025-01-05T10:00:00Z" }
+        }"#;
+
+        let mapping: IssueMapping = serde_json::from_str(json).unwrap();
+        assert_eq!(mapping.get_issue("FEAT_old.md"), Some(42));
+        assert_eq!(mapping.get_entry("FEAT_old.md").unwrap().hash, None);
+
+        let entry = mapping.get_entry("BUG_new.md").unwrap();
+        assert_eq!(entry.issue, 43);
+        assert_eq!(entry.hash.as_deref(), Some("abc"));
+        assert_eq!(entry.remote_updated_at.as_deref(), Some("2025-01-05T10:00:00Z"));
+    }
+
+    #[test]
+    fn test_field_changes() {
+        let base = IssueData {
+            title: "Title".to_string(),
+            labels: vec!["bug".to_string(), "p1".to_string()],
+            body: "Body".to_string(),
+            ..Default::default()
+        };
+
+        let mut reordered = base.clone();
+        reordered.labels.reverse();
+        assert!(FieldChanges::between(Some(&base), &reordered).is_empty());
+
+        let mut edited = base.clone();
+        edited.body.push_str(" more");
+        edited.labels.pop();
+        let changes = FieldChanges::between(Some(&base), &edited);
+        assert_eq!(changes, FieldChanges { body: true, labels: true, ..Default::default() });
+
+        assert_eq!(FieldChanges::between(None, &base), FieldChanges::all());
+    }
+
+    #[test]
+    fn test_record_sync_round_trip() {
+        let mut mapping = IssueMapping::default();
+        let base = IssueData {
+            title: "Test".to_string(),
+            labels: vec!["bug".to_string()],
+            body: "Body".to_string(),
+            ..Default::default()
+        };
+        mapping.record_sync("FEAT_test.md".to_string(), 42, "h".to_string(), base, None);
+
+        let temp_file = NamedTempFile::new().unwrap();
+        mapping.save(temp_file.path()).unwrap();
+
+        let loaded = IssueMapping::load(temp_file.path()).unwrap();
+        assert_eq!(loaded.get_entry("FEAT_test.md"), mapping.get_entry("FEAT_test.md"));
+    }
+
+    #[test]
+    fn test_rename() {
+        let mut mapping = IssueMapping::default();
+        mapping.record_sync("FEAT_old.md".to_string(), 7, "abc".to_string(), IssueData::default(), None);
+
+        assert!(mapping.rename("FEAT_old.md", "FEAT_new.md".to_string()));
+        assert!(!mapping.contains_file("FEAT_old.md"));
+        assert_eq!(mapping.get_entry("FEAT_new.md").unwrap().hash.as_deref(), Some("abc"));
+        assert!(!mapping.rename("FEAT_old.md", "FEAT_other.md".to_string()));
+    }
+
     #[test]
     fn test_bidirectional_lookup() {
         let mut mapping = IssueMapping::default();
@@ -0,0 +1,265 @@
+//! Three-Way Merge
+//!
+//! Reconciles a local issue file with the remote issue, using the snapshot
+//! stored in the mapping at the last sync as the common base.
+//!
+//! | Local vs base | Remote vs base | Result |
+//! |---------------|----------------|--------|
+//! | unchanged | unchanged | nothing to do |
+//! | changed | unchanged | push the field |
+//! | unchanged | changed | write the field to the file |
+//! | changed | changed | conflict, resolved by [`ConflictStrategy`] |
+//!
+//! Without a base (files mapped before snapshots existed) local wins, as
+//! it always did.
+
+use anyhow::Result;
+
+use crate::mapping::FieldChanges;
+use crate::parser::{Field, IssueData};
+
+const MARKER_LOCAL: &str = "<<<<<<< local";
+const MARKER_SEPARATOR: &str = "=======";
+const MARKER_REMOTE: &str = ">>>>>>> remote";
+
+/// How to resolve fields changed on both sides
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConflictStrategy {
+    /// Keep the local value and push it
+    Ours,
+    /// Keep the remote value and write it to the file
+    Theirs,
+    /// Write conflict markers into the file and push nothing for the field
+    #[default]
+    Markers,
+}
+
+impl std::str::FromStr for ConflictStrategy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "ours" | "local" => Ok(ConflictStrategy::Ours),
+            "theirs" | "remote" => Ok(ConflictStrategy::Theirs),
+            "markers" | "mark" => Ok(ConflictStrategy::Markers),
+            _ => Err(anyhow::anyhow!("Invalid conflict strategy: {} (expected ours, theirs or markers)", s)),
+        }
+    }
+}
+
+/// Result of merging one issue
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeResult {
+    /// Content the local file should have
+    pub local: IssueData,
+    /// Fields to send to GitHub (values taken from `local`)
+    pub push: FieldChanges,
+    /// Whether the local file must be rewritten
+    pub write_local: bool,
+    /// Names of the fields changed on both sides
+    pub conflicts: Vec<&'static str>,
+}
+
+/// Which side changed a field since the base
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Neither,
+    Local,
+    Remote,
+    Both,
+}
+
+fn side(local_changed: bool, remote_changed: bool, sides_equal: bool) -> Side {
+    match (local_changed, remote_changed) {
+        // Both sides made the same edit
+        (true, true) if sides_equal => Side::Neither,
+        (true, true) => Side::Both,
+        (true, false) => Side::Local,
+        (false, true) => Side::Remote,
+        (false, false) => Side::Neither,
+    }
+}
+
+/// Merge local and remote against the last-synced base
+pub fn three_way(
+    base: Option<&IssueData>,
+    local: &IssueData,
+    remote: &IssueData,
+    strategy: ConflictStrategy,
+) -> MergeResult {
+    let Some(base) = base else {
+        return MergeResult {
+            local: local.clone(),
+            push: FieldChanges::between(Some(remote), local),
+            write_local: false,
+            conflicts: vec![],
+        };
+    };
+
+    let mut merged = local.clone();
+    let mut push = FieldChanges::default();
+    let mut conflicts = Vec::new();
+    let mut marker_lines: Vec<(String, String)> = Vec::new();
+
+    for field in Field::ALL {
+        let local_changed = base.differs(local, field);
+        let remote_changed = base.differs(remote, field);
+
+        match side(local_changed, remote_changed, !local.differs(remote, field)) {
+            Side::Neither => {}
+            Side::Local => push.insert(field),
+            Side::Remote => merged.take(remote, field),
+            Side::Both => {
+                conflicts.push(field.name());
+                match strategy {
+                    ConflictStrategy::Ours => push.insert(field),
+                    ConflictStrategy::Theirs => merged.take(remote, field),
+                    ConflictStrategy::Markers if field != Field::Body => {
+                        marker_lines.push((local.field_line(field), remote.field_line(field)));
+                    }
+                    ConflictStrategy::Markers => {}
+                }
+            }
+        }
+    }
+
+    if strategy == ConflictStrategy::Markers && !conflicts.is_empty() {
+        let mut body = String::new();
+
+        if !marker_lines.is_empty() {
+            let (ours, theirs): (Vec<String>, Vec<String>) = marker_lines.into_iter().unzip();
+            body.push_str(&conflict_block(&ours.join("\n"), &theirs.join("\n")));
+            body.push_str("\n\n");
+        }
+
+        if conflicts.contains(&"body") {
+            body.push_str(&conflict_block(&local.body, &remote.body));
+        } else {
+            body.push_str(&merged.body);
+        }
+
+        merged.body = body;
+    }
+
+    MergeResult {
+        write_local: merged != *local,
+        local: merged,
+        push,
+        conflicts,
+    }
+}
+
+fn conflict_block(ours: &str, theirs: &str) -> String {
+    format!("{}\n{}\n{}\n{}\n{}", MARKER_LOCAL, ours, MARKER_SEPARATOR, theirs, MARKER_REMOTE)
+}
+
+/// Whether a body still contains unresolved conflict markers
+pub fn has_conflict_markers(body: &str) -> bool {
+    let mut lines = body.lines().map(str::trim_end);
+    lines.any(|l| l == MARKER_LOCAL) && lines.any(|l| l == MARKER_REMOTE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(title: &str, labels: &[&str], body: &str) -> IssueData {
+        IssueData {
+            title: title.to_string(),
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+            body: body.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_strategy_from_str() {
+        assert_eq!("ours".parse::<ConflictStrategy>().unwrap(), ConflictStrategy::Ours);
+        assert_eq!("Theirs".parse::<ConflictStrategy>().unwrap(), ConflictStrategy::Theirs);
+        assert_eq!("markers".parse::<ConflictStrategy>().unwrap(), ConflictStrategy::Markers);
+        assert!("both".parse::<ConflictStrategy>().is_err());
+    }
+
+    #[test]
+    fn test_one_sided_changes() {
+        let base = issue("Title", &["bug"], "Body");
+        let local = issue("New title", &["bug"], "Body");
+        let remote = issue("Title", &["bug", "p1"], "Body");
+
+        let result = three_way(Some(&base), &local, &remote, ConflictStrategy::Markers);
+
+        assert!(result.conflicts.is_empty());
+        assert_eq!(result.push, FieldChanges { title: true, ..Default::default() });
+        assert!(result.write_local);
+        assert_eq!(result.local, issue("New title", &["bug", "p1"], "Body"));
+    }
+
+    #[test]
+    fn test_same_edit_on_both_sides_is_not_a_conflict() {
+        let base = issue("Title", &[], "Body");
+        let edited = issue("Title", &[], "Fixed body");
+
+        let result = three_way(Some(&base), &edited, &edited, ConflictStrategy::Markers);
+        assert!(result.conflicts.is_empty());
+        assert!(result.push.is_empty());
+        assert!(!result.write_local);
+    }
+
+    #[test]
+    fn test_conflict_strategies() {
+        let base = issue("Title", &[], "Body");
+        let local = issue("Local title", &[], "Local body");
+        let remote = issue("Remote title", &[], "Remote body");
+
+        let ours = three_way(Some(&base), &local, &remote, ConflictStrategy::Ours);
+        assert_eq!(ours.conflicts, ["title", "body"]);
+        assert_eq!(ours.push, FieldChanges { title: true, body: true, ..Default::default() });
+        assert!(!ours.write_local);
+
+        let theirs = three_way(Some(&base), &local, &remote, ConflictStrategy::Theirs);
+        assert!(theirs.push.is_empty());
+        assert_eq!(theirs.local, remote);
+
+        let marked = three_way(Some(&base), &local, &remote, ConflictStrategy::Markers);
+        assert!(marked.push.is_empty());
+        assert!(marked.write_local);
+        assert_eq!(marked.local.title, "Local title");
+        assert!(marked.local.body.contains("title: Local title\n=======\ntitle: Remote title"));
+        assert!(marked.local.body.contains("Local body\n=======\nRemote body"));
+        assert!(has_conflict_markers(&marked.local.body));
+        assert!(!has_conflict_markers("Body with ======= inside"));
+    }
+
+    #[test]
+    fn test_metadata_fields() {
+        let base = issue("Title", &[], "Body");
+        let mut local = base.clone();
+        local.state = crate::parser::IssueState::Closed;
+        local.parent = Some(40);
+        let mut remote = base.clone();
+        remote.milestone = Some("v1.0".to_string());
+
+        let result = three_way(Some(&base), &local, &remote, ConflictStrategy::Markers);
+        assert_eq!(result.push, FieldChanges { state: true, parent: true, ..Default::default() });
+        assert_eq!(result.local.milestone.as_deref(), Some("v1.0"));
+
+        // Clearing the milestone on both sides differently is a conflict
+        let mut cleared = remote.clone();
+        cleared.milestone = None;
+        let mut renamed = remote.clone();
+        renamed.milestone = Some("v2.0".to_string());
+        let marked = three_way(Some(&remote), &cleared, &renamed, ConflictStrategy::Markers);
+        assert_eq!(marked.conflicts, ["milestone"]);
+        assert!(marked.local.body.contains("milestone: \n=======\nmilestone: v2.0"));
+    }
+
+    #[test]
+    fn test_without_base_local_wins() {
+        let local = issue("Title", &["bug"], "Local");
+        let remote = issue("Title", &["bug"], "Remote");
+
+        let result = three_way(None, &local, &remote, ConflictStrategy::Markers);
+        assert_eq!(result.push, FieldChanges { body: true, ..Default::default() });
+        assert!(!result.write_local);
+    }
+}
@@ -10,24 +10,242 @@
 //!   - enhancement
 //!   - rust
 //! assignees: []
+//! milestone: v1.0          # title; omit to clear
+//! state: closed            # open (default) or closed
+//! state_reason: completed  # completed, not_planned or reopened
+//! closed_at: 2025-01-05    # written when a closed issue is archived
+//! number: 42               # binds the file to this issue
+//! type: Feature            # organization issue type
+//! parent: 40               # makes this a sub-issue of #40
+//! projects: [3]            # Projects (v2) numbers of the repo owner
+//! project:                 # fields on the --project board
+//!   status: In Progress
 //! ---
 //!
 //! Issue body content here...
 //! ```
+//!
+//! `type`, `parent` and `projects` are only pushed: edits made to them on
+//! GitHub are not written back to the file. `project` fields are synced
+//! both ways (see [`crate::projects`]). Unknown keys are kept when the
+//! file is rewritten and reported by the syncer.
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::Path;
 
+use crate::frontmatter::Document;
+use crate::projects::{field_key, ProjectValues};
+
+/// Start of the comments section (see [`crate::comments`])
+pub const COMMENTS_START: &str = "<!-- issue-syncer:comments -->";
+/// End of the comments section
+pub const COMMENTS_END: &str = "<!-- /issue-syncer:comments -->";
+/// Matches the start marker of older files too
+const COMMENTS_START_PREFIX: &str = "<!-- issue-syncer:comments";
+
 /// Parsed GitHub Issue data from frontmatter
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct IssueData {
     pub title: String,
     #[serde(default)]
     pub labels: Vec<String>,
     #[serde(default)]
     pub assignees: Vec<String>,
+    /// Milestone title
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub milestone: Option<String>,
+    #[serde(default, skip_serializing_if = "IssueState::is_open")]
+    pub state: IssueState,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_reason: Option<StateReason>,
+    /// Close date (`YYYY-MM-DD`), informational
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub closed_at: Option<String>,
+    /// Issue the file is bound to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub number: Option<u64>,
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub issue_type: Option<String>,
+    /// Parent issue number
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<u64>,
+    /// Projects (v2) numbers
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub projects: Vec<u64>,
+    /// Field values on the `--project` board
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub project: ProjectValues,
     pub body: String,
+    /// Frontmatter keys the syncer does not know (kept on rewrite)
+    #[serde(skip)]
+    pub unknown: BTreeMap<String, serde_yaml::Value>,
+}
+
+/// Issue state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueState {
+    #[default]
+    Open,
+    Closed,
+}
+
+impl IssueState {
+    pub fn is_open(&self) -> bool {
+        *self == IssueState::Open
+    }
+}
+
+/// Why an issue was closed (or reopened)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StateReason {
+    Completed,
+    NotPlanned,
+    Reopened,
+}
+
+/// A synced issue field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Title,
+    Body,
+    Labels,
+    Assignees,
+    Milestone,
+    /// `state` and `state_reason`
+    State,
+    Type,
+    Parent,
+    Projects,
+    /// Field values on the `--project` board
+    Project,
+}
+
+impl Field {
+    pub const ALL: [Field; 10] = [
+        Field::Title,
+        Field::Body,
+        Field::Labels,
+        Field::Assignees,
+        Field::Milestone,
+        Field::State,
+        Field::Type,
+        Field::Parent,
+        Field::Projects,
+        Field::Project,
+    ];
+
+    /// Frontmatter key
+    pub fn name(self) -> &'static str {
+        match self {
+            Field::Title => "title",
+            Field::Body => "body",
+            Field::Labels => "labels",
+            Field::Assignees => "assignees",
+            Field::Milestone => "milestone",
+            Field::State => "state",
+            Field::Type => "type",
+            Field::Parent => "parent",
+            Field::Projects => "projects",
+            Field::Project => "project",
+        }
+    }
+
+    /// Whether GitHub edits to the field are not pulled into files
+    pub fn is_push_only(self) -> bool {
+        matches!(self, Field::Type | Field::Parent | Field::Projects)
+    }
+}
+
+impl IssueData {
+    /// Whether a field differs from `other` (list order is ignored)
+    pub fn differs(&self, other: &IssueData, field: Field) -> bool {
+        match field {
+            Field::Title => self.title != other.title,
+            Field::Body => self.body != other.body,
+            Field::Labels => !same_set(&self.labels, &other.labels),
+            Field::Assignees => !same_set(&self.assignees, &other.assignees),
+            Field::Milestone => self.milestone != other.milestone,
+            Field::State => self.state != other.state || self.closed_reason() != other.closed_reason(),
+            Field::Type => self.issue_type != other.issue_type,
+            Field::Parent => self.parent != other.parent,
+            Field::Projects => {
+                let mut a = self.projects.clone();
+                let mut b = other.projects.clone();
+                a.sort_unstable();
+                b.sort_unstable();
+                a != b
+            }
+            Field::Project => self.project != other.project,
+        }
+    }
+
+    /// Copy a field from `from`
+    pub fn take(&mut self, from: &IssueData, field: Field) {
+        match field {
+            Field::Title => self.title = from.title.clone(),
+            Field::Body => self.body = from.body.clone(),
+            Field::Labels => self.labels = from.labels.clone(),
+            Field::Assignees => self.assignees = from.assignees.clone(),
+            Field::Milestone => self.milestone = from.milestone.clone(),
+            Field::State => {
+                self.state = from.state;
+                self.state_reason = from.state_reason;
+            }
+            Field::Type => self.issue_type = from.issue_type.clone(),
+            Field::Parent => self.parent = from.parent,
+            Field::Projects => self.projects = from.projects.clone(),
+            Field::Project => self.project = from.project.clone(),
+        }
+    }
+
+    /// A field as a `key: value` frontmatter line
+    pub fn field_line(&self, field: Field) -> String {
+        let value = match field {
+            Field::Title => self.title.clone(),
+            Field::Body => self.body.clone(),
+            Field::Labels => format!("[{}]", self.labels.join(", ")),
+            Field::Assignees => format!("[{}]", self.assignees.join(", ")),
+            Field::Milestone => self.milestone.clone().unwrap_or_default(),
+            Field::State => match self.closed_reason() {
+                Some(reason) => format!("closed ({:?})", reason),
+                None if self.state.is_open() => "open".to_string(),
+                None => "closed".to_string(),
+            },
+            Field::Type => self.issue_type.clone().unwrap_or_default(),
+            Field::Parent => self.parent.map(|p| p.to_string()).unwrap_or_default(),
+            Field::Projects => format!("{:?}", self.projects),
+            Field::Project => {
+                let values: Vec<String> = self
+                    .project
+                    .iter()
+                    .map(|(k, v)| format!("{}: {}", k, serde_yaml::to_string(v).unwrap_or_default().trim_end()))
+                    .collect();
+                format!("{{{}}}", values.join(", "))
+            }
+        };
+        format!("{}: {}", field.name(), value)
+    }
+
+    /// The state reason, which only means something for closed issues
+    fn closed_reason(&self) -> Option<StateReason> {
+        match self.state {
+            IssueState::Open => None,
+            IssueState::Closed => self.state_reason,
+        }
+    }
+}
+
+/// Order-insensitive list comparison
+pub fn same_set(a: &[String], b: &[String]) -> bool {
+    let mut a = a.to_vec();
+    let mut b = b.to_vec();
+    a.sort();
+    b.sort();
+    a == b
 }
 
 /// Parse a markdown file with YAML frontmatter
@@ -47,40 +265,176 @@ pub fn parse_issue_file<P: AsRef<Path>>(path: P) -> Result<IssueData> {
 }
 
 /// Parse frontmatter from markdown content
+///
+/// See [`crate::frontmatter`] for the accepted syntax.
 pub fn parse_frontmatter(content: &str) -> Result<IssueData> {
-    // Manual frontmatter extraction
-    if !content.starts_with("---") {
-        anyhow::bail!("Missing YAML frontmatter marker");
-    }
-
-    // Find end of frontmatter
-    let rest = &content[3..];
-    let end_marker = rest.find("\n---\n")
-        .context("Missing frontmatter closing marker")?;
-
-    let yaml_str = &rest[..end_marker];
-    let body = rest[end_marker + 5..].trim();
-
-    // Parse YAML
-    let frontmatter: FrontmatterData = serde_yaml::from_str(yaml_str)
-        .context("Failed to parse YAML frontmatter")?;
+    let document = Document::parse(content)?;
+    let frontmatter: FrontmatterData = document.deserialize()?;
+    let body = strip_comments_section(&document.body).trim();
 
     Ok(IssueData {
         title: frontmatter.title,
         labels: frontmatter.labels,
         assignees: frontmatter.assignees,
+        milestone: frontmatter.milestone,
+        state: frontmatter.state,
+        state_reason: frontmatter.state_reason,
+        closed_at: frontmatter.closed_at,
+        number: frontmatter.number,
+        issue_type: frontmatter.issue_type,
+        parent: frontmatter.parent,
+        projects: frontmatter.projects,
+        // Keys as the board names them; empty values are unset
+        project: frontmatter
+            .project
+            .into_iter()
+            .filter(|(_, value)| !value.is_null())
+            .map(|(key, value)| (field_key(&key), value))
+            .collect(),
         body: body.to_string(),
+        unknown: frontmatter.unknown,
     })
 }
 
+/// The comments section of a file, if any
+pub fn comments_section(content: &str) -> Option<&str> {
+    comments_range(content).map(|range| &content[range])
+}
+
+fn comments_range(content: &str) -> Option<std::ops::Range<usize>> {
+    let start = content.find(COMMENTS_START_PREFIX)?;
+    let end = content[start..]
+        .find(COMMENTS_END)
+        .map(|i| start + i + COMMENTS_END.len())
+        .unwrap_or(content.len());
+    Some(start..end)
+}
+
+/// Body without the comments section
+fn strip_comments_section(body: &str) -> &str {
+    match body.find(COMMENTS_START_PREFIX) {
+        Some(start) => &body[..start],
+        None => body,
+    }
+}
+
+/// Replace (or append) the comments section of a file
+pub fn set_comments_section(original: &str, section: &str) -> Result<String> {
+    let mut document = Document::parse(original)?;
+
+    match comments_range(&document.body) {
+        Some(range) => document.body.replace_range(range, section),
+        None => {
+            let body = document.body.trim_end();
+            document.body = format!("{}\n\n{}\n", body, section);
+        }
+    }
+
+    Ok(document.render())
+}
+
+/// Render issue data as markdown with YAML frontmatter
+pub fn render_issue_file(data: &IssueData) -> Result<String> {
+    let frontmatter = serde_yaml::to_string(&FrontmatterData::from(data))
+        .context("Failed to serialize frontmatter")?;
+
+    Ok(format!("---\n{}---\n\n{}\n", frontmatter, data.body))
+}
+
+/// Rewrite a file with new issue data, changing only what differs
+///
+/// Unchanged keys, comments, key order, the frontmatter syntax, line
+/// endings and the read-only comments section are kept.
+pub fn update_issue_file(original: &str, data: &IssueData) -> Result<String> {
+    let mut document = Document::parse(original)?;
+    let current = parse_frontmatter(original)?;
+
+    let old = frontmatter_value(&FrontmatterData::from(&current))?;
+    let new = frontmatter_value(&FrontmatterData::from(data))?;
+
+    for key in FrontmatterData::KEYS {
+        let value = new.get(key);
+        if old.get(key) != value {
+            document.set(key, value)?;
+        }
+    }
+
+    if current.body != data.body {
+        let mut body = format!("\n{}\n", data.body);
+        if let Some(section) = comments_section(&document.body) {
+            body.push('\n');
+            body.push_str(section);
+            body.push('\n');
+        }
+        document.body = body;
+    }
+
+    Ok(document.render())
+}
+
+fn frontmatter_value(frontmatter: &FrontmatterData) -> Result<serde_yaml::Mapping> {
+    match serde_yaml::to_value(frontmatter).context("Failed to serialize frontmatter")? {
+        serde_yaml::Value::Mapping(mapping) => Ok(mapping),
+        _ => anyhow::bail!("Frontmatter is not a mapping"),
+    }
+}
+
 /// Frontmatter structure (internal)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 struct FrontmatterData {
     title: String,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    number: Option<u64>,
+    #[serde(default, skip_serializing_if = "IssueState::is_open")]
+    state: IssueState,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    state_reason: Option<StateReason>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    closed_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    milestone: Option<String>,
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    issue_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    parent: Option<u64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     labels: Vec<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     assignees: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    projects: Vec<u64>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    project: BTreeMap<String, serde_yaml::Value>,
+    #[serde(flatten)]
+    unknown: BTreeMap<String, serde_yaml::Value>,
+}
+
+impl FrontmatterData {
+    /// Keys the syncer writes
+    const KEYS: [&'static str; 12] = [
+        "title", "number", "state", "state_reason", "closed_at", "milestone", "type", "parent", "labels", "assignees", "projects",
+        "project",
+    ];
+}
+
+impl From<&IssueData> for FrontmatterData {
+    fn from(data: &IssueData) -> Self {
+        Self {
+            title: data.title.clone(),
+            number: data.number,
+            state: data.state,
+            state_reason: data.state_reason,
+            closed_at: data.closed_at.clone(),
+            milestone: data.milestone.clone(),
+            issue_type: data.issue_type.clone(),
+            parent: data.parent,
+            labels: data.labels.clone(),
+            assignees: data.assignees.clone(),
+            projects: data.projects.clone(),
+            project: data.project.clone(),
+            unknown: data.unknown.clone(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -123,6 +477,143 @@ Body content.
         assert!(issue.assignees.is_empty());
     }
 
+    #[test]
+    fn test_render_round_trip() {
+        let issue = IssueData {
+            title: "Fix: \"quoted\" title".to_string(),
+            labels: vec!["bug".to_string()],
+            milestone: Some("v1.0".to_string()),
+            state: IssueState::Closed,
+            state_reason: Some(StateReason::NotPlanned),
+            number: Some(42),
+            parent: Some(40),
+            projects: vec![3],
+            body: "Line 1\n\nLine 2".to_string(),
+            unknown: BTreeMap::from([("priority".to_string(), serde_yaml::Value::from("high"))]),
+            ..Default::default()
+        };
+
+        let rendered = render_issue_file(&issue).unwrap();
+        assert!(rendered.starts_with("---\n"));
+        assert_eq!(parse_frontmatter(&rendered).unwrap(), issue);
+    }
+
+    #[test]
+    fn test_parse_extended_frontmatter() {
+        let content = r#"---
+title: "Sub task"
+state: closed
+state_reason: not_planned
+milestone: v1.0
+type: Bug
+parent: 40
+projects: [3, 5]
+estimate: 3
+---
+
+Body
+"#;
+
+        let issue = parse_frontmatter(content).unwrap();
+        assert_eq!(issue.state, IssueState::Closed);
+        assert_eq!(issue.state_reason, Some(StateReason::NotPlanned));
+        assert_eq!(issue.milestone.as_deref(), Some("v1.0"));
+        assert_eq!(issue.issue_type.as_deref(), Some("Bug"));
+        assert_eq!(issue.parent, Some(40));
+        assert_eq!(issue.projects, vec![3, 5]);
+        assert_eq!(issue.unknown.keys().collect::<Vec<_>>(), ["estimate"]);
+
+        assert!(parse_frontmatter("---\ntitle: T\nstate: merged\n---\nBody").is_err());
+    }
+
+    #[test]
+    fn test_project_fields() {
+        let content = "---\ntitle: T\nproject:\n  Status: Todo\n  Story Points: 3\n  priority:\n---\nBody\n";
+        let mut issue = parse_frontmatter(content).unwrap();
+
+        assert_eq!(issue.project.keys().collect::<Vec<_>>(), ["status", "story_points"]);
+        assert_eq!(issue.project["story_points"], serde_yaml::Value::from(3));
+
+        // Unchanged values keep the file as written
+        assert_eq!(update_issue_file(content, &issue).unwrap(), content);
+
+        issue.project.insert("status".to_string(), "Done".into());
+        assert_eq!(
+            update_issue_file(content, &issue).unwrap(),
+            "---\ntitle: T\nproject:\n  status: Done\n  story_points: 3\n---\nBody\n"
+        );
+    }
+
+    #[test]
+    fn test_state_reason_ignored_while_open() {
+        let open = IssueData { state_reason: Some(StateReason::Reopened), ..Default::default() };
+        assert!(!open.differs(&IssueData::default(), Field::State));
+
+        let closed = IssueData { state: IssueState::Closed, ..Default::default() };
+        assert!(closed.differs(&IssueData::default(), Field::State));
+    }
+
+    #[test]
+    fn test_parse_tolerant_syntax() {
+        let crlf = "\u{feff}---\r\ntitle: Test\r\nlabels:\r\n  - bug\r\n---\r\n\r\nLine 1\r\nLine 2\r\n";
+        let issue = parse_frontmatter(crlf).unwrap();
+        assert_eq!(issue.labels, vec!["bug"]);
+        assert_eq!(issue.body, "Line 1\nLine 2");
+
+        let toml = "+++\ntitle = \"Test\"\nlabels = [\"bug\"]\nparent = 4\n+++\nBody";
+        let issue = parse_frontmatter(toml).unwrap();
+        assert_eq!(issue.title, "Test");
+        assert_eq!(issue.parent, Some(4));
+
+        let error = parse_frontmatter("---\ntitle: T\nparent: [1]\n---\n").unwrap_err().to_string();
+        assert!(error.starts_with("Invalid YAML frontmatter at line 3, column 9: parent: invalid type"), "{}", error);
+    }
+
+    #[test]
+    fn test_update_issue_file_keeps_formatting() {
+        let original = "---\r\n# Tracked in the roadmap\r\ntitle: Test\r\nlabels: [bug]\r\nestimate: 3\r\n---\r\n\r\nBody\r\n\r\n"
+            .to_string()
+            + COMMENTS_START
+            + "\r\nold comment\r\n"
+            + COMMENTS_END
+            + "\r\n";
+
+        let mut issue = parse_frontmatter(&original).unwrap();
+        assert_eq!(update_issue_file(&original, &issue).unwrap(), original);
+
+        issue.labels.push("p1".to_string());
+        issue.milestone = Some("v1.0".to_string());
+        issue.body = "New body".to_string();
+        let updated = update_issue_file(&original, &issue).unwrap();
+
+        assert!(updated.starts_with("---\r\n# Tracked in the roadmap\r\ntitle: Test\r\nlabels: [bug, p1]\r\nestimate: 3\r\nmilestone: v1.0\r\n---\r\n\r\nNew body\r\n"));
+        assert!(updated.contains("old comment"));
+        assert_eq!(parse_frontmatter(&updated).unwrap(), issue);
+
+        let toml = "+++\ntitle = \"Test\" # keep\nlabels = [\"bug\"]\n+++\nBody\n";
+        let mut issue = parse_frontmatter(toml).unwrap();
+        issue.labels.clear();
+        assert_eq!(update_issue_file(toml, &issue).unwrap(), "+++\ntitle = \"Test\" # keep\n+++\nBody\n");
+    }
+
+    #[test]
+    fn test_set_comments_section() {
+        let original = "---\r\ntitle: T\r\n---\r\n\r\nBody\r\n";
+        let with_section = set_comments_section(original, "<!-- issue-syncer:comments -->\nA\n<!-- /issue-syncer:comments -->").unwrap();
+        assert_eq!(
+            with_section,
+            "---\r\ntitle: T\r\n---\r\n\r\nBody\r\n\r\n<!-- issue-syncer:comments -->\r\nA\r\n<!-- /issue-syncer:comments -->\r\n"
+        );
+
+        let replaced = set_comments_section(&with_section, "<!-- issue-syncer:comments -->\nB\n<!-- /issue-syncer:comments -->").unwrap();
+        assert!(replaced.contains("\r\nB\r\n") && !replaced.contains("\r\nA\r\n"));
+        assert_eq!(parse_frontmatter(&replaced).unwrap().body, "Body");
+
+        // Sections written before comment sync existed
+        let legacy = "---\ntitle: T\n---\nBody\n<!-- issue-syncer:comments (read-only, not synced) -->\nold\n<!-- /issue-syncer:comments -->\n";
+        assert_eq!(parse_frontmatter(legacy).unwrap().body, "Body");
+    }
+
     #[test]
     fn test_parse_frontmatter_multiline_body() {
         let content = r#"---
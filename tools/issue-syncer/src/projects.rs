@@ -0,0 +1,335 @@
+//! Projects (v2) Fields
+//!
+//! Syncs the `project` frontmatter key with the fields of one Projects (v2)
+//! board of the repository owner, chosen with `--project`:
+//!
+//! ```yaml
+//! project:
+//!   status: In Progress   # single select: option name
+//!   priority: P1
+//!   iteration: current    # iteration title, `current` or `next`
+//!   estimate: 3           # number
+//! ```
+//!
+//! Keys are field names in lowercase with `_` between words
+//! (`Story Points` → `story_points`). Text and date (`YYYY-MM-DD`) fields
+//! work too. Issues with project fields are added to the board if they are
+//! not on it yet.
+
+use anyhow::{Context, Result};
+use chrono::{Duration, NaiveDate};
+use serde_json::{json, Value as Json};
+use serde_yaml::Value;
+use std::collections::{BTreeMap, HashMap};
+
+/// Field values of one item, by field key
+pub type ProjectValues = BTreeMap<String, Value>;
+
+/// Frontmatter key of a field name
+pub fn field_key(name: &str) -> String {
+    let mut key = String::new();
+    for c in name.trim().chars() {
+        if c.is_alphanumeric() {
+            key.extend(c.to_lowercase());
+        } else if !key.is_empty() && !key.ends_with('_') {
+            key.push('_');
+        }
+    }
+    key.trim_end_matches('_').to_string()
+}
+
+/// Kind of a project field and what it accepts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldKind {
+    /// Option ids by name
+    SingleSelect(Vec<(String, String)>),
+    Iteration(Vec<Iteration>),
+    Number,
+    Text,
+    Date,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Iteration {
+    pub id: String,
+    pub title: String,
+    pub start_date: NaiveDate,
+    /// Length in days
+    pub duration: i64,
+}
+
+/// A field of the board
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectField {
+    pub id: String,
+    pub name: String,
+    pub kind: FieldKind,
+}
+
+/// An issue on the board
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectItem {
+    pub id: String,
+    pub values: ProjectValues,
+}
+
+/// A Projects (v2) board with the items of one repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Project {
+    pub id: String,
+    pub number: u64,
+    pub fields: Vec<ProjectField>,
+    /// Items by issue number
+    pub items: HashMap<u64, ProjectItem>,
+}
+
+impl Project {
+    /// Board and fields from a GraphQL `ProjectV2` node (items are added separately)
+    ///
+    /// Built-in fields (title, assignees, labels, …) are left out.
+    pub fn from_graphql(number: u64, node: &Json) -> Result<Self> {
+        let id = node["id"]
+            .as_str()
+            .with_context(|| format!("Project {} has no id", number))?
+            .to_string();
+
+        let fields = node["fields"]["nodes"]
+            .as_array()
+            .map(|nodes| nodes.iter().filter_map(parse_field).collect())
+            .unwrap_or_default();
+
+        Ok(Self { id, number, fields, items: HashMap::new() })
+    }
+
+    /// Add GraphQL `ProjectV2Item` nodes whose issue is in `repository` (`owner/name`)
+    pub fn add_items(&mut self, nodes: &[Json], repository: &str) {
+        for node in nodes {
+            let content = &node["content"];
+            let in_repository = content["repository"]["nameWithOwner"]
+                .as_str()
+                .is_some_and(|r| r.eq_ignore_ascii_case(repository));
+            let (Some(number), Some(id), true) = (content["number"].as_u64(), node["id"].as_str(), in_repository) else {
+                continue;
+            };
+
+            let values = node["fieldValues"]["nodes"]
+                .as_array()
+                .map(|values| values.iter().filter_map(|v| self.parse_value(v)).collect())
+                .unwrap_or_default();
+
+            self.items.insert(number, ProjectItem { id: id.to_string(), values });
+        }
+    }
+
+    /// Key and value of a GraphQL field value node, for fields the board syncs
+    fn parse_value(&self, node: &Json) -> Option<(String, Value)> {
+        let key = field_key(node["field"]["name"].as_str()?);
+        self.fields.iter().find(|f| field_key(&f.name) == key)?;
+
+        let value = if let Some(number) = node["number"].as_f64() {
+            number_value(number)
+        } else {
+            ["name", "title", "text", "date"]
+                .iter()
+                .find_map(|k| node[*k].as_str())
+                .map(Value::from)?
+        };
+        Some((key, value))
+    }
+
+    /// Field for a frontmatter key
+    pub fn field(&self, key: &str) -> Result<&ProjectField> {
+        let key = field_key(key);
+        self.fields.iter().find(|f| field_key(&f.name) == key).with_context(|| {
+            let names: Vec<String> = self.fields.iter().map(|f| field_key(&f.name)).collect();
+            format!("Project {} has no field `{}` (fields: {})", self.number, key, names.join(", "))
+        })
+    }
+}
+
+impl ProjectField {
+    /// GraphQL `ProjectV2FieldValue` for a frontmatter value
+    pub fn input(&self, value: &Value, today: NaiveDate) -> Result<Json> {
+        let text = scalar(value).with_context(|| format!("`{}` must be a single value", field_key(&self.name)))?;
+
+        match &self.kind {
+            FieldKind::SingleSelect(options) => options
+                .iter()
+                .find(|(_, name)| name.eq_ignore_ascii_case(&text))
+                .map(|(id, _)| json!({ "singleSelectOptionId": id }))
+                .with_context(|| {
+                    let names: Vec<&str> = options.iter().map(|(_, name)| name.as_str()).collect();
+                    format!("`{}` has no option `{}` (options: {})", self.name, text, names.join(", "))
+                }),
+            FieldKind::Iteration(iterations) => {
+                let iteration = match text.to_lowercase().as_str() {
+                    "current" => iterations
+                        .iter()
+                        .find(|i| i.start_date <= today && today < i.start_date + Duration::days(i.duration)),
+                    "next" => iterations
+                        .iter()
+                        .filter(|i| i.start_date > today)
+                        .min_by_key(|i| i.start_date),
+                    _ => iterations.iter().find(|i| i.title.eq_ignore_ascii_case(&text)),
+                };
+                iteration
+                    .map(|i| json!({ "iterationId": i.id }))
+                    .with_context(|| format!("`{}` has no iteration `{}`", self.name, text))
+            }
+            FieldKind::Number => text
+                .parse::<f64>()
+                .map(|n| json!({ "number": n }))
+                .with_context(|| format!("`{}` must be a number, not `{}`", self.name, text)),
+            FieldKind::Text => Ok(json!({ "text": text })),
+            FieldKind::Date => NaiveDate::parse_from_str(&text, "%Y-%m-%d")
+                .map(|_| json!({ "date": text }))
+                .with_context(|| format!("`{}` must be a YYYY-MM-DD date, not `{}`", self.name, text)),
+        }
+    }
+}
+
+/// A GraphQL `ProjectV2FieldConfiguration` node, if the field is synced
+fn parse_field(node: &Json) -> Option<ProjectField> {
+    let id = node["id"].as_str()?.to_string();
+    let name = node["name"].as_str()?.to_string();
+
+    let kind = match node["dataType"].as_str()? {
+        "SINGLE_SELECT" => FieldKind::SingleSelect(
+            node["options"]
+                .as_array()?
+                .iter()
+                .filter_map(|o| Some((o["id"].as_str()?.to_string(), o["name"].as_str()?.to_string())))
+                .collect(),
+        ),
+        "ITERATION" => {
+            let configuration = &node["configuration"];
+            let all = ["iterations", "completedIterations"]
+                .iter()
+                .filter_map(|k| configuration[*k].as_array())
+                .flatten();
+            FieldKind::Iteration(
+                all.filter_map(|i| {
+                    Some(Iteration {
+                        id: i["id"].as_str()?.to_string(),
+                        title: i["title"].as_str()?.to_string(),
+                        start_date: NaiveDate::parse_from_str(i["startDate"].as_str()?, "%Y-%m-%d").ok()?,
+                        duration: i["duration"].as_i64()?,
+                    })
+                })
+                .collect(),
+            )
+        }
+        "NUMBER" => FieldKind::Number,
+        "TEXT" => FieldKind::Text,
+        "DATE" => FieldKind::Date,
+        _ => return None,
+    };
+
+    Some(ProjectField { id, name, kind })
+}
+
+/// Whole numbers as integers, so `3` stays `3` in the file
+fn number_value(number: f64) -> Value {
+    if number.fract() == 0.0 && number.abs() < i64::MAX as f64 {
+        Value::from(number as i64)
+    } else {
+        Value::from(number)
+    }
+}
+
+fn scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board() -> Project {
+        let node = json!({
+            "id": "PVT_1",
+            "fields": { "nodes": [
+                { "id": "F_title", "name": "Title", "dataType": "TITLE" },
+                { "id": "F_status", "name": "Status", "dataType": "SINGLE_SELECT",
+                  "options": [{ "id": "O_todo", "name": "Todo" }, { "id": "O_wip", "name": "In Progress" }] },
+                { "id": "F_sprint", "name": "Iteration", "dataType": "ITERATION",
+                  "configuration": {
+                      "iterations": [
+                          { "id": "I_2", "title": "Sprint 2", "startDate": "2025-01-13", "duration": 14 },
+                          { "id": "I_3", "title": "Sprint 3", "startDate": "2025-01-27", "duration": 14 }
+                      ],
+                      "completedIterations": [
+                          { "id": "I_1", "title": "Sprint 1", "startDate": "2024-12-30", "duration": 14 }
+                      ]
+                  } },
+                { "id": "F_points", "name": "Story Points", "dataType": "NUMBER" },
+                { "id": "F_due", "name": "Due", "dataType": "DATE" }
+            ] }
+        });
+        Project::from_graphql(3, &node).unwrap()
+    }
+
+    #[test]
+    fn test_field_key() {
+        assert_eq!(field_key("Story Points"), "story_points");
+        assert_eq!(field_key(" Status "), "status");
+        assert_eq!(field_key("story_points"), "story_points");
+    }
+
+    #[test]
+    fn test_items() {
+        let mut project = board();
+        assert_eq!(project.fields.len(), 4);
+
+        let items = [
+            json!({
+                "id": "PVTI_1",
+                "content": { "number": 42, "repository": { "nameWithOwner": "owner/repo" } },
+                "fieldValues": { "nodes": [
+                    { "text": "Add login", "field": { "name": "Title" } },
+                    { "name": "In Progress", "field": { "name": "Status" } },
+                    { "title": "Sprint 2", "field": { "name": "Iteration" } },
+                    { "number": 3.0, "field": { "name": "Story Points" } },
+                    {}
+                ] }
+            }),
+            json!({
+                "id": "PVTI_2",
+                "content": { "number": 7, "repository": { "nameWithOwner": "owner/other" } },
+                "fieldValues": { "nodes": [] }
+            }),
+        ];
+        project.add_items(&items, "Owner/Repo");
+
+        assert_eq!(project.items.len(), 1);
+        let values = &project.items[&42].values;
+        assert_eq!(values.len(), 3);
+        assert_eq!(values["status"], Value::from("In Progress"));
+        assert_eq!(values["iteration"], Value::from("Sprint 2"));
+        assert_eq!(values["story_points"], Value::from(3));
+    }
+
+    #[test]
+    fn test_field_input() {
+        let project = board();
+        let today = NaiveDate::from_ymd_opt(2025, 1, 20).unwrap();
+        let input = |key: &str, value: Value| project.field(key).unwrap().input(&value, today);
+
+        assert_eq!(input("status", "in progress".into()).unwrap(), json!({ "singleSelectOptionId": "O_wip" }));
+        assert_eq!(input("iteration", "current".into()).unwrap(), json!({ "iterationId": "I_2" }));
+        assert_eq!(input("iteration", "next".into()).unwrap(), json!({ "iterationId": "I_3" }));
+        assert_eq!(input("iteration", "Sprint 1".into()).unwrap(), json!({ "iterationId": "I_1" }));
+        assert_eq!(input("Story Points", 5.into()).unwrap(), json!({ "number": 5.0 }));
+        assert_eq!(input("due", "2025-02-01".into()).unwrap(), json!({ "date": "2025-02-01" }));
+
+        assert!(input("status", "Blocked".into()).is_err());
+        assert!(input("due", "soon".into()).is_err());
+        let error = project.field("priority").unwrap_err().to_string();
+        assert!(error.contains("no field `priority`"), "{}", error);
+    }
+}
@@ -0,0 +1,227 @@
+//! Rate Limiting
+//!
+//! Paces the GitHub API requests of concurrent sync jobs. Every response
+//! updates one shared limiter:
+//!
+//! - `x-ratelimit-remaining` / `x-ratelimit-reset`: once only a reserve of
+//!   requests is left, requests wait for the window to reset
+//! - `retry-after`, or a 403/429 about a secondary rate limit: all requests
+//!   pause, and the gap between requests doubles
+//!
+//! Rate-limited requests are retried with exponential backoff and jitter.
+//! The gap shrinks again while requests go through.
+
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Retries of a rate-limited request before it fails
+pub const MAX_RETRIES: u32 = 5;
+
+/// Requests of the primary limit left for other clients of the token
+const RESERVE: u64 = 10;
+
+/// Backoff of the first retry without `retry-after`, and its upper bound
+const BASE_BACKOFF: Duration = Duration::from_secs(2);
+const MAX_BACKOFF: Duration = Duration::from_secs(120);
+
+/// Gap between requests after a secondary limit, and its upper bound
+const MIN_SPACING: Duration = Duration::from_secs(1);
+const MAX_SPACING: Duration = Duration::from_secs(16);
+
+/// Rate limit headers of a response
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimitHeaders {
+    pub remaining: Option<u64>,
+    /// Unix time the window resets at
+    pub reset: Option<u64>,
+    /// Seconds to wait before retrying
+    pub retry_after: Option<u64>,
+}
+
+impl RateLimitHeaders {
+    /// Read the headers with `get(name)`
+    pub fn parse<'a>(get: impl Fn(&str) -> Option<&'a str>) -> Self {
+        let number = |name: &str| get(name).and_then(|v| v.trim().parse().ok());
+        Self {
+            remaining: number("x-ratelimit-remaining"),
+            reset: number("x-ratelimit-reset"),
+            retry_after: number("retry-after"),
+        }
+    }
+}
+
+/// Whether a 403/429 response is a rate limit rather than a permission error
+pub fn is_rate_limited(status: u16, headers: &RateLimitHeaders, body: &str) -> bool {
+    status == 429
+        || headers.retry_after.is_some()
+        || headers.remaining == Some(0)
+        || body.to_lowercase().contains("rate limit")
+}
+
+/// Request pacing shared by all clones of a `GitHubClient`
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    /// Requests left in the current window (counted down locally)
+    remaining: Option<u64>,
+    reset_at: Option<Instant>,
+    /// No requests before this, after a rate-limited response
+    paused_until: Option<Instant>,
+    /// Gap between requests
+    spacing: Duration,
+    next_at: Option<Instant>,
+}
+
+impl RateLimiter {
+    /// Wait until a request may be sent
+    pub async fn wait(&self) {
+        loop {
+            let delay = self.state().delay(Instant::now());
+            match delay {
+                Some(delay) => tokio::time::sleep(delay).await,
+                None => return,
+            }
+        }
+    }
+
+    /// Record the headers of a response
+    ///
+    /// If it was `limited`, all requests pause and the wait before retrying
+    /// is returned.
+    pub fn observe(&self, headers: &RateLimitHeaders, limited: bool, attempt: u32) -> Option<Duration> {
+        let unix_now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        self.state().observe(Instant::now(), unix_now, headers, limited, attempt)
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl State {
+    /// How long a request sent `now` must wait; `None` takes the slot
+    fn delay(&mut self, now: Instant) -> Option<Duration> {
+        if let Some(until) = self.paused_until.filter(|until| *until > now) {
+            return Some(until - now);
+        }
+
+        match self.reset_at {
+            Some(reset) if reset <= now => {
+                self.remaining = None;
+                self.reset_at = None;
+            }
+            Some(reset) if self.remaining.is_some_and(|r| r <= RESERVE) => return Some(reset - now),
+            _ => {}
+        }
+
+        if let Some(next) = self.next_at.filter(|next| *next > now) {
+            return Some(next - now);
+        }
+
+        self.next_at = Some(now + self.spacing);
+        self.remaining = self.remaining.map(|r| r.saturating_sub(1));
+        None
+    }
+
+    fn observe(
+        &mut self,
+        now: Instant,
+        unix_now: u64,
+        headers: &RateLimitHeaders,
+        limited: bool,
+        attempt: u32,
+    ) -> Option<Duration> {
+        if let Some(remaining) = headers.remaining {
+            // Responses of concurrent requests arrive out of order
+            self.remaining = Some(self.remaining.map_or(remaining, |r| r.min(remaining)));
+        }
+        if let Some(reset) = headers.reset {
+            self.reset_at = Some(now + Duration::from_secs(reset.saturating_sub(unix_now)));
+        }
+
+        if !limited {
+            self.spacing = self.spacing.mul_f64(0.9);
+            if self.spacing < Duration::from_millis(50) {
+                self.spacing = Duration::ZERO;
+            }
+            return None;
+        }
+
+        let wait = match (headers.retry_after, self.remaining, self.reset_at) {
+            (Some(seconds), _, _) => Duration::from_secs(seconds),
+            (None, Some(0), Some(reset)) => reset.saturating_duration_since(now),
+            _ => BASE_BACKOFF.saturating_mul(2u32.saturating_pow(attempt)).min(MAX_BACKOFF),
+        };
+        // Jitter, so paused requests do not resume in lockstep
+        let wait = wait.mul_f64(1.0 + fastrand::f64() * 0.25);
+
+        self.spacing = (self.spacing * 2).clamp(MIN_SPACING, MAX_SPACING);
+        self.paused_until = Some(self.paused_until.map_or(now + wait, |until| until.max(now + wait)));
+        Some(wait)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_headers() {
+        let headers = RateLimitHeaders::parse(|name| match name {
+            "x-ratelimit-remaining" => Some("42"),
+            "x-ratelimit-reset" => Some("1700000000"),
+            _ => None,
+        });
+        assert_eq!(headers, RateLimitHeaders { remaining: Some(42), reset: Some(1_700_000_000), retry_after: None });
+
+        assert!(is_rate_limited(403, &headers, "You have exceeded a secondary rate limit"));
+        assert!(!is_rate_limited(403, &headers, "Resource not accessible by integration"));
+        assert!(is_rate_limited(429, &headers, ""));
+    }
+
+    #[test]
+    fn test_waits_for_reset_near_the_limit() {
+        let mut state = State::default();
+        let now = Instant::now();
+        let headers = RateLimitHeaders { remaining: Some(RESERVE + 1), reset: Some(1_060), retry_after: None };
+
+        assert_eq!(state.observe(now, 1_000, &headers, false, 0), None);
+        assert_eq!(state.delay(now), None);
+        assert_eq!(state.delay(now), Some(Duration::from_secs(60)));
+
+        // A new window
+        assert_eq!(state.delay(now + Duration::from_secs(60)), None);
+        assert_eq!(state.remaining, None);
+    }
+
+    #[test]
+    fn test_backs_off_on_secondary_limits() {
+        let mut state = State::default();
+        let now = Instant::now();
+        let limited = RateLimitHeaders { retry_after: Some(30), ..Default::default() };
+
+        let wait = state.observe(now, 0, &limited, true, 0).unwrap();
+        assert!(wait >= Duration::from_secs(30) && wait <= Duration::from_millis(37_500), "{:?}", wait);
+        assert_eq!(state.delay(now), Some(wait));
+        assert_eq!(state.spacing, MIN_SPACING);
+
+        // Without `retry-after`, exponential backoff
+        let wait = state.observe(now, 0, &RateLimitHeaders::default(), true, 2).unwrap();
+        assert!(wait >= Duration::from_secs(8) && wait <= Duration::from_secs(10), "{:?}", wait);
+        assert_eq!(state.spacing, MIN_SPACING * 2);
+
+        // Requests are spaced out after the pause, and the gap shrinks again
+        let later = now + Duration::from_secs(60);
+        assert_eq!(state.delay(later), None);
+        assert_eq!(state.delay(later), Some(MIN_SPACING * 2));
+        state.observe(later, 0, &RateLimitHeaders::default(), false, 0);
+        assert!(state.spacing < MIN_SPACING * 2);
+    }
+}
@@ -3,13 +3,61 @@
 //! Bidirectional sync between `.github/issues/*.md` files and GitHub Issues.
 
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use octocrab::models::issues::Issue;
+use futures::stream::{self, StreamExt};
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use tracing::{info, warn};
 use walkdir::WalkDir;
 
+use crate::doctor::{diagnose, similarity, Diagnosis, Finding, LocalFile, RENAME_SIMILARITY};
+use crate::comments::{is_sidecar, sidecar_path, CommentBlock, CommentMode, CommentThread};
 use crate::github::GitHubClient;
-use crate::mapping::IssueMapping;
-use crate::parser::{parse_issue_file, IssueData};
+use crate::mapping::{content_hash, FieldChanges, IssueMapping, MappingEntry};
+use crate::merge::{has_conflict_markers, three_way, ConflictStrategy, MergeResult};
+use crate::import::{import_filename, ImportFilter, StateFilter};
+use crate::projects::{Project, ProjectValues};
+use crate::parser::{
+    comments_section, parse_frontmatter, render_issue_file, set_comments_section, update_issue_file, Field, IssueData,
+    IssueState,
+};
+
+/// Directory under the issues directory that holds archived files
+pub const ARCHIVE_DIR: &str = "archive";
+
+/// Issues created or updated at the same time by default
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Synced files between saves of the mapping during a push
+const CHECKPOINT_INTERVAL: usize = 10;
+
+/// What happens to the local file of a closed issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClosePolicy {
+    /// Delete the file
+    #[default]
+    Delete,
+    /// Move it to `archive/YYYY-MM/` with the close date and reason
+    Archive,
+    /// Keep it in place with `state: closed`
+    Mark,
+}
+
+impl std::str::FromStr for ClosePolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "delete" => Ok(ClosePolicy::Delete),
+            "archive" => Ok(ClosePolicy::Archive),
+            "mark" => Ok(ClosePolicy::Mark),
+            _ => Err(anyhow::anyhow!("Invalid close policy: {} (expected delete, archive or mark)", s)),
+        }
+    }
+}
 
 /// Sync report with statistics
 #[derive(Debug, Default)]
@@ -17,13 +65,36 @@ pub struct SyncReport {
     pub created: usize,
     pub updated: usize,
     pub deleted: usize,
+    /// Local files of closed issues moved to the archive
+    pub archived: usize,
+    /// Local files rewritten with remote changes
+    pub pulled: usize,
     pub skipped: usize,
+    /// Remote issues written to new local files
+    pub imported: usize,
+    /// Files with fields changed both locally and on GitHub
+    pub conflicts: usize,
+    /// Comments posted or mirrored
+    pub comments: usize,
     pub errors: usize,
 }
 
 impl SyncReport {
     pub fn total_operations(&self) -> usize {
-        self.created + self.updated + self.deleted
+        self.created + self.updated + self.deleted + self.archived + self.pulled + self.imported
+    }
+
+    fn merge(&mut self, other: SyncReport) {
+        self.created += other.created;
+        self.updated += other.updated;
+        self.deleted += other.deleted;
+        self.archived += other.archived;
+        self.pulled += other.pulled;
+        self.skipped += other.skipped;
+        self.imported += other.imported;
+        self.conflicts += other.conflicts;
+        self.comments += other.comments;
+        self.errors += other.errors;
     }
 }
 
@@ -34,6 +105,15 @@ pub struct IssueSyncer {
     mapping_file: PathBuf,
     mapping: IssueMapping,
     dry_run: bool,
+    conflict_strategy: ConflictStrategy,
+    close_policy: ClosePolicy,
+    comment_mode: CommentMode,
+    /// Projects (v2) board synced with the `project` key
+    project: Option<u64>,
+    /// Issues created or updated at the same time during a push
+    concurrency: usize,
+    /// Set to stop a push early (see `with_cancel`)
+    cancel: Arc<AtomicBool>,
 }
 
 impl IssueSyncer {
@@ -57,6 +137,12 @@ impl IssueSyncer {
             mapping_file,
             mapping,
             dry_run: false,
+            conflict_strategy: ConflictStrategy::default(),
+            close_policy: ClosePolicy::default(),
+            comment_mode: CommentMode::default(),
+            project: None,
+            concurrency: DEFAULT_CONCURRENCY,
+            cancel: Arc::default(),
         })
     }
 
@@ -66,7 +152,46 @@ impl IssueSyncer {
         self
     }
 
-    /// Sync all: push local changes + pull closed issues
+    /// How to resolve fields changed both locally and on GitHub
+    pub fn with_conflict_strategy(mut self, strategy: ConflictStrategy) -> Self {
+        self.conflict_strategy = strategy;
+        self
+    }
+
+    /// What to do with local files of closed issues
+    pub fn with_close_policy(mut self, policy: ClosePolicy) -> Self {
+        self.close_policy = policy;
+        self
+    }
+
+    /// Where issue comments are mirrored
+    pub fn with_comment_mode(mut self, mode: CommentMode) -> Self {
+        self.comment_mode = mode;
+        self
+    }
+
+    /// Projects (v2) board (owner project number) synced with the `project` key
+    pub fn with_project(mut self, project: Option<u64>) -> Self {
+        self.project = project;
+        self
+    }
+
+    /// How many issues a push creates or updates at the same time (at least 1)
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Flag that stops a push once set, e.g. from a Ctrl-C handler
+    ///
+    /// Jobs not started yet are dropped and the requests in flight finish;
+    /// the mapping is saved and the push fails, so a rerun resumes it.
+    pub fn with_cancel(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// Sync all: three-way merge of mapped files + pull closed issues
     pub async fn sync_all(&mut self) -> Result<SyncReport> {
         info!("Starting bidirectional sync");
 
@@ -77,17 +202,44 @@ impl IssueSyncer {
             created: push_report.created,
             updated: push_report.updated,
             deleted: pull_report.deleted,
+            archived: pull_report.archived,
+            pulled: push_report.pulled + pull_report.pulled,
             skipped: push_report.skipped + pull_report.skipped,
+            imported: 0,
+            conflicts: push_report.conflicts,
+            comments: push_report.comments,
             errors: push_report.errors + pull_report.errors,
         })
     }
 
     /// Push: Sync local .md files to GitHub Issues
+    ///
+    /// Mapped files are merged three-way with their issue: local edits are
+    /// pushed, remote edits are written to the file, and fields edited on
+    /// both sides are resolved with the conflict strategy.
+    ///
+    /// With a comment mode, comments are then mirrored for every mapped file
+    /// whose issue changed or that has new local comments.
+    ///
+    /// Issues are created and updated concurrently (see `with_concurrency`).
+    /// The mapping is saved every few files, and a cancelled push (see
+    /// `with_cancel`) lets the requests in flight finish, so it resumes
+    /// where it stopped.
     pub async fn push(&mut self) -> Result<SyncReport> {
         info!("Pushing local files to GitHub");
 
         let mut report = SyncReport::default();
         let files = self.scan_issue_files()?;
+        let remote_changes = self.fetch_remote_changes().await?;
+        let board = match self.project {
+            Some(number) if !self.dry_run => Some(self.github.fetch_project(number).await?),
+            _ => None,
+        };
+        // (file, issue, changed on GitHub) to mirror comments for
+        let mut comment_files: Vec<(String, u64, bool)> = Vec::new();
+        // Issue → the file pushed for it
+        let mut claimed: HashMap<u64, String> = HashMap::new();
+        let mut jobs = Vec::new();
 
         for file_path in files {
             let filename = file_path
@@ -101,7 +253,15 @@ impl IssueSyncer {
             }
 
             // Parse file
-            let issue_data = match parse_issue_file(&file_path) {
+            let content = match std::fs::read_to_string(&file_path) {
+                Ok(content) => content,
+                Err(e) => {
+                    warn!("Failed to read {}: {}", filename, e);
+                    report.errors += 1;
+                    continue;
+                }
+            };
+            let issue_data = match parse_frontmatter(&content) {
                 Ok(data) => data,
                 Err(e) => {
                     warn!("Failed to parse {}: {}", filename, e);
@@ -110,41 +270,129 @@ impl IssueSyncer {
                 }
             };
 
-            // Check if mapped
-            if let Some(issue_number) = self.mapping.get_issue(filename) {
-                // Update existing issue
-                if !self.dry_run {
-                    match self.github.update_issue(issue_number, &issue_data).await {
-                        Ok(_) => {
-                            info!("Updated issue #{} from {}", issue_number, filename);
-                            report.updated += 1;
-                        }
-                        Err(e) => {
-                            warn!("Failed to update issue #{}: {}", issue_number, e);
-                            report.errors += 1;
+            if !issue_data.unknown.is_empty() {
+                let keys: Vec<&str> = issue_data.unknown.keys().map(String::as_str).collect();
+                warn!("{}: unknown frontmatter keys ignored: {}", filename, keys.join(", "));
+            }
+
+            let hash = content_hash(&content);
+
+            // The frontmatter `number` binds the file to its issue; a renamed
+            // file takes over the sync state of its old name
+            let entry = match (self.mapping.get_entry(filename).cloned(), issue_data.number) {
+                (Some(entry), Some(number)) if entry.issue == number => Some(entry),
+                (entry, Some(number)) => {
+                    if let Some(entry) = entry {
+                        warn!("{} is mapped to issue #{} but its frontmatter says #{}, using #{}",
+                            filename, entry.issue, number, number);
+                    }
+                    match self.mapping.get_file(number) {
+                        Some(old) if old != filename && !self.issues_dir.join(&old).exists() => {
+                            info!("{} was renamed to {} (issue #{})", old, filename, number);
+                            self.mapping.rename(&old, filename.to_string());
+                            self.mapping.get_entry(filename).cloned()
                         }
+                        _ => Some(MappingEntry::new(number)),
                     }
-                } else {
-                    info!("[DRY RUN] Would update issue #{}", issue_number);
-                    report.updated += 1;
                 }
-            } else {
-                // Create new issue
-                if !self.dry_run {
-                    match self.github.create_issue(&issue_data).await {
-                        Ok(number) => {
-                            info!("Created issue #{} from {}", number, filename);
-                            self.mapping.add(filename.to_string(), number);
-                            report.created += 1;
-                        }
-                        Err(e) => {
-                            warn!("Failed to create issue from {}: {}", filename, e);
-                            report.errors += 1;
-                        }
+                (entry, None) => entry,
+            };
+
+            if let Some(entry) = &entry {
+                if let Some(other) = claimed.get(&entry.issue) {
+                    warn!("{} and {} are both bound to issue #{}, skipping {} (run `doctor`)",
+                        other, filename, entry.issue, filename);
+                    report.errors += 1;
+                    continue;
+                }
+                claimed.insert(entry.issue, filename.to_string());
+            } else if let Some((old, issue)) = self.renamed_from(&issue_data) {
+                // Creating an issue would duplicate the one of the old name
+                warn!("{} looks like {} renamed (issue #{}), skipping; add `number: {}` or run `doctor --fix`",
+                    filename, old, issue, issue);
+                report.skipped += 1;
+                continue;
+            }
+
+            // Check if mapped
+            if let Some(entry) = entry {
+                let remote_issue = remote_changes.get(&entry.issue).cloned();
+                comment_files.push((filename.to_string(), entry.issue, remote_issue.is_some()));
+
+                // Board fields are not part of the issue's `updated_at`
+                let board_changed = board.as_ref().is_some_and(|board| {
+                    let base = entry.base.as_ref().map(|b| &b.project);
+                    match board.items.get(&entry.issue) {
+                        Some(item) => base != Some(&item.values) && !(base.is_none() && item.values.is_empty()),
+                        None => !issue_data.project.is_empty(),
                     }
-                } else {
-                    info!("[DRY RUN] Would create issue from {}", filename);
-                    report.created += 1;
+                });
+
+                // Unchanged on both sides since the last sync
+                if entry.hash.as_deref() == Some(hash.as_str()) && remote_issue.is_none() && !board_changed {
+                    report.skipped += 1;
+                    continue;
+                }
+
+                jobs.push(PushJob {
+                    filename: filename.to_string(),
+                    local: issue_data,
+                    content,
+                    hash,
+                    entry: Some(entry),
+                    remote_issue,
+                });
+            } else {
+                jobs.push(PushJob {
+                    filename: filename.to_string(),
+                    local: issue_data,
+                    content,
+                    hash,
+                    entry: None,
+                    remote_issue: None,
+                });
+            }
+        }
+
+        // Run the jobs concurrently and apply their outcomes as they finish
+        let worker = PushWorker {
+            github: &self.github,
+            issues_dir: &self.issues_dir,
+            dry_run: self.dry_run,
+            conflict_strategy: self.conflict_strategy,
+            board: board.as_ref(),
+            cancel: &self.cancel,
+        };
+        let worker = &worker;
+        let mut outcomes = stream::iter(jobs)
+            .map(|job| worker.run(job))
+            .buffer_unordered(self.concurrency);
+        let mut synced = 0;
+
+        while let Some(outcome) = outcomes.next().await {
+            report.merge(outcome.report);
+            comment_files.extend(outcome.comments);
+            if let Some(record) = outcome.record {
+                self.mapping.record_sync(record.file, record.issue, record.hash, record.base, record.remote_updated_at);
+                synced += 1;
+                if synced % CHECKPOINT_INTERVAL == 0 && !self.dry_run {
+                    self.mapping
+                        .save(&self.mapping_file)
+                        .context("Failed to save issue mapping")?;
+                }
+            }
+        }
+        drop(outcomes);
+
+        if self.comment_mode != CommentMode::Off {
+            comment_files.sort();
+            for (filename, issue_number, remote_changed) in comment_files {
+                if self.is_cancelled() {
+                    break;
+                }
+                if let Err(e) = self.sync_comments(&filename, issue_number, remote_changed, &mut report).await {
+                    warn!("Failed to sync comments of {} (issue #{}): {:#}", filename, issue_number, e);
+                    report.errors += 1;
                 }
             }
         }
@@ -154,10 +402,137 @@ impl IssueSyncer {
             self.save_mapping()?;
         }
 
+        if self.is_cancelled() {
+            anyhow::bail!("Push interrupted after {} file(s); run it again to resume", synced);
+        }
+
         Ok(report)
     }
 
-    /// Pull: Delete local files for closed issues
+    /// Post local comments of a file and mirror the issue's comments into it
+    ///
+    /// Only comments edited since the last comment sync are fetched; they
+    /// are merged into the local thread by id.
+    async fn sync_comments(
+        &mut self,
+        filename: &str,
+        issue_number: u64,
+        remote_changed: bool,
+        report: &mut SyncReport,
+    ) -> Result<()> {
+        let file_path = self.issues_dir.join(filename);
+        let sidecar = sidecar_path(&file_path);
+
+        let (mut thread, content) = match self.comment_mode {
+            CommentMode::Off => return Ok(()),
+            CommentMode::Sidecar => {
+                let thread = match std::fs::read_to_string(&sidecar) {
+                    Ok(text) => CommentThread::parse(&text),
+                    Err(_) => CommentThread::default(),
+                };
+                (thread, None)
+            }
+            CommentMode::Section => {
+                let content = std::fs::read_to_string(&file_path)
+                    .with_context(|| format!("Failed to read {}", filename))?;
+                let thread = comments_section(&content)
+                    .map(CommentThread::from_section)
+                    .unwrap_or_default();
+                (thread, Some(content))
+            }
+        };
+
+        let synced_at = self
+            .mapping
+            .get_entry(filename)
+            .and_then(|entry| entry.comments_synced_at.as_deref())
+            .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+            .map(|t| t.with_timezone(&Utc));
+        let pending = thread.pending().count();
+
+        if pending == 0 && !remote_changed && synced_at.is_some() {
+            return Ok(());
+        }
+
+        if self.dry_run {
+            if pending > 0 {
+                info!("[DRY RUN] Would post {} comment(s) from {} on issue #{}", pending, filename, issue_number);
+                report.comments += pending;
+            }
+            return Ok(());
+        }
+
+        let started = Utc::now();
+        let mut changed = false;
+
+        for block in thread.blocks.iter_mut().filter(|b| b.id.is_none()) {
+            match self.github.create_comment(issue_number, &block.body).await {
+                Ok(comment) => {
+                    info!("Posted comment {} on issue #{} from {}", comment.id, issue_number, filename);
+                    *block = CommentBlock::from(&comment);
+                    report.comments += 1;
+                    changed = true;
+                }
+                Err(e) => {
+                    // Keep the ids of the comments already posted, so a retry does not post them again
+                    if changed {
+                        if let Some(hash) = self.write_thread(filename, issue_number, &thread, content.as_deref())? {
+                            self.mapping.set_hash(filename, hash);
+                        }
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        for comment in self.github.fetch_comments(issue_number, synced_at).await? {
+            if thread.merge(CommentBlock::from(&comment)) {
+                report.comments += 1;
+                changed = true;
+            }
+        }
+
+        let mut hash = None;
+        if changed || (content.is_none() && !sidecar.exists() && !thread.blocks.is_empty()) {
+            hash = self.write_thread(filename, issue_number, &thread, content.as_deref())?;
+        }
+        if changed {
+            info!("Mirrored comments of issue #{} into {}", issue_number, filename);
+        }
+
+        self.mapping.record_comments(filename, started.to_rfc3339(), hash);
+        Ok(())
+    }
+
+    /// Write a comment thread to the sidecar, or into the section of `content`
+    ///
+    /// Returns the new hash of a section file that matched its issue.
+    fn write_thread(
+        &self,
+        filename: &str,
+        issue_number: u64,
+        thread: &CommentThread,
+        content: Option<&str>,
+    ) -> Result<Option<String>> {
+        let file_path = self.issues_dir.join(filename);
+
+        let Some(content) = content else {
+            let sidecar = sidecar_path(&file_path);
+            std::fs::write(&sidecar, thread.render_sidecar(issue_number))
+                .with_context(|| format!("Failed to write {}", sidecar.display()))?;
+            return Ok(None);
+        };
+
+        let updated = set_comments_section(content, &thread.render_section())?;
+        std::fs::write(&file_path, &updated)
+            .with_context(|| format!("Failed to write {}", filename))?;
+        // The section is not synced, so a file that matched its issue still does
+        let in_sync = self.mapping.get_entry(filename).and_then(|e| e.hash.as_deref())
+            == Some(content_hash(content).as_str());
+        Ok(in_sync.then(|| content_hash(&updated)))
+    }
+
+    /// Pull: Apply the close policy to local files of closed issues
     pub async fn pull(&mut self) -> Result<SyncReport> {
         info!("Pulling closed issues from GitHub");
 
@@ -169,26 +544,313 @@ impl IssueSyncer {
         };
 
         for issue in closed_issues {
+            let Some(filename) = self.mapping.get_file(issue.number) else {
+                continue;
+            };
+            let file_path = self.issues_dir.join(&filename);
+            if !file_path.exists() {
+                continue;
+            }
+
+            if let Err(e) = self.close_file(&filename, &issue, &mut report) {
+                warn!("Failed to close {} for issue #{}: {:#}", filename, issue.number, e);
+                report.errors += 1;
+            }
+        }
+
+        // Save mapping if not dry run
+        if !self.dry_run {
+            self.save_mapping()?;
+        }
+
+        Ok(report)
+    }
+
+    /// Delete, archive or mark the file of a closed issue
+    fn close_file(&mut self, filename: &str, issue: &Issue, report: &mut SyncReport) -> Result<()> {
+        let file_path = self.issues_dir.join(filename);
+
+        if self.close_policy == ClosePolicy::Delete {
+            if self.dry_run {
+                info!("[DRY RUN] Would delete {} for closed issue #{}", filename, issue.number);
+            } else {
+                std::fs::remove_file(&file_path)
+                    .with_context(|| format!("Failed to delete {}", filename))?;
+                let sidecar = sidecar_path(&file_path);
+                if sidecar.exists() {
+                    std::fs::remove_file(&sidecar)
+                        .with_context(|| format!("Failed to delete {}", sidecar.display()))?;
+                }
+                info!("Deleted {} for closed issue #{}", filename, issue.number);
+                self.mapping.remove_by_issue(issue.number);
+            }
+            report.deleted += 1;
+            return Ok(());
+        }
+
+        let content = std::fs::read_to_string(&file_path)
+            .with_context(|| format!("Failed to read {}", filename))?;
+        let local = parse_frontmatter(&content)?;
+
+        // Close state, reason and date from GitHub; the notes stay
+        let remote = IssueData::from(issue);
+        let mut closed = local.clone();
+        closed.take(&remote, Field::State);
+        closed.closed_at = remote.closed_at.clone();
+        closed.number = Some(issue.number);
+
+        if self.close_policy == ClosePolicy::Mark {
+            if local.state == closed.state && local.closed_at == closed.closed_at {
+                report.skipped += 1;
+                return Ok(());
+            }
+            if self.dry_run {
+                info!("[DRY RUN] Would mark {} closed (issue #{})", filename, issue.number);
+            } else {
+                let updated = update_issue_file(&content, &closed)?;
+                std::fs::write(&file_path, &updated)
+                    .with_context(|| format!("Failed to write {}", filename))?;
+                info!("Marked {} closed (issue #{})", filename, issue.number);
+
+                let mut base = self
+                    .mapping
+                    .get_entry(filename)
+                    .and_then(|e| e.base.clone())
+                    .unwrap_or_else(|| remote.clone());
+                base.take(&remote, Field::State);
+                self.mapping.record_sync(
+                    filename.to_string(),
+                    issue.number,
+                    content_hash(&updated),
+                    base,
+                    Some(issue.updated_at.to_rfc3339()),
+                );
+            }
+            report.pulled += 1;
+            return Ok(());
+        }
+
+        let closed_at = issue.closed_at.unwrap_or_else(Utc::now);
+        let archive_dir = self.issues_dir.join(ARCHIVE_DIR).join(closed_at.format("%Y-%m").to_string());
+        let destination = archive_dir.join(filename);
+        if destination.exists() {
+            anyhow::bail!("{} already exists", destination.display());
+        }
+
+        if self.dry_run {
+            info!("[DRY RUN] Would archive {} to {}", filename, destination.display());
+        } else {
+            std::fs::create_dir_all(&archive_dir)
+                .with_context(|| format!("Failed to create {}", archive_dir.display()))?;
+            std::fs::write(&destination, update_issue_file(&content, &closed)?)
+                .with_context(|| format!("Failed to write {}", destination.display()))?;
+            std::fs::remove_file(&file_path)
+                .with_context(|| format!("Failed to remove {}", filename))?;
+            let sidecar = sidecar_path(&file_path);
+            if sidecar.exists() {
+                std::fs::rename(&sidecar, sidecar_path(&destination))
+                    .with_context(|| format!("Failed to archive {}", sidecar.display()))?;
+            }
+            info!("Archived {} to {} (issue #{} closed)", filename, destination.display(), issue.number);
+            self.mapping.remove_by_issue(issue.number);
+        }
+        report.archived += 1;
+        Ok(())
+    }
+
+    /// Restore: Reopen the issue of an archived file and move the file back
+    ///
+    /// `file` is a path to the archived file, or its name within the archive.
+    pub async fn restore(&mut self, file: &Path) -> Result<PathBuf> {
+        let archived = self.find_archived(file)?;
+        let filename = archived
+            .file_name()
+            .and_then(|n| n.to_str())
+            .context("Invalid archived file name")?
+            .to_string();
+
+        let destination = self.issues_dir.join(&filename);
+        if destination.exists() {
+            anyhow::bail!("{} already exists", destination.display());
+        }
+
+        let content = std::fs::read_to_string(&archived)
+            .with_context(|| format!("Failed to read {}", archived.display()))?;
+        let archived_data = parse_frontmatter(&content)
+            .with_context(|| format!("Failed to parse {}", archived.display()))?;
+        let number = archived_data
+            .number
+            .with_context(|| format!("{} has no issue `number` in its frontmatter", filename))?;
+
+        let mut reopened = archived_data.clone();
+        reopened.state = IssueState::Open;
+        reopened.state_reason = None;
+        reopened.closed_at = None;
+
+        if self.dry_run {
+            info!("[DRY RUN] Would reopen issue #{} and restore {}", number, filename);
+            return Ok(destination);
+        }
+
+        let state = FieldChanges { state: true, ..Default::default() };
+        let issue = self
+            .github
+            .update_issue(number, &reopened, &state, &archived_data)
+            .await
+            .with_context(|| format!("Failed to reopen issue #{}", number))?;
+        info!("Reopened issue #{}", number);
+
+        let restored = update_issue_file(&content, &reopened)?;
+        std::fs::write(&destination, &restored)
+            .with_context(|| format!("Failed to write {}", destination.display()))?;
+        std::fs::remove_file(&archived)
+            .with_context(|| format!("Failed to remove {}", archived.display()))?;
+        let sidecar = sidecar_path(&archived);
+        if sidecar.exists() {
+            std::fs::rename(&sidecar, sidecar_path(&destination))
+                .with_context(|| format!("Failed to restore {}", sidecar.display()))?;
+        }
+
+        self.mapping.record_sync(
+            filename,
+            number,
+            content_hash(&restored),
+            IssueData::from(&issue),
+            Some(issue.updated_at.to_rfc3339()),
+        );
+        self.save_mapping()?;
+
+        Ok(destination)
+    }
+
+    /// An archived file by path, or by name under `archive/*/`
+    fn find_archived(&self, file: &Path) -> Result<PathBuf> {
+        if file.is_file() {
+            return Ok(file.to_path_buf());
+        }
+
+        let archive = self.issues_dir.join(ARCHIVE_DIR);
+        let name = file.file_name().context("Invalid file name")?;
+        let mut matches: Vec<PathBuf> = WalkDir::new(&archive)
+            .min_depth(2)
+            .max_depth(2)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_name() == name)
+            .map(|e| e.into_path())
+            .collect();
+
+        match matches.len() {
+            0 => anyhow::bail!("{} not found in {}", file.display(), archive.display()),
+            1 => Ok(matches.remove(0)),
+            _ => anyhow::bail!(
+                "{} is archived more than once, pass the path: {}",
+                file.display(),
+                matches.iter().map(|p| p.display().to_string()).collect::<Vec<_>>().join(", ")
+            ),
+        }
+    }
+
+    /// Import: Write local files for remote issues that are not mapped yet
+    ///
+    /// With `comments > 0`, that many of the most recent comments are
+    /// written to each file's sidecar in sidecar mode, or appended to the
+    /// file as a comments section otherwise.
+    ///
+    /// Closed issues are only imported with the `archive` close policy,
+    /// straight into the archive, or `mark`; with `delete`, the next pull
+    /// would delete their files again.
+    pub async fn import(&mut self, filter: &ImportFilter, comments: usize) -> Result<SyncReport> {
+        if filter.state != StateFilter::Open && self.close_policy == ClosePolicy::Delete {
+            anyhow::bail!(
+                "Importing closed issues needs --on-close archive or mark; with delete, the next pull deletes them"
+            );
+        }
+
+        info!("Importing issues from GitHub");
+
+        let mut report = SyncReport::default();
+        let issues = self.github.fetch_issues(filter).await?;
+
+        let mut taken: HashSet<String> = self
+            .scan_issue_files()?
+            .iter()
+            .filter_map(|path| path.file_name().and_then(|n| n.to_str()).map(str::to_string))
+            .collect();
+        taken.extend(self.mapping.files());
+
+        for issue in issues {
             if let Some(filename) = self.mapping.get_file(issue.number) {
-                let file_path = self.issues_dir.join(&filename);
-
-                if file_path.exists() {
-                    if !self.dry_run {
-                        match std::fs::remove_file(&file_path) {
-                            Ok(_) => {
-                                info!("Deleted {} for closed issue #{}", filename, issue.number);
-                                self.mapping.remove_by_issue(issue.number);
-                                report.deleted += 1;
-                            }
-                            Err(e) => {
-                                warn!("Failed to delete {}: {}", filename, e);
-                                report.errors += 1;
-                            }
+                info!("Issue #{} already mapped to {}, skipping", issue.number, filename);
+                report.skipped += 1;
+                continue;
+            }
+
+            let data = IssueData::from(&issue);
+            let filename = import_filename(issue.number, &data.title, &data.labels, &taken);
+            taken.insert(filename.clone());
+
+            if self.dry_run {
+                info!("[DRY RUN] Would import issue #{} as {}", issue.number, filename);
+                report.imported += 1;
+                continue;
+            }
+
+            let mut content = match render_issue_file(&data) {
+                Ok(content) => content,
+                Err(e) => {
+                    warn!("Failed to render issue #{}: {:#}", issue.number, e);
+                    report.errors += 1;
+                    continue;
+                }
+            };
+
+            let mut thread = CommentThread::default();
+            if comments > 0 {
+                match self.github.fetch_comments(issue.number, None).await {
+                    Ok(all) => {
+                        for comment in &all[all.len().saturating_sub(comments)..] {
+                            thread.merge(CommentBlock::from(comment));
                         }
-                    } else {
-                        info!("[DRY RUN] Would delete {} for closed issue #{}", filename, issue.number);
-                        report.deleted += 1;
                     }
+                    Err(e) => warn!("Failed to fetch comments of issue #{}: {}", issue.number, e),
+                }
+            }
+
+            if !thread.blocks.is_empty() {
+                if self.comment_mode == CommentMode::Sidecar {
+                    let sidecar = sidecar_path(&self.issues_dir.join(&filename));
+                    if let Err(e) = std::fs::write(&sidecar, thread.render_sidecar(issue.number)) {
+                        warn!("Failed to write {}: {}", sidecar.display(), e);
+                        report.errors += 1;
+                    }
+                } else {
+                    content.push('\n');
+                    content.push_str(&thread.render_section());
+                    content.push('\n');
+                }
+            }
+
+            if let Err(e) = std::fs::write(self.issues_dir.join(&filename), &content) {
+                warn!("Failed to write {}: {}", filename, e);
+                report.errors += 1;
+                continue;
+            }
+
+            info!("Imported issue #{} as {}", issue.number, filename);
+            self.mapping.record_sync(
+                filename.clone(),
+                issue.number,
+                content_hash(&content),
+                data,
+                Some(issue.updated_at.to_rfc3339()),
+            );
+            report.imported += 1;
+
+            if issue.state == octocrab::models::IssueState::Closed && self.close_policy == ClosePolicy::Archive {
+                if let Err(e) = self.close_file(&filename, &issue, &mut report) {
+                    warn!("Failed to archive {} for issue #{}: {:#}", filename, issue.number, e);
+                    report.errors += 1;
                 }
             }
         }
@@ -201,22 +863,193 @@ impl IssueSyncer {
         Ok(report)
     }
 
+    /// Mapped issues edited on GitHub since their last sync
+    async fn fetch_remote_changes(&self) -> Result<HashMap<u64, Issue>> {
+        if self.dry_run || self.mapping.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        // Oldest sync point; everything when a mapping predates snapshots
+        let since = self
+            .mapping
+            .entries()
+            .map(|(_, entry)| {
+                entry
+                    .remote_updated_at
+                    .as_deref()
+                    .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+                    .map(|t| t.with_timezone(&Utc))
+            })
+            .collect::<Option<Vec<_>>>()
+            .and_then(|times| times.into_iter().min());
+
+        let issues = self.github.fetch_issues_updated_since(since).await?;
+
+        Ok(issues
+            .into_iter()
+            .filter(|issue| {
+                let file = self.mapping.get_file(issue.number);
+                let entry = file.as_deref().and_then(|f| self.mapping.get_entry(f));
+                entry.is_some_and(|e| e.remote_updated_at.as_deref() != Some(issue.updated_at.to_rfc3339().as_str()))
+            })
+            .map(|issue| (issue.number, issue))
+            .collect())
+    }
+
+    /// Mapped file that is gone and whose last synced content is like `data`
+    fn renamed_from(&self, data: &IssueData) -> Option<(String, u64)> {
+        self.mapping
+            .entries()
+            .filter(|(file, _)| !self.issues_dir.join(file).exists())
+            .filter_map(|(file, entry)| Some((file, entry.issue, similarity(entry.base.as_ref()?, data))))
+            .filter(|(_, _, score)| *score >= RENAME_SIMILARITY)
+            .max_by(|a, b| a.2.total_cmp(&b.2))
+            .map(|(file, issue, _)| (file.clone(), issue))
+    }
+
     /// Scan issues directory for .md files
+    ///
+    /// Only the top level is synced; files in subdirectories are reported
+    /// by `doctor`.
     fn scan_issue_files(&self) -> Result<Vec<PathBuf>> {
-        let mut files = Vec::new();
-
-        for entry in WalkDir::new(&self.issues_dir)
+        let mut files: Vec<PathBuf> = WalkDir::new(&self.issues_dir)
             .max_depth(1)
             .into_iter()
             .filter_map(|e| e.ok())
-        {
-            let path = entry.path();
-            if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("md") {
-                files.push(path.to_path_buf());
+            .map(|e| e.into_path())
+            .filter(|path| is_issue_file(path))
+            .collect();
+
+        files.sort();
+        Ok(files)
+    }
+
+    /// Issue files below subdirectories, except the archive
+    fn scan_nested_files(&self) -> Vec<PathBuf> {
+        // `filter_entry` does not see entries above `min_depth`, so depth is checked after
+        let mut files: Vec<PathBuf> = WalkDir::new(&self.issues_dir)
+            .into_iter()
+            .filter_entry(|e| !(e.depth() == 1 && e.file_name() == ARCHIVE_DIR))
+            .filter_map(|e| e.ok())
+            .filter(|e| e.depth() >= 2)
+            .map(|e| e.into_path())
+            .filter(|path| is_issue_file(path))
+            .collect();
+
+        files.sort();
+        files
+    }
+
+    /// Doctor: Check files, mapping and open issues; with `fix`, repair what can be
+    pub async fn doctor(&mut self, fix: bool) -> Result<Vec<Diagnosis>> {
+        info!("Checking issue files and mapping");
+
+        let mut files = Vec::new();
+        let mut unparseable = Vec::new();
+        for path in self.scan_issue_files()? {
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("unknown").to_string();
+            let parsed = std::fs::read_to_string(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|content| parse_frontmatter(&content));
+            match parsed {
+                Ok(data) => files.push(LocalFile { name, data }),
+                Err(e) => unparseable.push((name, format!("{:#}", e))),
             }
         }
+        unparseable.sort();
 
-        Ok(files)
+        let remote: Vec<(u64, String)> = if self.dry_run {
+            vec![]
+        } else {
+            self.github
+                .fetch_open_issues()
+                .await?
+                .into_iter()
+                .map(|issue| (issue.number, issue.title))
+                .collect()
+        };
+
+        let findings = diagnose(&files, &unparseable, &self.mapping, &remote, &self.scan_nested_files());
+
+        let mut diagnoses = Vec::new();
+        for finding in findings {
+            let fixed = fix && !self.dry_run && finding.is_fixable() && match self.fix(&finding) {
+                Ok(()) => true,
+                Err(e) => {
+                    warn!("Could not fix: {}: {:#}", finding, e);
+                    false
+                }
+            };
+            diagnoses.push(Diagnosis { finding, fixed });
+        }
+
+        if fix && !self.dry_run {
+            self.save_mapping()?;
+        }
+
+        Ok(diagnoses)
+    }
+
+    fn fix(&mut self, finding: &Finding) -> Result<()> {
+        match finding {
+            Finding::Renamed { from, to, issue } => {
+                self.mapping.rename(from, to.clone());
+                let (old, new) = (sidecar_path(&self.issues_dir.join(from)), sidecar_path(&self.issues_dir.join(to)));
+                if old.exists() && !new.exists() {
+                    std::fs::rename(&old, &new).with_context(|| format!("Failed to rename {}", old.display()))?;
+                }
+                self.embed_number(to, *issue)
+            }
+            Finding::Orphaned { file, .. } => {
+                self.mapping.remove_by_file(file);
+                Ok(())
+            }
+            Finding::MissingNumber { file, issue } => self.embed_number(file, *issue),
+            Finding::NumberMismatch { file, number, .. } => {
+                self.mapping.add(file.clone(), *number);
+                Ok(())
+            }
+            Finding::Nested { path } => {
+                let name = path.file_name().context("Invalid file name")?;
+                let destination = self.issues_dir.join(name);
+                if destination.exists() {
+                    anyhow::bail!("{} already exists", destination.display());
+                }
+                std::fs::rename(path, &destination)
+                    .with_context(|| format!("Failed to move {}", path.display()))?;
+                if sidecar_path(path).exists() && !sidecar_path(&destination).exists() {
+                    std::fs::rename(sidecar_path(path), sidecar_path(&destination))
+                        .with_context(|| format!("Failed to move the comments of {}", path.display()))?;
+                }
+                Ok(())
+            }
+            Finding::SharedNumber { .. } | Finding::DuplicateTitle { .. } | Finding::Unparseable { .. } => {
+                anyhow::bail!("needs a manual fix")
+            }
+        }
+    }
+
+    /// Write `number` into a file; an otherwise unchanged file stays in sync
+    fn embed_number(&mut self, filename: &str, number: u64) -> Result<()> {
+        let content = std::fs::read_to_string(self.issues_dir.join(filename))
+            .with_context(|| format!("Failed to read {}", filename))?;
+        let mut data = parse_frontmatter(&content)?;
+        if data.number == Some(number) {
+            return Ok(());
+        }
+
+        data.number = Some(number);
+        let updated = write_issue_file(&self.issues_dir, filename, &content, &data)?;
+
+        let hash = self.mapping.get_entry(filename).and_then(|e| e.hash.clone());
+        if hash == Some(content_hash(&content)) {
+            self.mapping.set_hash(filename, content_hash(&updated));
+        }
+        Ok(())
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
     }
 
     /// Save mapping to disk
@@ -231,6 +1064,293 @@ impl IssueSyncer {
     }
 }
 
+/// A file to create or sync an issue for during a push
+struct PushJob {
+    filename: String,
+    local: IssueData,
+    content: String,
+    hash: String,
+    /// Sync state of a mapped file; `None` creates the issue
+    entry: Option<MappingEntry>,
+    /// The issue, if it changed on GitHub since the last sync
+    remote_issue: Option<Issue>,
+}
+
+/// Sync state of a file to record in the mapping
+struct SyncRecord {
+    file: String,
+    issue: u64,
+    hash: String,
+    base: IssueData,
+    remote_updated_at: Option<String>,
+}
+
+/// What a push job did, applied to the mapping and report once it finishes
+#[derive(Default)]
+struct JobOutcome {
+    report: SyncReport,
+    record: Option<SyncRecord>,
+    /// (file, issue, changed on GitHub) to mirror comments for
+    comments: Option<(String, u64, bool)>,
+}
+
+/// The part of the syncer push jobs need, shared by the jobs in flight
+struct PushWorker<'a> {
+    github: &'a GitHubClient,
+    issues_dir: &'a Path,
+    dry_run: bool,
+    conflict_strategy: ConflictStrategy,
+    board: Option<&'a Project>,
+    /// Once set, jobs not started yet are dropped
+    cancel: &'a AtomicBool,
+}
+
+impl PushWorker<'_> {
+    async fn run(&self, job: PushJob) -> JobOutcome {
+        let mut outcome = JobOutcome::default();
+        if self.cancel.load(Ordering::Relaxed) {
+            return outcome;
+        }
+
+        let PushJob { filename, local, content, hash, entry, remote_issue } = job;
+        match entry {
+            Some(entry) => {
+                outcome.record = self
+                    .sync_mapped_file(&filename, &entry, local, &content, remote_issue.as_ref(), &mut outcome.report)
+                    .await;
+            }
+            None => self.create(&filename, local, &content, hash, &mut outcome).await,
+        }
+        outcome
+    }
+
+    /// Create the issue of an unmapped file and write its number into the file
+    async fn create(&self, filename: &str, data: IssueData, content: &str, hash: String, outcome: &mut JobOutcome) {
+        if self.dry_run {
+            info!("[DRY RUN] Would create issue from {}", filename);
+            outcome.report.created += 1;
+            return;
+        }
+
+        let issue = match self.github.create_issue(&data).await {
+            Ok(issue) => issue,
+            Err(e) => {
+                warn!("Failed to create issue from {}: {}", filename, e);
+                outcome.report.errors += 1;
+                return;
+            }
+        };
+        info!("Created issue #{} from {}", issue.number, filename);
+        outcome.comments = Some((filename.to_string(), issue.number, true));
+        let mut base = data;
+        base.number = Some(issue.number);
+
+        // The file records its issue from now on
+        let hash = match write_issue_file(self.issues_dir, filename, content, &base) {
+            Ok(updated) => content_hash(&updated),
+            Err(e) => {
+                warn!("Failed to add `number: {}` to {}: {:#}", issue.number, filename, e);
+                hash
+            }
+        };
+
+        if let Some(board) = self.board.filter(|_| !base.project.is_empty()) {
+            let none = ProjectValues::new();
+            if let Err(e) = self.github.update_project_fields(board, &issue, &base.project, &none).await {
+                warn!("Failed to set project fields of issue #{}: {:#}", issue.number, e);
+                outcome.report.errors += 1;
+                // Pushed again on the next sync
+                base.project.clear();
+            }
+        }
+
+        outcome.record = Some(SyncRecord {
+            file: filename.to_string(),
+            issue: issue.number,
+            hash,
+            base,
+            remote_updated_at: Some(issue.updated_at.to_rfc3339()),
+        });
+        outcome.report.created += 1;
+    }
+
+    /// Three-way merge of a mapped file with its issue, then push and/or rewrite the file
+    ///
+    /// Returns the new sync state of the file, if it was synced.
+    async fn sync_mapped_file(
+        &self,
+        filename: &str,
+        entry: &MappingEntry,
+        local: IssueData,
+        content: &str,
+        remote_issue: Option<&Issue>,
+        report: &mut SyncReport,
+    ) -> Option<SyncRecord> {
+        let issue_number = entry.issue;
+        if has_conflict_markers(&local.body) {
+            warn!("{} has unresolved conflict markers, skipping issue #{}", filename, issue_number);
+            report.conflicts += 1;
+            return None;
+        }
+
+        // Without a base, fetch the issue so only real differences are pushed
+        let fetched = match remote_issue {
+            None if entry.base.is_none() && !self.dry_run => self.github.get_issue(issue_number).await.ok(),
+            _ => None,
+        };
+        let remote_issue = remote_issue.or(fetched.as_ref());
+
+        // Remote as it is now: fetched if it changed, otherwise the base.
+        // Push-only fields cannot be read back, so they keep their base value.
+        let mut remote = match (remote_issue, &entry.base) {
+            (Some(issue), base) => {
+                let mut remote = IssueData::from(issue);
+                if let Some(base) = base {
+                    for field in Field::ALL.into_iter().filter(|f| f.is_push_only()) {
+                        remote.take(base, field);
+                    }
+                    remote.project = base.project.clone();
+                }
+                Some(remote)
+            }
+            (None, base) => base.clone(),
+        };
+
+        // Board fields as they are now; an issue not on the board yet gets
+        // all of its local values
+        let board = self.board;
+        let mut base = entry.base.clone();
+        if let Some(board) = board {
+            let item = board.items.get(&issue_number);
+            if let Some(remote) = remote.as_mut() {
+                remote.project = item.map(|i| i.values.clone()).unwrap_or_default();
+            }
+            if let (None, Some(base)) = (item, base.as_mut()) {
+                base.project.clear();
+            }
+        }
+
+        let merge = match &remote {
+            Some(remote) => three_way(base.as_ref(), &local, remote, self.conflict_strategy),
+            // Mapped before snapshots and not edited remotely: local wins
+            None => MergeResult {
+                local: local.clone(),
+                push: FieldChanges::all(),
+                write_local: false,
+                conflicts: vec![],
+            },
+        };
+        let push = merge.push;
+
+        if !merge.conflicts.is_empty() {
+            warn!(
+                "Conflict in {} (issue #{}): {} changed on both sides, resolved with {:?}",
+                filename, issue_number, merge.conflicts.join(", "), self.conflict_strategy
+            );
+            report.conflicts += 1;
+        }
+
+        if self.dry_run {
+            if !push.is_empty() {
+                info!("[DRY RUN] Would update issue #{}", issue_number);
+                report.updated += 1;
+            }
+            if merge.write_local {
+                info!("[DRY RUN] Would update {} from issue #{}", filename, issue_number);
+                report.pulled += 1;
+            }
+            if push.is_empty() && !merge.write_local {
+                report.skipped += 1;
+            }
+            return None;
+        }
+
+        let mut remote_updated_at = remote_issue
+            .map(|issue| issue.updated_at.to_rfc3339())
+            .or_else(|| entry.remote_updated_at.clone());
+
+        if !push.is_empty() {
+            let current = remote.clone().unwrap_or_default();
+            let updated = async {
+                let issue = self.github.update_issue(issue_number, &merge.local, &push, &current).await?;
+                if push.project {
+                    match board {
+                        Some(board) => {
+                            self.github
+                                .update_project_fields(board, &issue, &merge.local.project, &current.project)
+                                .await?
+                        }
+                        None => warn!("{}: `project` changed but no --project board is set", filename),
+                    }
+                }
+                Ok::<_, anyhow::Error>(issue)
+            };
+            match updated.await {
+                Ok(issue) => {
+                    info!("Updated issue #{} from {}", issue_number, filename);
+                    remote_updated_at = Some(issue.updated_at.to_rfc3339());
+                    report.updated += 1;
+                }
+                Err(e) => {
+                    warn!("Failed to update issue #{}: {}", issue_number, e);
+                    report.errors += 1;
+                    return None;
+                }
+            }
+        }
+
+        // Files without `number` get it, so renames keep their issue
+        let mut written = merge.local.clone();
+        written.number = Some(issue_number);
+
+        let mut hash = content_hash(content);
+        if merge.write_local || written.number != local.number {
+            // Only the changed keys and body are rewritten
+            match write_issue_file(self.issues_dir, filename, content, &written) {
+                Ok(content) => {
+                    hash = content_hash(&content);
+                    if merge.write_local {
+                        info!("Updated {} from issue #{}", filename, issue_number);
+                        report.pulled += 1;
+                    }
+                }
+                Err(e) => {
+                    warn!("{:#}", e);
+                    report.errors += 1;
+                    return None;
+                }
+            }
+        }
+
+        if push.is_empty() && !merge.write_local {
+            report.skipped += 1;
+        }
+
+        // The new base is what GitHub holds now
+        let base = match &remote {
+            Some(remote) => push.apply(&merge.local, remote),
+            None => merge.local,
+        };
+        Some(SyncRecord { file: filename.to_string(), issue: issue_number, hash, base, remote_updated_at })
+    }
+}
+
+/// Rewrite the changed keys and body of an issue file, returning the new content
+fn write_issue_file(issues_dir: &Path, filename: &str, content: &str, data: &IssueData) -> Result<String> {
+    let updated = update_issue_file(content, data)?;
+    std::fs::write(issues_dir.join(filename), &updated)
+        .with_context(|| format!("Failed to write {}", filename))?;
+    Ok(updated)
+}
+
+/// A top-level `.md` issue file: not hidden and not a comments sidecar
+fn is_issue_file(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    path.is_file() && name.ends_with(".md") && !name.starts_with('.') && !is_sidecar(name)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -267,6 +1387,286 @@ mod tests {
         assert!(files.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_push_skips_unchanged_files() {
+        let (mut syncer, _temp) = create_test_syncer();
+
+        let content = "---\ntitle: Test\n---\nBody";
+        std::fs::write(syncer.issues_dir.join("FEAT_same.md"), content).unwrap();
+        std::fs::write(syncer.issues_dir.join("FEAT_edited.md"), content).unwrap();
+
+        let base = parse_frontmatter(content).unwrap();
+        syncer.mapping.record_sync("FEAT_same.md".to_string(), 1, content_hash(content), base.clone(), None);
+        syncer.mapping.record_sync("FEAT_edited.md".to_string(), 2, content_hash("old"), base, None);
+        std::fs::write(syncer.issues_dir.join("FEAT_edited.md"), "---\ntitle: Test\n---\nNew body").unwrap();
+
+        let report = syncer.push().await.unwrap();
+        assert_eq!(report.skipped, 1);
+        assert_eq!(report.updated, 1);
+        assert_eq!(report.created, 0);
+    }
+
+    #[tokio::test]
+    async fn test_push_runs_jobs_concurrently() {
+        let (syncer, _temp) = create_test_syncer();
+        let mut syncer = syncer.with_concurrency(3);
+
+        for i in 0..7 {
+            let content = format!("---\ntitle: Task {}\n---\nBody", i);
+            std::fs::write(syncer.issues_dir.join(format!("TASK_{}.md", i)), content).unwrap();
+        }
+        for i in 0..2 {
+            let filename = format!("FEAT_{}.md", i);
+            let base = parse_frontmatter("---\ntitle: Feature\n---\nOld").unwrap();
+            syncer.mapping.record_sync(filename.clone(), 10 + i, content_hash("old"), base, None);
+            std::fs::write(syncer.issues_dir.join(&filename), "---\ntitle: Feature\n---\nNew").unwrap();
+        }
+
+        let report = syncer.push().await.unwrap();
+        assert_eq!(report.created, 7);
+        assert_eq!(report.updated, 2);
+        assert_eq!(report.errors, 0);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_push_starts_no_jobs() {
+        let (syncer, _temp) = create_test_syncer();
+        let cancel = Arc::new(AtomicBool::new(true));
+        let mut syncer = syncer.with_cancel(Arc::clone(&cancel));
+
+        std::fs::write(syncer.issues_dir.join("TASK_a.md"), "---\ntitle: A\n---\nBody").unwrap();
+
+        let error = syncer.push().await.unwrap_err().to_string();
+        assert!(error.contains("interrupted after 0 file(s)"), "{}", error);
+
+        cancel.store(false, Ordering::Relaxed);
+        assert_eq!(syncer.push().await.unwrap().created, 1);
+    }
+
+    #[tokio::test]
+    async fn test_push_counts_pending_sidecar_comments() {
+        let (syncer, _temp) = create_test_syncer();
+        let mut syncer = syncer.with_comment_mode(CommentMode::Sidecar);
+
+        let content = "---\ntitle: Test\n---\nBody";
+        std::fs::write(syncer.issues_dir.join("FEAT_x.md"), content).unwrap();
+        std::fs::write(
+            syncer.issues_dir.join("FEAT_x.comments.md"),
+            "# Comments on #5\n\n<!-- comment id=1 author=octocat -->\nFirst\n\n<!-- comment -->\nPlease rerun CI\n",
+        )
+        .unwrap();
+        syncer.mapping.record_sync("FEAT_x.md".to_string(), 5, content_hash(content), parse_frontmatter(content).unwrap(), None);
+        syncer.mapping.record_comments("FEAT_x.md", "2025-01-05T10:00:00+00:00".to_string(), None);
+
+        let report = syncer.push().await.unwrap();
+        assert_eq!(report.comments, 1);
+        assert_eq!(report.skipped, 1);
+        // The sidecar is not an issue file
+        assert_eq!(report.created, 0);
+    }
+
+    #[tokio::test]
+    async fn test_comment_ids_are_kept_when_a_post_fails() {
+        let mut server = mockito::Server::new_async().await;
+        let url = "https://api.github.com/x";
+        let posted = server
+            .mock("POST", "/repos/owner/repo/issues/5/comments")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({ "body": "First" })))
+            .with_body(
+                serde_json::json!({
+                    "id": 11, "node_id": "C", "url": url, "html_url": url, "body": "First",
+                    "user": octocat(), "created_at": "2025-01-05T10:00:00Z",
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let failed = server
+            .mock("POST", "/repos/owner/repo/issues/5/comments")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({ "body": "Second" })))
+            .with_status(500)
+            .with_body(r#"{"message": "Server Error"}"#)
+            // octocrab retries server errors
+            .expect_at_least(1)
+            .create_async()
+            .await;
+
+        let (syncer, _temp) = create_test_syncer();
+        let client = Octocrab::builder().base_uri(server.url()).unwrap().build().unwrap();
+        let mut syncer = IssueSyncer::new(
+            GitHubClient::new(client, "owner".to_string(), "repo".to_string()),
+            syncer.issues_dir.clone(),
+            syncer.mapping_file.clone(),
+        )
+        .unwrap()
+        .with_comment_mode(CommentMode::Sidecar);
+
+        let content = "---\ntitle: Test\n---\nBody";
+        std::fs::write(syncer.issues_dir.join("FEAT_x.md"), content).unwrap();
+        std::fs::write(
+            syncer.issues_dir.join("FEAT_x.comments.md"),
+            "# Comments on #5\n\n<!-- comment -->\nFirst\n\n<!-- comment -->\nSecond\n",
+        )
+        .unwrap();
+        syncer.mapping.record_sync("FEAT_x.md".to_string(), 5, content_hash(content), parse_frontmatter(content).unwrap(), None);
+
+        let mut report = SyncReport::default();
+        assert!(syncer.sync_comments("FEAT_x.md", 5, false, &mut report).await.is_err());
+        posted.assert_async().await;
+        failed.assert_async().await;
+
+        let thread = CommentThread::parse(&std::fs::read_to_string(syncer.issues_dir.join("FEAT_x.comments.md")).unwrap());
+        let ids: Vec<Option<u64>> = thread.blocks.iter().map(|b| b.id).collect();
+        assert_eq!(ids, [Some(11), None]);
+        assert_eq!(report.comments, 1);
+        // Not a completed sync
+        assert!(syncer.mapping().get_entry("FEAT_x.md").unwrap().comments_synced_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_push_follows_renamed_files() {
+        let (mut syncer, _temp) = create_test_syncer();
+
+        let numbered = "---\ntitle: Renamed\nnumber: 3\n---\nBody";
+        let plain = "---\ntitle: Add OAuth login\n---\nSupport Google and GitHub providers";
+        syncer.mapping.record_sync("FEAT_old.md".to_string(), 3, content_hash(numbered), parse_frontmatter(numbered).unwrap(), None);
+        syncer.mapping.record_sync("FEAT_login.md".to_string(), 4, content_hash(plain), parse_frontmatter(plain).unwrap(), None);
+
+        // Renamed with its number, and renamed without one
+        std::fs::write(syncer.issues_dir.join("FEAT_new.md"), numbered).unwrap();
+        std::fs::write(syncer.issues_dir.join("FEAT_sign-in.md"), plain).unwrap();
+
+        let report = syncer.push().await.unwrap();
+        assert_eq!(report.created, 0);
+        assert_eq!(report.skipped, 2);
+        assert_eq!(syncer.mapping.get_file(3).as_deref(), Some("FEAT_new.md"));
+        assert_eq!(syncer.mapping.get_file(4).as_deref(), Some("FEAT_login.md"));
+    }
+
+    #[tokio::test]
+    async fn test_doctor_reports_nested_and_renamed_files() {
+        let (mut syncer, _temp) = create_test_syncer();
+
+        let content = "---\ntitle: T\nnumber: 3\n---\nBody";
+        syncer.mapping.add("FEAT_old.md".to_string(), 3);
+        std::fs::write(syncer.issues_dir.join("FEAT_new.md"), content).unwrap();
+        // Mapped, but its frontmatter is broken: not an orphan
+        syncer.mapping.add("BUG_broken.md".to_string(), 4);
+        std::fs::write(syncer.issues_dir.join("BUG_broken.md"), "---\ntitle: [\n---\nBody").unwrap();
+        for dir in ["drafts", "archive/2025-01"] {
+            std::fs::create_dir_all(syncer.issues_dir.join(dir)).unwrap();
+            std::fs::write(syncer.issues_dir.join(dir).join("FEAT_x.md"), content).unwrap();
+        }
+
+        let diagnoses = syncer.doctor(true).await.unwrap();
+        let findings: Vec<Finding> = diagnoses.iter().map(|d| d.finding.clone()).collect();
+        assert!(
+            matches!(&findings[0], Finding::Unparseable { file, .. } if file == "BUG_broken.md"),
+            "{:?}",
+            findings
+        );
+        assert_eq!(
+            findings[1..],
+            [
+                Finding::Renamed { from: "FEAT_old.md".to_string(), to: "FEAT_new.md".to_string(), issue: 3 },
+                Finding::Nested { path: syncer.issues_dir.join("drafts/FEAT_x.md") },
+            ]
+        );
+        // Dry run: nothing is fixed
+        assert!(diagnoses.iter().all(|d| !d.fixed));
+    }
+
+    #[tokio::test]
+    async fn test_push_skips_files_with_conflict_markers() {
+        let (mut syncer, _temp) = create_test_syncer();
+
+        let content = "---\ntitle: Test\n---\n<<<<<<< local\nmine\n=======\ntheirs\n>>>>>>> remote";
+        std::fs::write(syncer.issues_dir.join("FEAT_conflict.md"), content).unwrap();
+        syncer.mapping.add("FEAT_conflict.md".to_string(), 7);
+
+        let report = syncer.push().await.unwrap();
+        assert_eq!(report.conflicts, 1);
+        assert_eq!(report.updated, 0);
+    }
+
+    fn octocat() -> serde_json::Value {
+        let url = "https://api.github.com/x";
+        serde_json::json!({
+            "login": "octocat", "id": 1, "node_id": "U", "avatar_url": url, "gravatar_id": "",
+            "url": url, "html_url": url, "followers_url": url, "following_url": url, "gists_url": url,
+            "starred_url": url, "subscriptions_url": url, "organizations_url": url, "repos_url": url,
+            "events_url": url, "received_events_url": url, "type": "User", "site_admin": false,
+        })
+    }
+
+    fn closed_issue(number: u64) -> Issue {
+        let url = "https://api.github.com/x";
+        let user = octocat();
+        serde_json::from_value(serde_json::json!({
+            "id": number, "node_id": "I", "url": url, "repository_url": url, "labels_url": url,
+            "comments_url": url, "events_url": url, "html_url": url, "number": number,
+            "state": "closed", "state_reason": "not_planned", "title": "Remote title", "body": "Remote",
+            "user": user, "labels": [], "assignees": [], "author_association": "OWNER",
+            "locked": false, "comments": 0, "closed_at": "2025-01-05T10:00:00Z",
+            "created_at": "2025-01-01T10:00:00Z", "updated_at": "2025-01-05T10:00:00Z",
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_close_policy_archive() {
+        let (syncer, _temp) = create_test_syncer();
+        let mut syncer = syncer.with_dry_run(false).with_close_policy(ClosePolicy::Archive);
+
+        let content = "---\ntitle: Local title # my notes\n---\n\nLocal notes\n";
+        std::fs::write(syncer.issues_dir.join("FEAT_done.md"), content).unwrap();
+        syncer.mapping.add("FEAT_done.md".to_string(), 5);
+
+        let mut report = SyncReport::default();
+        syncer.close_file("FEAT_done.md", &closed_issue(5), &mut report).unwrap();
+
+        let archived = syncer.issues_dir.join("archive/2025-01/FEAT_done.md");
+        assert_eq!(report.archived, 1);
+        assert!(!syncer.issues_dir.join("FEAT_done.md").exists());
+        assert!(syncer.mapping().is_empty());
+        assert_eq!(
+            std::fs::read_to_string(&archived).unwrap(),
+            "---\ntitle: Local title # my notes\nnumber: 5\nstate: closed\nstate_reason: not_planned\nclosed_at: 2025-01-05\n---\n\nLocal notes\n"
+        );
+
+        assert_eq!(syncer.find_archived(Path::new("FEAT_done.md")).unwrap(), archived);
+        assert!(syncer.find_archived(Path::new("FEAT_other.md")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_close_policy_mark() {
+        let (syncer, _temp) = create_test_syncer();
+        let mut syncer = syncer.with_dry_run(false).with_close_policy(ClosePolicy::Mark);
+
+        std::fs::write(syncer.issues_dir.join("BUG_x.md"), "---\ntitle: X\n---\nBody").unwrap();
+        syncer.mapping.add("BUG_x.md".to_string(), 6);
+
+        let mut report = SyncReport::default();
+        syncer.close_file("BUG_x.md", &closed_issue(6), &mut report).unwrap();
+        syncer.close_file("BUG_x.md", &closed_issue(6), &mut report).unwrap();
+
+        assert_eq!((report.pulled, report.skipped), (1, 1));
+        let marked = parse_frontmatter(&std::fs::read_to_string(syncer.issues_dir.join("BUG_x.md")).unwrap()).unwrap();
+        assert_eq!(marked.state, IssueState::Closed);
+        assert_eq!(marked.title, "X");
+        assert_eq!(syncer.mapping().get_issue("BUG_x.md"), Some(6));
+        assert!("shred".parse::<ClosePolicy>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_import_closed_needs_keeping_close_policy() {
+        let (mut syncer, _temp) = create_test_syncer();
+        let filter = ImportFilter { state: StateFilter::All, ..Default::default() };
+
+        let err = syncer.import(&filter, 0).await.unwrap_err();
+        assert!(err.to_string().contains("--on-close"), "{}", err);
+    }
+
     #[tokio::test]
     async fn test_scan_with_files() {
         let (syncer, _temp) = create_test_syncer();